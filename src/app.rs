@@ -4,6 +4,7 @@
 
 use crate::commands;
 use crate::logging;
+use crate::transcription::OutputFormat;
 use anyhow::anyhow;
 use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::{generate, Shell};
@@ -95,6 +96,14 @@ struct Cli {
     #[arg(short = 'p', long = "process", value_name = "ACTION", num_args = 0..=1, default_missing_value = "")]
     process: Option<String>,
 
+    /// Spoken language as an ISO-639-1 code, e.g. "en" or "sv" (record default command)
+    #[arg(short, long, value_name = "CODE")]
+    language: Option<String>,
+
+    /// Translate the speech to English (record default command)
+    #[arg(long)]
+    translate: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -118,6 +127,14 @@ enum Commands {
         /// Enable processing after transcription. Optionally specify action ID to skip picker.
         #[arg(short = 'p', long = "process", value_name = "ACTION", num_args = 0..=1, default_missing_value = "")]
         process: Option<String>,
+
+        /// Spoken language as an ISO-639-1 code, e.g. "en" or "sv" ("auto" to detect; overrides [transcription] language)
+        #[arg(short, long, value_name = "CODE")]
+        language: Option<String>,
+
+        /// Translate the speech to English (overrides [transcription] translate)
+        #[arg(long)]
+        translate: bool,
     },
 
     /// Retry transcription of a previous recording
@@ -140,6 +157,25 @@ enum Commands {
         /// Enable processing after transcription. Optionally specify action ID to skip picker.
         #[arg(short = 'p', long = "process", value_name = "ACTION", num_args = 0..=1, default_missing_value = "")]
         process: Option<String>,
+
+        /// Output format: plain text, SRT/WebVTT captions, or JSON with timings
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Txt)]
+        format: OutputFormat,
+
+        /// Label speakers and output "Speaker N:" turns (overrides [transcription] diarize)
+        #[arg(long)]
+        diarize: bool,
+        /// Spoken language as an ISO-639-1 code, e.g. "en" or "sv" ("auto" to detect; overrides [transcription] language)
+        #[arg(short, long, value_name = "CODE")]
+        language: Option<String>,
+
+        /// Translate the speech to English (overrides [transcription] translate)
+        #[arg(long)]
+        translate: bool,
+
+        /// Transcribe again even if the same audio and settings are cached
+        #[arg(long)]
+        no_cache: bool,
     },
 
     /// Transcribe a pre-recorded audio file
@@ -151,6 +187,11 @@ enum Commands {
     ///   ostt transcribe recording.ogg
     ///   ostt transcribe voice-memo.mp3 -c
     ///   ostt transcribe meeting.wav -o transcript.txt
+    ///   ostt transcribe demo.mp4 --format srt -o demo.srt
+    ///   ostt transcribe meeting.wav --diarize
+    ///   ostt transcribe intervju.m4a --language sv
+    ///   ostt transcribe intervju.m4a --translate
+    ///   ostt transcribe meeting.wav --no-cache
     ///   ostt transcribe audio.ogg | grep keyword
     #[command(visible_alias = "t")]
     Transcribe {
@@ -169,6 +210,113 @@ enum Commands {
         /// Enable processing after transcription. Optionally specify action ID to skip picker.
         #[arg(short = 'p', long = "process", value_name = "ACTION", num_args = 0..=1, default_missing_value = "")]
         process: Option<String>,
+
+        /// Output format: plain text, SRT/WebVTT captions, or JSON with timings
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Txt)]
+        format: OutputFormat,
+
+        /// Label speakers and output "Speaker N:" turns (overrides [transcription] diarize)
+        #[arg(long)]
+        diarize: bool,
+        /// Spoken language as an ISO-639-1 code, e.g. "en" or "sv" ("auto" to detect; overrides [transcription] language)
+        #[arg(short, long, value_name = "CODE")]
+        language: Option<String>,
+
+        /// Translate the speech to English (overrides [transcription] translate)
+        #[arg(long)]
+        translate: bool,
+
+        /// Transcribe again even if the same audio and settings are cached
+        #[arg(long)]
+        no_cache: bool,
+    },
+
+    /// Compare several models on the same recording
+    ///
+    /// Transcribe a recording or audio file with every given model at the same
+    /// time and show the transcripts side by side. Words that differ from the
+    /// reference model are highlighted, with latency and estimated cost per model.
+    ///
+    /// Examples:
+    ///   ostt compare -m whisper,nova-3,groq-whisper-large-v3
+    ///   ostt compare 3 -m gpt-4o-transcribe,elevenlabs-scribe-v2
+    ///   ostt compare interview.mp3 -m nova-3,assemblyai-universal-3-pro
+    Compare {
+        /// Recording index (1 = most recent) or path to an audio file
+        #[arg(value_name = "N|FILE")]
+        target: Option<String>,
+
+        /// Comma-separated model IDs to compare
+        #[arg(
+            short,
+            long,
+            value_name = "MODELS",
+            value_delimiter = ',',
+            required = true
+        )]
+        models: Vec<String>,
+    },
+
+    /// List transcription models and what they support
+    ///
+    /// Show every built-in and user-defined model with its keyword style,
+    /// timestamps, speaker labels, translation, live transcription, languages,
+    /// upload limits and accepted audio formats, and whether an API key is
+    /// saved for its provider.
+    ///
+    /// Examples:
+    ///   ostt models
+    ///   ostt models --json
+    Models {
+        /// Print the models as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show transcription usage and estimated costs
+    ///
+    /// Sum the audio minutes and estimated cost of every transcription and retry
+    /// recorded in history. Costs are estimates based on list prices per minute.
+    ///
+    /// Examples:
+    ///   ostt usage
+    ///   ostt usage --since 30d --by model
+    ///   ostt usage --since 2026-05-01 --by day
+    Usage {
+        /// Only count transcriptions since a date (YYYY-MM-DD) or a number of days ago (e.g. 30d)
+        #[arg(long, value_name = "DATE|DAYS")]
+        since: Option<String>,
+
+        /// Show one row per model or per day
+        #[arg(long, value_enum)]
+        by: Option<commands::UsageGrouping>,
+    },
+
+    /// Transcribe recordings queued after a failed transcription
+    ///
+    /// When a recording cannot be transcribed because of a network or provider
+    /// error, it is queued instead of being left unlabeled in the recordings
+    /// directory. Run the queue once the connection or provider is back.
+    ///
+    /// Examples:
+    ///   ostt queue list
+    ///   ostt queue run --notify
+    ///   ostt queue drop 3
+    Queue {
+        #[command(subcommand)]
+        action: commands::QueueAction,
+    },
+
+    /// Manage the transcription cache
+    ///
+    /// Transcripts are cached by audio and settings so retrying the same
+    /// recording does not pay for another request. The newest 1000 are kept.
+    ///
+    /// Examples:
+    ///   ostt cache clear
+    Cache {
+        #[command(subcommand)]
+        action: commands::CacheAction,
     },
 
     /// Replay a previous recording using system audio player
@@ -390,32 +538,82 @@ pub async fn run() -> Result<(), anyhow::Error> {
             // Default command is record
             // Merge top-level options with explicit record command options
             // If both are specified, the explicit record command options take precedence
-            let (clipboard, output, process) = match cli.command {
+            let (clipboard, output, process, language, translate) = match cli.command {
                 Some(Commands::Record {
                     clipboard,
                     output,
                     process,
-                }) => (clipboard, output, process),
-                None => (cli.clipboard, cli.output, cli.process),
+                    language,
+                    translate,
+                }) => (clipboard, output, process, language, translate),
+                None => (
+                    cli.clipboard,
+                    cli.output,
+                    cli.process,
+                    cli.language,
+                    cli.translate,
+                ),
                 _ => unreachable!(),
             };
-            commands::handle_record(clipboard, output, process).await?;
+            let options = commands::TranscriptionOptions {
+                language,
+                translate,
+                ..Default::default()
+            };
+            commands::handle_record(clipboard, output, process, options).await?;
         }
         Some(Commands::Retry {
             index,
             clipboard,
             output,
             process,
+            format,
+            diarize,
+            language,
+            translate,
+            no_cache,
         }) => {
-            commands::handle_retry(index, clipboard, output, process).await?;
+            let options = commands::TranscriptionOptions {
+                diarize,
+                language,
+                translate,
+                no_cache,
+            };
+            commands::handle_retry(index, clipboard, output, process, format, options).await?;
         }
         Some(Commands::Transcribe {
             file,
             clipboard,
             output,
             process,
+            format,
+            diarize,
+            language,
+            translate,
+            no_cache,
         }) => {
-            commands::handle_transcribe(file, clipboard, output, process).await?;
+            let options = commands::TranscriptionOptions {
+                diarize,
+                language,
+                translate,
+                no_cache,
+            };
+            commands::handle_transcribe(file, clipboard, output, process, format, options).await?;
+        }
+        Some(Commands::Compare { target, models }) => {
+            commands::handle_compare(target, models).await?;
+        }
+        Some(Commands::Models { json }) => {
+            commands::handle_models(json).await?;
+        }
+        Some(Commands::Usage { since, by }) => {
+            commands::handle_usage(since, by).await?;
+        }
+        Some(Commands::Queue { action }) => {
+            commands::handle_queue(action).await?;
+        }
+        Some(Commands::Cache { action }) => {
+            commands::handle_cache(action).await?;
         }
         Some(Commands::Replay { index }) => {
             commands::handle_replay(index).await?;
@@ -2,9 +2,16 @@
 //!
 //! Unified authentication flow: select a provider/model combination and optionally enter an API key.
 //! Users can keep existing API keys by pressing Enter without entering anything.
+//! Models declared under `[[providers.models]]` in `ostt.toml` are listed next to the built-in ones,
+//! as are models that providers with a models endpoint list but ostt does not ship with.
+//! Offline whisper.cpp models (with the `local-whisper` feature) ask for a model file instead:
+//! either a download or a GGML file that is already on disk.
 
 use crate::config;
-use crate::transcription;
+use crate::keywords::KeywordsManager;
+use crate::recording::ffmpeg;
+use crate::transcription::{self, discovery, KeywordSupport};
+use cliclack::log;
 use cliclack::note;
 use cliclack::outro;
 use cliclack::{intro, password, select};
@@ -24,6 +31,27 @@ pub async fn handle_auth() -> Result<(), anyhow::Error> {
 
     intro(style(" auth ").on_white().black())?;
 
+    // User-defined models live in ostt.toml; fall back to built-in models if it can't be read
+    let config_data = config::OsttConfig::load()
+        .inspect_err(|e| {
+            tracing::warn!("Failed to load configuration, showing built-in models only: {e}")
+        })
+        .ok();
+    let (providers_config, network_config) = match &config_data {
+        Some(config_data) => (config_data.providers.clone(), config_data.network.clone()),
+        None => (
+            config::ProvidersConfig::default(),
+            config::NetworkConfig::default(),
+        ),
+    };
+    for model in &providers_config.models {
+        if let Err(e) = transcription::TranscriptionModel::from_config(model) {
+            log::warning(format!("Skipping model from ostt.toml: {e}"))?;
+        }
+    }
+
+    refresh_discovered_models(&providers_config, &network_config).await;
+
     // Get all available provider/model combinations
     let providers = transcription::TranscriptionProvider::all();
     let mut provider_model_options: Vec<(
@@ -41,10 +69,13 @@ pub async fn handle_auth() -> Result<(), anyhow::Error> {
 
     // Build list of all provider/model combinations
     for provider in providers.iter() {
-        let models = transcription::TranscriptionModel::models_for_provider(provider);
+        let models = transcription::TranscriptionModel::models_for_provider_with_config(
+            provider,
+            &providers_config,
+        );
         for model in models {
             display_options.push(format!("{} / {}", provider.name(), model.description()));
-            provider_model_options.push((provider.clone(), model));
+            provider_model_options.push((*provider, model));
         }
     }
 
@@ -62,6 +93,133 @@ pub async fn handle_auth() -> Result<(), anyhow::Error> {
 
     let (selected_provider, selected_model) = &provider_model_options[selected_idx];
 
+    if let Some(config_data) = &config_data {
+        warn_about_capabilities(selected_model, config_data)?;
+    }
+
+    // Offline models need a model file instead of an API key
+    let api_key_to_save =
+        if setup_local_model(selected_model, &providers_config, &network_config).await? {
+            None
+        } else {
+            prompt_api_key(selected_provider)?
+        };
+
+    // Save the API key for this provider
+    if let Some(api_key_to_save) = api_key_to_save {
+        config::save_api_key(selected_provider.id(), &api_key_to_save)?;
+    }
+
+    // Save the selected model to secrets (not to config file)
+    config::save_selected_model(selected_provider.id(), selected_model.id())?;
+    // Note: save_selected_model ignores provider_id and stores only the model_id
+    // since only one model selection is active globally
+
+    outro("✅ Configuration saved.")?;
+
+    tracing::info!(
+        "Authentication completed: provider={}, model={}",
+        selected_provider.id(),
+        selected_model.id()
+    );
+
+    Ok(())
+}
+
+/// Fetches the model lists of providers whose cached list is older than a day.
+///
+/// Only providers with a saved API key (or that need none) are asked. A failed fetch is
+/// logged and the picker falls back to the models that are already known.
+async fn refresh_discovered_models(
+    providers_config: &config::ProvidersConfig,
+    network_config: &config::NetworkConfig,
+) {
+    let stale = discovery::stale_providers(providers_config, network_config);
+    if stale.is_empty() {
+        return;
+    }
+
+    let spinner = cliclack::spinner();
+    spinner.start("Checking providers for new models...");
+    match discovery::refresh(&stale, providers_config, network_config).await {
+        Ok(0) => spinner.stop("No models beyond the built-in ones"),
+        Ok(count) => spinner.stop(format!("Found {count} models beyond the built-in ones")),
+        Err(e) => {
+            tracing::warn!("Failed to update discovered models: {e:#}");
+            spinner.stop("Could not update the model lists");
+        }
+    }
+}
+
+/// Warns about settings in ostt.toml and `ostt keywords` that `model` cannot honor.
+///
+/// # Errors
+/// - If a warning cannot be printed
+fn warn_about_capabilities(
+    model: &transcription::TranscriptionModel,
+    config_data: &config::OsttConfig,
+) -> anyhow::Result<()> {
+    let Some(capabilities) = model.capabilities() else {
+        return Ok(());
+    };
+    let settings = &config_data.transcription;
+
+    let extension = ffmpeg::extension_for_output_format(&config_data.audio.output_format);
+    if !capabilities.accepts_container(extension) {
+        log::warning(format!(
+            "{} does not accept .{extension} recordings. Change [audio] output_format to one of: {}",
+            model.id(),
+            capabilities.containers.join(", ")
+        ))?;
+    }
+    if let Some(language) = settings.language(None) {
+        if !capabilities.supports_language(&language) {
+            log::warning(format!(
+                "{} does not transcribe '{language}' from [transcription] language. Supported languages: {}",
+                model.id(),
+                capabilities.languages.join(", ")
+            ))?;
+        }
+    }
+    if settings.translate && !capabilities.translation {
+        log::warning(format!(
+            "{} cannot translate; [transcription] translate will fail",
+            model.id()
+        ))?;
+    }
+    if settings.diarize && !capabilities.diarization {
+        log::warning(format!(
+            "{} cannot label speakers; [transcription] diarize is ignored",
+            model.id()
+        ))?;
+    }
+    if capabilities.keywords == KeywordSupport::None {
+        let keywords = dirs::config_dir()
+            .and_then(|dir| KeywordsManager::new(&dir.join("ostt")).ok())
+            .and_then(|manager| manager.load_keywords().ok())
+            .unwrap_or_default();
+        if !keywords.is_empty() {
+            log::warning(format!(
+                "{} ignores keywords; your {} keyword(s) will not be sent",
+                model.id(),
+                keywords.len()
+            ))?;
+        }
+    }
+    Ok(())
+}
+
+/// Prompts for the API key of `selected_provider`.
+///
+/// Returns the key to save, the current key if the user pressed Enter, or `None` for an
+/// optional key that was left empty.
+///
+/// # Errors
+/// - If the prompt is cancelled
+/// - If the provider requires a key and none was entered or saved before
+fn prompt_api_key(
+    selected_provider: &transcription::TranscriptionProvider,
+) -> anyhow::Result<Option<String>> {
     // Check if we already have an API key for this provider
     let current_api_key = config::get_api_key(selected_provider.id()).ok().flatten();
 
@@ -75,6 +233,15 @@ pub async fn handle_auth() -> Result<(), anyhow::Error> {
             .allow_empty()
             .interact()
             .map_err(|e| anyhow::anyhow!("API key input cancelled: {e}"))?
+    } else if !selected_provider.requires_api_key() {
+        let api_key_prompt = format!(
+            "Enter API key for {} (optional, press Enter to skip):",
+            selected_provider.name()
+        );
+        password(&api_key_prompt)
+            .allow_empty()
+            .interact()
+            .map_err(|e| anyhow::anyhow!("API key input cancelled: {e}"))?
     } else {
         let api_key_prompt = format!("Enter API key for {}:", selected_provider.name());
         password(&api_key_prompt)
@@ -83,31 +250,112 @@ pub async fn handle_auth() -> Result<(), anyhow::Error> {
     };
 
     // If empty input and we have a current key, keep the current one
-    let api_key_to_save = if api_key.is_empty() {
+    if api_key.is_empty() {
         if let Some(key) = current_api_key {
-            key
+            Ok(Some(key))
+        } else if !selected_provider.requires_api_key() {
+            Ok(None)
         } else {
-            return Err(anyhow::anyhow!("API key cannot be empty"));
+            Err(anyhow::anyhow!("API key cannot be empty"))
         }
     } else {
-        api_key
-    };
+        Ok(Some(api_key))
+    }
+}
 
-    // Save the API key for this provider
-    config::save_api_key(selected_provider.id(), &api_key_to_save)?;
+/// Makes sure an offline model has its GGML file, downloading it or linking an existing one.
+///
+/// Returns false without prompting for models of other providers.
+///
+/// # Errors
+/// - If a prompt is cancelled
+/// - If the download fails or the given file is not a GGML model
+#[cfg(feature = "local-whisper")]
+async fn setup_local_model(
+    model: &transcription::TranscriptionModel,
+    providers_config: &config::ProvidersConfig,
+    network_config: &config::NetworkConfig,
+) -> anyhow::Result<bool> {
+    use transcription::local_model;
 
-    // Save the selected model to secrets (not to config file)
-    config::save_selected_model(selected_provider.id(), selected_model.id())?;
-    // Note: save_selected_model ignores provider_id and stores only the model_id
-    // since only one model selection is active globally
+    if model.provider().id() != "local" {
+        return Ok(false);
+    }
 
-    outro("✅ Configuration saved.")?;
+    let path = local_model::model_path(providers_config, model)?;
+    if providers_config.local.model_path.is_some() {
+        if path.exists() {
+            log::info(format!(
+                "Using {} from [providers.local].model_path",
+                path.display()
+            ))?;
+        } else {
+            log::warning(format!(
+                "[providers.local].model_path points to {}, which does not exist",
+                path.display()
+            ))?;
+        }
+        return Ok(true);
+    }
 
-    tracing::info!(
-        "Authentication completed: provider={}, model={}",
-        selected_provider.id(),
-        selected_model.id()
-    );
+    let mut choice = select(format!("Model file for {}:", model.id()));
+    if path.exists() {
+        choice = choice.item("keep", format!("Keep {}", path.display()), "");
+    }
+    let choice = choice
+        .item(
+            "download",
+            format!("Download {}", local_model::file_name(model)),
+            "from the whisper.cpp model repository",
+        )
+        .item("file", "Use a GGML file I already have", "")
+        .interact()
+        .map_err(|e| anyhow::anyhow!("Selection cancelled: {e}"))?;
 
-    Ok(())
+    match choice {
+        "download" => {
+            let progress = cliclack::progress_bar(0).with_download_template();
+            progress.start(format!("Downloading {}", local_model::file_name(model)));
+            let result = local_model::download(model, network_config, |received, total| {
+                if let Some(total) = total {
+                    progress.set_length(total);
+                }
+                progress.set_position(received);
+            })
+            .await;
+            match result {
+                Ok(path) => progress.stop(format!("Saved {}", path.display())),
+                Err(e) => {
+                    progress.error("Download failed");
+                    return Err(e);
+                }
+            }
+        }
+        "file" => {
+            let source: String = cliclack::input("Path to the GGML model file:")
+                .placeholder("~/Downloads/ggml-base.bin")
+                .interact()
+                .map_err(|e| anyhow::anyhow!("Path input cancelled: {e}"))?;
+            let source = match source.trim().strip_prefix("~/") {
+                Some(rest) => dirs::home_dir()
+                    .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?
+                    .join(rest),
+                None => std::path::PathBuf::from(source.trim()),
+            };
+            let path = local_model::import(model, &source)?;
+            log::success(format!("Linked {}", path.display()))?;
+        }
+        _ => {}
+    }
+    Ok(true)
+}
+
+/// Offline models are only available with the `local-whisper` feature.
+#[cfg(not(feature = "local-whisper"))]
+async fn setup_local_model(
+    _model: &transcription::TranscriptionModel,
+    _providers_config: &config::ProvidersConfig,
+    _network_config: &config::NetworkConfig,
+) -> anyhow::Result<bool> {
+    Ok(false)
 }
@@ -0,0 +1,42 @@
+//! Transcription cache management.
+//!
+//! Finished transcriptions are cached in the history database (see
+//! [`crate::transcription::cache`]); only the newest entries are kept. `ostt cache clear`
+//! removes them all, for example after a provider changed what it returns for a model.
+
+use clap::Subcommand;
+
+use crate::history::HistoryManager;
+
+/// What `ostt cache` does.
+#[derive(Debug, Clone, Subcommand)]
+pub enum CacheAction {
+    /// Remove every cached transcription
+    ///
+    /// History, usage and queued recordings are kept.
+    Clear,
+}
+
+/// Manages the transcription cache.
+///
+/// # Errors
+/// - If data directory cannot be determined
+/// - If the history database cannot be updated
+pub async fn handle_cache(action: CacheAction) -> Result<(), anyhow::Error> {
+    tracing::info!("=== ostt Cache ===");
+
+    let data_dir = dirs::home_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?
+        .join(".local")
+        .join("share")
+        .join("ostt");
+    let mut history = HistoryManager::new(&data_dir)?;
+
+    match action {
+        CacheAction::Clear => {
+            let removed = history.clear_cache()?;
+            println!("Removed {removed} cached transcription(s).");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,196 @@
+//! Compare several transcription models on the same audio.
+//!
+//! Transcribes a recording from history or an audio file with every given model at the
+//! same time and shows the transcripts side by side with latency and estimated cost.
+
+use crate::commands::TranscriptionOptions;
+use crate::config;
+use crate::keywords::KeywordsManager;
+use crate::recording::{ffmpeg, RecordingHistory};
+use crate::transcription::{self, chunking, pricing, TranscriptionConfig, TranscriptionModel};
+use crate::ui::compare::{CompareViewer, ComparisonResult};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Transcribes the same audio with several models and shows the results side by side.
+///
+/// # Arguments
+/// * `target` - Recording index (1 = most recent) or path to an audio file; defaults to the
+///   most recent recording
+/// * `model_ids` - Models to compare; each provider needs a saved API key
+///
+/// # Errors
+/// - If the recording or file cannot be found
+/// - If fewer than two models are given, a model is unknown or its provider has no API key
+/// - If the terminal UI fails
+pub async fn handle_compare(
+    target: Option<String>,
+    model_ids: Vec<String>,
+) -> Result<(), anyhow::Error> {
+    tracing::info!("=== ostt Compare Command ===");
+
+    let audio_path = resolve_audio(target.as_deref())?;
+    let config_data = config::OsttConfig::load().map_err(|err| {
+        tracing::error!("Failed to load configuration: {err}");
+        anyhow::anyhow!("Configuration error: {err}\n\nPlease check your ~/.config/ostt/ostt.toml file and try again.")
+    })?;
+
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?
+        .join("ostt");
+    let keywords = KeywordsManager::new(&config_dir)?.load_keywords()?;
+
+    let mut configs: Vec<TranscriptionConfig> = Vec::new();
+    for model_id in &model_ids {
+        if configs.iter().any(|config| config.model.id() == model_id) {
+            continue;
+        }
+        let model = TranscriptionModel::from_id_with_config(model_id, &config_data.providers)
+            .ok_or_else(|| anyhow::anyhow!("Unknown model: {model_id}"))?;
+        let provider = model.provider();
+        let api_key = match config::get_api_key(provider.id())? {
+            Some(key) => key,
+            None if !provider.requires_api_key() => String::new(),
+            None => {
+                return Err(anyhow::anyhow!(
+                    "No API key for {} (needed by {model_id}). Please run 'ostt auth'",
+                    provider.name()
+                ));
+            }
+        };
+        let mut transcription_config = TranscriptionConfig::new(
+            model,
+            api_key,
+            keywords.clone(),
+            config_data.providers.clone(),
+        );
+        TranscriptionOptions::default().apply(&config_data, &mut transcription_config);
+        configs.push(transcription_config);
+    }
+    if configs.len() < 2 {
+        return Err(anyhow::anyhow!(
+            "Give at least two different models to compare, e.g. --models whisper,nova-3"
+        ));
+    }
+
+    let probe_path = audio_path.clone();
+    let duration = tokio::task::spawn_blocking(move || ffmpeg::probe_duration(&probe_path))
+        .await?
+        .inspect_err(|e| tracing::debug!("Could not determine audio duration: {e}"))
+        .ok();
+
+    let (update_tx, mut update_rx) = tokio::sync::mpsc::unbounded_channel();
+    let tasks: Vec<_> = configs
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(index, config)| {
+            let update_tx = update_tx.clone();
+            let audio_path = audio_path.clone();
+            tokio::spawn(async move {
+                let started = Instant::now();
+                let result =
+                    match chunking::transcribe(transcription::registry(), &config, &audio_path)
+                        .await
+                    {
+                        Ok(response) => {
+                            let latency = started.elapsed();
+                            let seconds = duration.or_else(|| response.timed_duration());
+                            tracing::info!(
+                                "{} finished in {:.1}s",
+                                config.model.id(),
+                                latency.as_secs_f64()
+                            );
+                            ComparisonResult::Done {
+                                text: response.text,
+                                latency,
+                                cost: seconds.and_then(|seconds| {
+                                    pricing::estimate_cost(&config.model, seconds)
+                                }),
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!("{} failed: {e:#}", config.model.id());
+                            ComparisonResult::Failed(format!("{e:#}"))
+                        }
+                    };
+                update_tx.send((index, result)).ok();
+            })
+        })
+        .collect();
+
+    let file_name = audio_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let title = match duration {
+        Some(seconds) => format!(
+            "Comparing {} models on {file_name} ({})",
+            configs.len(),
+            format_duration(seconds)
+        ),
+        None => format!("Comparing {} models on {file_name}", configs.len()),
+    };
+    let ids = configs.iter().map(|c| c.model.id().to_string()).collect();
+
+    let mut viewer = CompareViewer::new(title, ids)?;
+    let result = viewer.run(&mut update_rx);
+
+    // Requests still running when the viewer closes are no longer needed
+    for task in tasks {
+        task.abort();
+    }
+    result
+}
+
+/// Resolves a recording index or file path to an audio file.
+fn resolve_audio(target: Option<&str>) -> anyhow::Result<PathBuf> {
+    let index = match target {
+        None => 1,
+        Some(value) => match value.parse::<usize>() {
+            Ok(index) => index,
+            Err(_) => {
+                let path = Path::new(value);
+                if !path.exists() {
+                    return Err(anyhow::anyhow!("File not found: {value}"));
+                }
+                return Ok(path.to_path_buf());
+            }
+        },
+    };
+
+    let data_dir = dirs::home_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?
+        .join(".local")
+        .join("share")
+        .join("ostt");
+    let recordings = RecordingHistory::new(&data_dir)?.get_all_recordings()?;
+    if recordings.is_empty() {
+        return Err(anyhow::anyhow!("No recordings found in history"));
+    }
+    if index < 1 || index > recordings.len() {
+        return Err(anyhow::anyhow!(
+            "Recording index out of range. Available recordings: 1-{}",
+            recordings.len()
+        ));
+    }
+
+    let audio_path = recordings[index - 1].clone();
+    if !audio_path.exists() {
+        return Err(anyhow::anyhow!(
+            "Audio file not found: {}",
+            audio_path.display()
+        ));
+    }
+    Ok(audio_path)
+}
+
+/// Formats seconds as `1m 05s` or `42s`.
+fn format_duration(seconds: f64) -> String {
+    let seconds = seconds.round() as u64;
+    if seconds >= 60 {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{seconds}s")
+    }
+}
@@ -5,37 +5,53 @@
 //!
 //! # Commands
 //! - `auth`: Provider + model selection and API key management (unified flow)
+//! - `compare`: Side-by-side comparison of several models on one recording
 //! - `record`: Audio recording with optional transcription
 //! - `history`: Transcription history viewer
 //! - `keywords`: Keyword management for transcription
 //! - `config`: Open configuration file in user's preferred editor
 //! - `list_devices`: List available audio input devices
 //! - `logs`: Display recent log entries
+//! - `models`: Available models with their capabilities and API key status
+//! - `queue`: Recordings queued after a failed transcription, transcribed later
 //! - `retry`: Retry the last recording with the same transcription model
 //! - `replay`: Replay a previous recording from history
+//! - `usage`: Transcription counts, audio minutes and estimated costs
 
 pub mod auth;
+pub mod cache;
+pub mod compare;
 pub mod config;
 pub mod history;
 pub mod keywords;
 pub mod launch;
 pub mod list_devices;
 pub mod logs;
+pub mod models;
+pub mod options;
 pub mod process;
+pub mod queue;
 pub mod record;
 pub mod replay;
 pub mod retry;
 pub mod transcribe;
+pub mod usage;
 
 pub use auth::handle_auth;
+pub use cache::{handle_cache, CacheAction};
+pub use compare::handle_compare;
 pub use config::handle_config;
 pub use history::handle_history;
 pub use keywords::handle_keywords;
 pub use launch::handle_launch;
 pub use list_devices::handle_list_devices;
 pub use logs::handle_logs;
+pub use models::handle_models;
+pub use options::TranscriptionOptions;
 pub use process::handle_process;
+pub use queue::{handle_queue, QueueAction};
 pub use record::handle_record;
 pub use replay::handle_replay;
 pub use retry::handle_retry;
 pub use transcribe::handle_transcribe;
+pub use usage::{handle_usage, UsageGrouping};
@@ -0,0 +1,240 @@
+//! Listing of transcription models and their capabilities.
+//!
+//! Shows every built-in and user-defined model with what it supports: keyword style,
+//! timestamps, speaker labels, translation, live transcription, languages, upload limits and
+//! accepted audio formats, and whether an API key is saved for its provider.
+
+use crate::config;
+use crate::transcription::{ModelCapabilities, TranscriptionModel};
+
+/// One model as listed by `ostt models`.
+struct ModelListing {
+    model: TranscriptionModel,
+    capabilities: ModelCapabilities,
+    /// Whether the provider needs an API key
+    requires_key: bool,
+    /// Whether an API key is saved for the provider
+    has_key: bool,
+    /// Whether this is the model selected with `ostt auth`
+    selected: bool,
+}
+
+/// Prints the available models and their capabilities.
+///
+/// # Arguments
+/// * `json` - Print a JSON array instead of a table
+///
+/// # Errors
+/// - If the output cannot be serialized
+pub async fn handle_models(json: bool) -> Result<(), anyhow::Error> {
+    tracing::info!("=== ostt Models ===");
+
+    let providers = match config::OsttConfig::load() {
+        Ok(config_data) => config_data.providers,
+        Err(e) => {
+            tracing::warn!("Failed to load configuration, listing built-in models only: {e}");
+            config::ProvidersConfig::default()
+        }
+    };
+    let selected = config::get_selected_model().ok().flatten();
+
+    let listings: Vec<ModelListing> = TranscriptionModel::all_with_config(&providers)
+        .into_iter()
+        .filter_map(|model| {
+            let capabilities = model.capabilities()?;
+            let provider = model.provider();
+            Some(ModelListing {
+                capabilities,
+                requires_key: provider.requires_api_key(),
+                has_key: config::get_api_key(provider.id()).ok().flatten().is_some(),
+                selected: selected.as_deref() == Some(model.id()),
+                model,
+            })
+        })
+        .collect();
+
+    if json {
+        let values: Vec<serde_json::Value> = listings.iter().map(to_json).collect();
+        println!("{}", serde_json::to_string_pretty(&values)?);
+        return Ok(());
+    }
+
+    let header = [
+        "Model",
+        "Provider",
+        "Key",
+        "Keywords",
+        "Timestamps",
+        "Speakers",
+        "Translate",
+        "Live",
+        "Languages",
+        "Limit",
+        "Formats",
+    ]
+    .map(String::from);
+    let rows: Vec<[String; 11]> = listings.iter().map(table_row).collect();
+    let mut widths = header.clone().map(|title| title.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    println!("  {}", format_cells(&header, &widths));
+    for (listing, row) in listings.iter().zip(&rows) {
+        let marker = if listing.selected { "*" } else { " " };
+        println!("{marker} {}", format_cells(row, &widths));
+    }
+    println!();
+    println!("* selected model. Run 'ostt auth' to select a model or save an API key.");
+
+    Ok(())
+}
+
+/// Returns the table cells for one model.
+fn table_row(listing: &ModelListing) -> [String; 11] {
+    let capabilities = &listing.capabilities;
+    [
+        listing.model.id().to_string(),
+        listing.model.provider().name().to_string(),
+        key_status(listing).to_string(),
+        capabilities.keywords.as_str().to_string(),
+        yes_no(capabilities.timestamps).to_string(),
+        yes_no(capabilities.diarization).to_string(),
+        yes_no(capabilities.translation).to_string(),
+        yes_no(capabilities.streaming).to_string(),
+        list_or_any(capabilities.languages),
+        format_limit(capabilities),
+        list_or_any(capabilities.containers),
+    ]
+}
+
+/// Pads each cell to its column width; the last column is not padded.
+fn format_cells(cells: &[String], widths: &[usize]) -> String {
+    let last = cells.len().saturating_sub(1);
+    cells
+        .iter()
+        .zip(widths)
+        .enumerate()
+        .map(|(i, (cell, &width))| {
+            if i == last {
+                cell.clone()
+            } else {
+                format!("{cell:<width$}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// Returns whether the model's provider has a saved API key.
+fn key_status(listing: &ModelListing) -> &'static str {
+    match (listing.has_key, listing.requires_key) {
+        (true, _) => "saved",
+        (false, true) => "missing",
+        (false, false) => "not needed",
+    }
+}
+
+/// Formats a capability flag for the table.
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+/// Joins a list of codes, or returns "any" for an empty (unrestricted) list.
+fn list_or_any(values: &[&str]) -> String {
+    if values.is_empty() {
+        "any".to_string()
+    } else {
+        values.join(", ")
+    }
+}
+
+/// Formats the upload size and duration limits of a single request, e.g. "25 MB, 23 min".
+fn format_limit(capabilities: &ModelCapabilities) -> String {
+    let size = capabilities
+        .max_upload_bytes
+        .map(|bytes| format!("{} MB", bytes / (1024 * 1024)));
+    let duration = capabilities
+        .max_duration_secs
+        .map(|secs| format!("{} min", secs / 60));
+    match (size, duration) {
+        (Some(size), Some(duration)) => format!("{size}, {duration}"),
+        (Some(limit), None) | (None, Some(limit)) => limit,
+        (None, None) => "none".to_string(),
+    }
+}
+
+/// Returns the JSON object printed for a model by `ostt models --json`.
+fn to_json(listing: &ModelListing) -> serde_json::Value {
+    let capabilities = &listing.capabilities;
+    serde_json::json!({
+        "id": listing.model.id(),
+        "provider": listing.model.provider().id(),
+        "api_model_name": listing.model.api_model_name(),
+        "description": listing.model.description(),
+        "selected": listing.selected,
+        "requires_api_key": listing.requires_key,
+        "has_api_key": listing.has_key,
+        "capabilities": {
+            "max_upload_bytes": capabilities.max_upload_bytes,
+            "max_duration_secs": capabilities.max_duration_secs,
+            "containers": capabilities.containers,
+            "keywords": capabilities.keywords.as_str(),
+            "timestamps": capabilities.timestamps,
+            "diarization": capabilities.diarization,
+            "translation": capabilities.translation,
+            "streaming": capabilities.streaming,
+            "languages": capabilities.languages,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(model_id: &str) -> ModelListing {
+        let model = TranscriptionModel::from_id(model_id).unwrap();
+        ModelListing {
+            capabilities: model.capabilities().unwrap(),
+            requires_key: true,
+            has_key: false,
+            selected: false,
+            model,
+        }
+    }
+
+    #[test]
+    fn limits_are_formatted_in_megabytes_and_minutes() {
+        assert_eq!(
+            format_limit(&listing("gpt-4o-transcribe").capabilities),
+            "25 MB, 23 min"
+        );
+        assert_eq!(format_limit(&listing("whisper").capabilities), "25 MB");
+        assert_eq!(format_limit(&listing("nova-3").capabilities), "none");
+    }
+
+    #[test]
+    fn json_lists_capabilities_and_key_status() {
+        let value = to_json(&listing("whisper"));
+        assert_eq!(value["provider"], "openai");
+        assert_eq!(value["has_api_key"], false);
+        assert_eq!(value["capabilities"]["keywords"], "prompt");
+        assert_eq!(value["capabilities"]["translation"], true);
+        assert_eq!(value["capabilities"]["timestamps"], true);
+        assert_eq!(
+            value["capabilities"]["max_duration_secs"],
+            serde_json::Value::Null
+        );
+        assert!(value["capabilities"]["containers"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!("mp3")));
+    }
+}
@@ -0,0 +1,31 @@
+//! Per-run transcription options shared by the record, transcribe and retry commands.
+
+use crate::config::OsttConfig;
+use crate::transcription::TranscriptionConfig;
+
+/// Transcription options given on the command line.
+///
+/// Each option adds to or overrides the matching `[transcription]` setting for one run.
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptionOptions {
+    /// Label speakers, in addition to the `[transcription] diarize` setting
+    pub diarize: bool,
+    /// Spoken language, overriding the `[transcription] language` setting
+    pub language: Option<String>,
+    /// Translate to English, in addition to the `[transcription] translate` setting
+    pub translate: bool,
+    /// Send the audio to the provider even if the transcript is cached
+    pub no_cache: bool,
+}
+
+impl TranscriptionOptions {
+    /// Applies the options, the `[transcription]` settings and the `[network]` settings to
+    /// a request configuration.
+    pub fn apply(&self, config_data: &OsttConfig, config: &mut TranscriptionConfig) {
+        let settings = &config_data.transcription;
+        config.network = config_data.network.clone();
+        config.diarize = self.diarize || settings.diarize;
+        config.language = settings.language(self.language.as_deref());
+        config.translate = self.translate || settings.translate;
+    }
+}
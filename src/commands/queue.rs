@@ -0,0 +1,441 @@
+//! Queue of recordings whose transcription failed.
+//!
+//! When the network or the provider fails while `ostt record` transcribes, the recording is
+//! moved to `~/.local/share/ostt/queue`, out of reach of the cleanup of old recordings, and a
+//! job is added to the history database. `ostt queue run` transcribes the queued recordings
+//! later, saves each transcript to history and moves the recording back to `recordings/`.
+
+use std::path::{Path, PathBuf};
+
+use clap::Subcommand;
+
+use crate::commands::TranscriptionOptions;
+use crate::config;
+use crate::history::{HistoryManager, QueuedRecording};
+use crate::keywords::KeywordsManager;
+use crate::notification;
+use crate::recording::RecordingHistory;
+use crate::transcription;
+use crate::transcription::pricing::TranscriptionUsage;
+
+/// What `ostt queue` does.
+#[derive(Debug, Clone, Subcommand)]
+pub enum QueueAction {
+    /// List queued recordings
+    List,
+
+    /// Transcribe queued recordings with the model they were queued under
+    ///
+    /// The selected model is used instead when that model is no longer known
+    /// or has no API key. Each transcript is printed and saved to history;
+    /// recordings that fail again stay in the queue.
+    Run {
+        /// Only transcribe this job
+        #[arg(value_name = "ID")]
+        id: Option<i64>,
+
+        /// Show a desktop notification for every finished transcription
+        #[arg(long)]
+        notify: bool,
+    },
+
+    /// Remove recordings from the queue without transcribing them
+    ///
+    /// The recordings are moved back to the recordings directory, where they
+    /// can still be retried with 'ostt retry'.
+    Drop {
+        /// Jobs to remove
+        #[arg(value_name = "ID", required_unless_present = "all")]
+        ids: Vec<i64>,
+
+        /// Remove every queued recording
+        #[arg(long, conflicts_with = "ids")]
+        all: bool,
+    },
+}
+
+/// Lists, transcribes or removes queued recordings.
+///
+/// # Errors
+/// - If the history database cannot be read or updated
+/// - If `ostt queue run` cannot load the configuration
+/// - If a queued recording could not be transcribed again
+pub async fn handle_queue(action: QueueAction) -> Result<(), anyhow::Error> {
+    tracing::info!("=== ostt Queue ===");
+
+    let data_dir = data_dir()?;
+    let mut history = HistoryManager::new(&data_dir)?;
+
+    match action {
+        QueueAction::List => list_queue(&mut history),
+        QueueAction::Run { id, notify } => run_queue(&data_dir, &mut history, id, notify).await,
+        QueueAction::Drop { ids, all } => drop_jobs(&data_dir, &mut history, ids, all),
+    }
+}
+
+/// Moves a recording into the queue after a failed transcription.
+///
+/// # Arguments
+/// * `data_dir` - The ostt data directory
+/// * `audio_path` - The recording, normally in `recordings/`
+/// * `model_id` - Model whose transcription failed
+/// * `options` - Per-run options to use again when the job runs
+/// * `error` - Why the transcription failed
+///
+/// # Errors
+/// - If the recording cannot be moved
+/// - If the job cannot be saved
+pub fn enqueue(
+    data_dir: &Path,
+    audio_path: &Path,
+    model_id: &str,
+    options: &TranscriptionOptions,
+    error: &anyhow::Error,
+) -> anyhow::Result<i64> {
+    let queue_dir = data_dir.join("queue");
+    std::fs::create_dir_all(&queue_dir)?;
+    let file_name = audio_path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid recording path: {}", audio_path.display()))?;
+    let queued_path = queue_dir.join(file_name);
+    std::fs::rename(audio_path, &queued_path).map_err(|e| {
+        anyhow::anyhow!("Failed to move {} to the queue: {e}", audio_path.display())
+    })?;
+
+    let job = QueuedRecording {
+        id: 0,
+        audio_path: queued_path.clone(),
+        model: model_id.to_string(),
+        language: options.language.clone(),
+        translate: options.translate,
+        diarize: options.diarize,
+        error: format!("{error:#}"),
+        attempts: 1,
+        queued_at: chrono::Local::now(),
+    };
+    HistoryManager::new(data_dir)?
+        .enqueue(&job)
+        .inspect_err(|_| {
+            // Without a job the recording would be lost in the queue directory
+            std::fs::rename(&queued_path, audio_path).ok();
+        })
+}
+
+/// Prints the queued recordings.
+fn list_queue(history: &mut HistoryManager) -> anyhow::Result<()> {
+    let jobs = history.get_queue()?;
+    if jobs.is_empty() {
+        println!("No queued recordings.");
+        return Ok(());
+    }
+
+    println!(
+        "{:>4}  {:<16}  {:<24}  {:>8}  Last error",
+        "ID", "Queued", "Model", "Attempts"
+    );
+    for job in &jobs {
+        let error = job.error.lines().next().unwrap_or_default();
+        println!(
+            "{:>4}  {:<16}  {:<24}  {:>8}  {}",
+            job.id,
+            job.queued_at.format("%Y-%m-%d %H:%M"),
+            job.model,
+            job.attempts,
+            truncate(error, 60)
+        );
+    }
+    println!();
+    println!("Run 'ostt queue run' to transcribe them with these models.");
+    Ok(())
+}
+
+/// Transcribes queued recordings, one at a time, oldest first.
+async fn run_queue(
+    data_dir: &Path,
+    history: &mut HistoryManager,
+    id: Option<i64>,
+    notify: bool,
+) -> anyhow::Result<()> {
+    let mut jobs = history.get_queue()?;
+    if let Some(id) = id {
+        jobs.retain(|job| job.id == id);
+        if jobs.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No queued recording with ID {id}. Run 'ostt queue list' to see the queue."
+            ));
+        }
+    }
+    if jobs.is_empty() {
+        println!("No queued recordings.");
+        return Ok(());
+    }
+
+    let config_data = config::OsttConfig::load().map_err(|err| {
+        tracing::error!("Failed to load configuration: {err}");
+        anyhow::anyhow!("Configuration error: {err}\n\nPlease check your ~/.config/ostt/ostt.toml file and try again.")
+    })?;
+    let selected_model = config::get_selected_model()?;
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?
+        .join("ostt");
+    let keywords = KeywordsManager::new(&config_dir)?.load_keywords()?;
+
+    let mut failed = 0;
+    for job in &jobs {
+        if !job.audio_path.exists() {
+            eprintln!(
+                "Job #{}: {} no longer exists, removing it from the queue",
+                job.id,
+                job.audio_path.display()
+            );
+            history.remove_from_queue(job.id)?;
+            continue;
+        }
+
+        let usable = |model_id: &str| usable_model(model_id, &config_data.providers);
+        let Some((model, api_key)) = job_model(&job.model, selected_model.as_deref(), usable)
+        else {
+            let error = format!(
+                "{} is unknown or has no API key, and no usable model is selected. Please run 'ostt auth'",
+                job.model
+            );
+            eprintln!("Job #{}: {error}", job.id);
+            history.record_queue_failure(job.id, &error)?;
+            failed += 1;
+            continue;
+        };
+        if model.id() != job.model {
+            eprintln!(
+                "Job #{}: {} is unknown or has no API key, using {}",
+                job.id,
+                job.model,
+                model.id()
+            );
+        }
+
+        let options = TranscriptionOptions {
+            diarize: job.diarize,
+            language: job.language.clone(),
+            translate: job.translate,
+            no_cache: false,
+        };
+        let mut transcription_config = transcription::TranscriptionConfig::new(
+            model,
+            api_key,
+            keywords.clone(),
+            config_data.providers.clone(),
+        );
+        options.apply(&config_data, &mut transcription_config);
+        let chain = transcription::fallback::build_chain(
+            transcription_config,
+            &config_data.transcription.fallback_models,
+        );
+
+        tracing::info!("Transcribing queued job #{}", job.id);
+        let result = transcription::cache::transcribe(
+            transcription::registry(),
+            &chain,
+            &job.audio_path,
+            history,
+            true,
+            |config| tracing::info!("Transcribing with {}", config.model.id()),
+        )
+        .await;
+        match result {
+            Ok(transcribed) => {
+                let response = transcribed.response;
+                let usage = if transcribed.cached {
+                    None
+                } else {
+                    Some(
+                        TranscriptionUsage::measure(
+                            &transcribed.config.model,
+                            &job.audio_path,
+                            &response,
+                        )
+                        .await,
+                    )
+                };
+                if let Err(e) = history.save_transcription(&response, usage.as_ref()) {
+                    tracing::warn!("Failed to save transcription to history: {}", e);
+                }
+                history.remove_from_queue(job.id)?;
+                restore_recording(data_dir, &job.audio_path);
+
+                let text = response.text.trim();
+                println!("#{}: {text}", job.id);
+                if notify {
+                    notification::notify("ostt: transcription finished", &truncate(text, 200));
+                }
+            }
+            Err(e) => {
+                tracing::error!("Queued job #{} failed again: {e:#}", job.id);
+                eprintln!("Job #{}: transcription failed: {e:#}", job.id);
+                history.record_queue_failure(job.id, &format!("{e:#}"))?;
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(anyhow::anyhow!(
+            "{failed} of {} queued recording(s) could not be transcribed and stay in the queue",
+            jobs.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Picks the model a queued job runs with.
+///
+/// The model the job was queued under wins, so the transcript matches what `ostt queue
+/// list` shows; the selected model only stands in when that one is not `usable`.
+fn job_model<T>(
+    queued_model: &str,
+    selected_model: Option<&str>,
+    usable: impl Fn(&str) -> Option<T>,
+) -> Option<T> {
+    usable(queued_model).or_else(|| selected_model.and_then(&usable))
+}
+
+/// Returns a model and its API key if the model is known and its provider has a key
+/// (or needs none).
+fn usable_model(
+    model_id: &str,
+    providers: &config::ProvidersConfig,
+) -> Option<(transcription::TranscriptionModel, String)> {
+    let model = transcription::TranscriptionModel::from_id_with_config(model_id, providers)?;
+    let provider = model.provider();
+    match config::get_api_key(provider.id()).ok().flatten() {
+        Some(key) => Some((model, key)),
+        None if !provider.requires_api_key() => Some((model, String::new())),
+        None => None,
+    }
+}
+
+/// Removes jobs from the queue and moves their recordings back to `recordings/`.
+fn drop_jobs(
+    data_dir: &Path,
+    history: &mut HistoryManager,
+    ids: Vec<i64>,
+    all: bool,
+) -> anyhow::Result<()> {
+    let jobs = history.get_queue()?;
+    let ids = if all {
+        jobs.iter().map(|job| job.id).collect()
+    } else {
+        ids
+    };
+
+    for id in ids {
+        let Some(job) = jobs.iter().find(|job| job.id == id) else {
+            eprintln!("No queued recording with ID {id}");
+            continue;
+        };
+        history.remove_from_queue(id)?;
+        restore_recording(data_dir, &job.audio_path);
+        println!("Removed job #{id} from the queue");
+    }
+    Ok(())
+}
+
+/// Moves a queued recording back to `recordings/`, where the usual cleanup applies.
+///
+/// Failures are only logged; the job has already left the queue.
+fn restore_recording(data_dir: &Path, queued_path: &Path) {
+    let Some(file_name) = queued_path.file_name() else {
+        return;
+    };
+    let recordings_dir = data_dir.join("recordings");
+    let target: PathBuf = recordings_dir.join(file_name);
+    if let Err(e) =
+        std::fs::create_dir_all(&recordings_dir).and_then(|_| std::fs::rename(queued_path, &target))
+    {
+        tracing::warn!(
+            "Failed to move {} back to recordings: {e}",
+            queued_path.display()
+        );
+        return;
+    }
+    if let Ok(recording_history) = RecordingHistory::new(data_dir) {
+        let _ = recording_history.cleanup_old_recordings();
+    }
+}
+
+/// Shortens text to at most `max` characters, marking the cut with an ellipsis.
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut short: String = text.chars().take(max.saturating_sub(1)).collect();
+    short.push('…');
+    short
+}
+
+/// Returns the ostt data directory.
+fn data_dir() -> anyhow::Result<PathBuf> {
+    Ok(dirs::home_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?
+        .join(".local")
+        .join("share")
+        .join("ostt"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queued_recordings_leave_and_return_to_the_recordings_dir() {
+        let dir = std::env::temp_dir().join(format!("ostt_test_queue_move_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let recording = dir.join("recordings").join("ostt-recording-1.mp3");
+        std::fs::create_dir_all(recording.parent().unwrap()).unwrap();
+        std::fs::write(&recording, b"audio").unwrap();
+
+        let options = TranscriptionOptions {
+            language: Some("sv".to_string()),
+            ..Default::default()
+        };
+        let error = anyhow::anyhow!("Network error");
+        let id = enqueue(&dir, &recording, "whisper", &options, &error).unwrap();
+        assert!(!recording.exists());
+
+        let mut history = HistoryManager::new(&dir).unwrap();
+        let queue = history.get_queue().unwrap();
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].id, id);
+        assert_eq!(
+            queue[0].audio_path,
+            dir.join("queue").join("ostt-recording-1.mp3")
+        );
+        assert_eq!(queue[0].language.as_deref(), Some("sv"));
+        assert_eq!(queue[0].error, "Network error");
+
+        drop_jobs(&dir, &mut history, vec![id], false).unwrap();
+        assert!(recording.exists());
+        assert!(history.get_queue().unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn jobs_run_with_their_own_model_when_it_is_usable() {
+        let usable = |model_id: &str| (model_id != "removed").then(|| model_id.to_string());
+        assert_eq!(
+            job_model("nova-3", Some("whisper"), usable).as_deref(),
+            Some("nova-3")
+        );
+        assert_eq!(
+            job_model("removed", Some("whisper"), usable).as_deref(),
+            Some("whisper")
+        );
+        assert_eq!(job_model("removed", None, usable), None);
+    }
+
+    #[test]
+    fn long_text_is_truncated_with_an_ellipsis() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("a longer sentence", 8), "a longe…");
+    }
+}
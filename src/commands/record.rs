@@ -4,16 +4,21 @@
 //! and history management. Supports external triggers via SIGUSR1 signal.
 
 use crate::clipboard::copy_to_clipboard;
+use crate::commands::{queue, TranscriptionOptions};
 use crate::config;
 use crate::history::HistoryManager;
 use crate::keywords::KeywordsManager;
 use crate::process;
-use crate::recording::{AudioRecorder, OsttTui, PickerEvent, RecordingCommand, RecordingHistory};
-use crate::transcription::TranscriptionAnimation;
+use crate::recording::{
+    ffmpeg, AudioRecorder, OsttTui, PickerEvent, RecordingCommand, RecordingHistory,
+};
+use crate::transcription::pricing::TranscriptionUsage;
+use crate::transcription::{self, LiveSession, TranscriptionAnimation};
 use crate::ui::ErrorScreen;
 use dirs;
 use ratatui::widgets::ListState;
 use std::fs;
+use std::sync::Arc;
 
 /// Handles audio recording and optional transcription.
 ///
@@ -24,10 +29,12 @@ use std::fs;
 /// * `clipboard` - If true, copy to clipboard instead of stdout
 /// * `output_file` - Optional file path to write output to instead of stdout
 /// * `process` - Optional processing action: None = no processing, Some("") = show picker, Some(id) = use action
+/// * `options` - Per-run transcription options (language, translation)
 pub async fn handle_record(
     clipboard: bool,
     output_file: Option<String>,
     process: Option<String>,
+    options: TranscriptionOptions,
 ) -> Result<(), anyhow::Error> {
     tracing::info!("=== ostt Audio Recorder Started ===");
 
@@ -58,6 +65,18 @@ pub async fn handle_record(
         config_data.audio.device.clone(),
     );
 
+    // Live transcription: captured audio is buffered in the channel until the session connects
+    let live_setup = if config_data.transcription.streaming {
+        prepare_live_transcription(&config_data, &options)
+    } else {
+        None
+    };
+    let live_audio = live_setup.as_ref().map(|_| {
+        let (audio_tx, audio_rx) = tokio::sync::mpsc::unbounded_channel();
+        audio_recorder.set_audio_sink(audio_tx);
+        audio_rx
+    });
+
     if let Err(e) = audio_recorder.start_recording() {
         tracing::error!("Failed to start recording: {e}");
         let error_message = format!(
@@ -70,6 +89,20 @@ pub async fn handle_record(
     }
 
     let actual_sample_rate = audio_recorder.get_sample_rate();
+
+    let mut live_session = match (live_setup, live_audio) {
+        (Some((provider, live_config)), Some(audio_rx)) => {
+            match LiveSession::start(provider, &live_config, actual_sample_rate, audio_rx) {
+                Ok(session) => Some(session),
+                Err(e) => {
+                    tracing::warn!("Live transcription unavailable: {e}");
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+
     let mut tui = OsttTui::new(
         actual_sample_rate,
         config_data.audio.peak_volume_threshold,
@@ -107,6 +140,10 @@ pub async fn handle_record(
                     tracing::debug!("Recording: {:.1}s recorded", duration_secs);
                 }
 
+                if let Some(session) = &live_session {
+                    tui.set_live_transcript(session.transcript());
+                }
+
                 let samples = audio_recorder.get_samples();
                 if let Err(e) = tui.render_waveform(&samples) {
                     tui.cleanup().ok();
@@ -138,20 +175,7 @@ pub async fn handle_record(
     }
 
     tracing::debug!("Stopping recording and saving audio...");
-    let codec = config_data
-        .audio
-        .output_format
-        .split_whitespace()
-        .next()
-        .unwrap_or("mp3");
-    let extension = match codec {
-        "libopus" => "ogg",
-        "libvorbis" => "ogg",
-        "flac" => "flac",
-        "aac" => "m4a",
-        "pcm_s16le" => "wav",
-        _ => codec,
-    };
+    let extension = ffmpeg::extension_for_output_format(&config_data.audio.output_format);
 
     // Prepare data directory for recordings
     let data_dir = match dirs::home_dir() {
@@ -197,13 +221,15 @@ pub async fn handle_record(
                 &config_data,
                 &model_id,
                 &filepath_str,
+                live_session.take(),
+                &options,
             )
             .await
             {
                 Ok(text) => Some(text),
                 Err(e) => {
-                    tracing::warn!("Transcription failed: {}", e);
-                    eprintln!("Warning: Transcription failed: {e}");
+                    tracing::warn!("Transcription failed: {:#}", e);
+                    eprintln!("Warning: Transcription failed: {e:#}");
                     None
                 }
             }
@@ -493,6 +519,8 @@ pub async fn handle_record(
 ///
 /// # Arguments
 /// * `output_mode` - Optional override for output mode (clipboard or stdout)
+/// * `live_session` - Live transcription session; its transcript is used instead of uploading
+///   the recording when it finishes successfully
 ///
 /// # Errors
 /// - If the model ID is invalid
@@ -503,10 +531,13 @@ async fn transcribe_recording_with_animation(
     config_data: &config::OsttConfig,
     model_id: &str,
     audio_filename: &str,
+    live_session: Option<LiveSession>,
+    options: &TranscriptionOptions,
 ) -> anyhow::Result<String> {
-    use crate::transcription;
-
-    let model = match transcription::TranscriptionModel::from_id(model_id) {
+    let model = match transcription::TranscriptionModel::from_id_with_config(
+        model_id,
+        &config_data.providers,
+    ) {
         Some(m) => m,
         None => {
             tui.cleanup().ok();
@@ -521,6 +552,7 @@ async fn transcribe_recording_with_animation(
 
     let api_key = match config::get_api_key(provider.id())? {
         Some(key) => key,
+        None if !provider.requires_api_key() => String::new(),
         None => {
             tui.cleanup().ok();
             let mut error_screen = ErrorScreen::new()?;
@@ -543,12 +575,17 @@ async fn transcribe_recording_with_animation(
     let keywords_manager = KeywordsManager::new(&config_dir)?;
     let keywords = keywords_manager.load_keywords()?;
 
-    let transcription_config = transcription::TranscriptionConfig::new(
+    let mut transcription_config = transcription::TranscriptionConfig::new(
         model,
         api_key,
         keywords,
         config_data.providers.clone(),
     );
+    options.apply(config_data, &mut transcription_config);
+    let chain = transcription::fallback::build_chain(
+        transcription_config,
+        &config_data.transcription.fallback_models,
+    );
 
     tracing::debug!(
         "Starting transcription with model '{}' for file '{}'",
@@ -559,13 +596,64 @@ async fn transcribe_recording_with_animation(
     let mut animation = TranscriptionAnimation::new(80);
     animation.set_status_label("Transcribing...");
 
+    // The task reports which provider it is using, which changes when it falls back
+    let (label_tx, mut label_rx) = tokio::sync::watch::channel(String::new());
+
+    let data_dir = dirs::home_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?
+        .join(".local")
+        .join("share")
+        .join("ostt");
+
     let filename = audio_filename.to_string();
+    let lookup = !options.no_cache;
+    let cache_dir = data_dir.clone();
     let transcription_handle = tokio::spawn(async move {
-        transcription::transcribe(&transcription_config, filename.as_ref()).await
+        if let Some(session) = live_session {
+            match session.finish().await {
+                Ok(text) if !text.is_empty() => {
+                    // Live results get the same post-processing as uploads, e.g. the language
+                    let response = transcription::registry::finish_response(
+                        &chain[0],
+                        transcription::TranscriptionResponse::new(text),
+                    );
+                    return Ok((response, chain[0].model.clone(), false));
+                }
+                Ok(_) => {
+                    tracing::warn!("Live transcription returned no text, uploading recording")
+                }
+                Err(e) => tracing::warn!("{e}, uploading recording instead"),
+            }
+        }
+        // Cache the transcript so a retry of this recording with the same model is free
+        let mut history_manager = HistoryManager::new(&cache_dir)?;
+        transcription::cache::transcribe(
+            transcription::registry(),
+            &chain,
+            filename.as_ref(),
+            &mut history_manager,
+            lookup,
+            |config| {
+                let label = format!("Transcribing with {}...", config.model.provider().name());
+                label_tx.send(label).ok();
+            },
+        )
+        .await
+        .map(|transcribed| {
+            (
+                transcribed.response,
+                transcribed.config.model.clone(),
+                transcribed.cached,
+            )
+        })
     });
 
     let mut cancelled = false;
     loop {
+        if label_rx.has_changed().unwrap_or(false) {
+            animation.set_status_label(&label_rx.borrow_and_update());
+        }
+
         if let Err(e) = tui.render_transcription_animation(&mut animation) {
             tracing::warn!("Failed to render animation: {}", e);
         }
@@ -607,31 +695,55 @@ async fn transcribe_recording_with_animation(
     }
 
     match transcription_handle.await {
-        Ok(Ok(text)) => {
-            let trimmed_text = text.trim().to_string();
+        Ok(Ok((response, used_model, cached))) => {
+            let trimmed_text = response.text.trim().to_string();
             tracing::debug!("Transcription completed: {}", trimmed_text);
-
-            let data_dir = dirs::home_dir()
-                .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?
-                .join(".local")
-                .join("share")
-                .join("ostt");
+            // Cached transcripts cost nothing, so they are saved without usage
+            let usage = if cached {
+                None
+            } else {
+                Some(
+                    TranscriptionUsage::measure(&used_model, audio_filename.as_ref(), &response)
+                        .await,
+                )
+            };
 
             let mut history_manager = HistoryManager::new(&data_dir)?;
-            if let Err(e) = history_manager.save_transcription(&trimmed_text) {
+            if let Err(e) = history_manager.save_transcription(&response, usage.as_ref()) {
                 tracing::warn!("Failed to save transcription to history: {}", e);
             }
 
             // Return the transcription text to be output after TUI cleanup
-            Ok(text)
+            Ok(response.text)
         }
         Ok(Err(e)) => {
             tracing::error!("Transcription failed: {}", e);
             tui.cleanup().ok();
+            let mut message = transcription::TranscriptionError::screen_message(&e);
+
+            // Network and provider outages pass; keep the recording for 'ostt queue run'
+            let queued = transcription::TranscriptionError::is_transient_error(&e)
+                && match queue::enqueue(&data_dir, audio_filename.as_ref(), model_id, options, &e) {
+                    Ok(_) => true,
+                    Err(queue_error) => {
+                        tracing::warn!("Failed to queue recording: {queue_error:#}");
+                        false
+                    }
+                };
+            if queued {
+                message.push_str(
+                    "\n\nThe recording was queued. Run 'ostt queue run' to transcribe it later.",
+                );
+            }
+
             let mut error_screen = ErrorScreen::new()?;
-            error_screen.show_error(&format!("Error: Transcription failed - {e}"))?;
+            error_screen.show_error(&message)?;
             error_screen.cleanup()?;
-            Err(e)
+            if queued {
+                Err(e.context("Recording queued, run 'ostt queue run' to transcribe it later"))
+            } else {
+                Err(e)
+            }
         }
         Err(e) => {
             tracing::error!("Transcription task failed: {}", e);
@@ -643,3 +755,66 @@ async fn transcribe_recording_with_animation(
         }
     }
 }
+
+/// Resolves the provider and request configuration for live transcription.
+///
+/// Returns `None` (and records why in the log) when no model is selected, the provider has no
+/// live recognition, or its API key is missing; the recording is then uploaded as usual.
+fn prepare_live_transcription(
+    config_data: &config::OsttConfig,
+    options: &TranscriptionOptions,
+) -> Option<(
+    Arc<dyn transcription::Provider>,
+    transcription::TranscriptionConfig,
+)> {
+    let model_id = config::get_selected_model().ok().flatten()?;
+    let model =
+        transcription::TranscriptionModel::from_id_with_config(&model_id, &config_data.providers)?;
+    let provider = transcription::registry()
+        .get(model.provider().id())?
+        .clone();
+
+    if !provider.capabilities().streaming {
+        tracing::info!(
+            "{} does not support live transcription, recording will be uploaded",
+            model.provider().name()
+        );
+        return None;
+    }
+
+    let api_key = match config::get_api_key(model.provider().id()).ok().flatten() {
+        Some(key) => key,
+        None => {
+            tracing::warn!(
+                "No API key for {}, live transcription disabled",
+                model.provider().name()
+            );
+            return None;
+        }
+    };
+
+    let keywords = dirs::config_dir()
+        .map(|dir| dir.join("ostt"))
+        .and_then(|dir| KeywordsManager::new(&dir).ok())
+        .and_then(|manager| manager.load_keywords().ok())
+        .unwrap_or_default();
+
+    let mut live_config = transcription::TranscriptionConfig::new(
+        model,
+        api_key,
+        keywords,
+        config_data.providers.clone(),
+    );
+    options.apply(config_data, &mut live_config);
+
+    // Live results carry no speaker labels or translations, so both need the full upload
+    if live_config.diarize {
+        tracing::info!("Speaker diarization is enabled, recording will be uploaded");
+        return None;
+    }
+    if live_config.translate {
+        tracing::info!("Translation is enabled, recording will be uploaded");
+        return None;
+    }
+    Some((provider, live_config))
+}
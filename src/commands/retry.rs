@@ -1,12 +1,14 @@
 //! Retry transcription of a previous recording without re-recording audio.
 
 use crate::clipboard::copy_to_clipboard;
+use crate::commands::TranscriptionOptions;
 use crate::config;
 use crate::history::HistoryManager;
 use crate::keywords::KeywordsManager;
 use crate::process;
 use crate::recording::RecordingHistory;
-use crate::transcription;
+use crate::transcription::pricing::TranscriptionUsage;
+use crate::transcription::{self, OutputFormat};
 use dirs;
 
 /// Retries transcription of a previous recording.
@@ -19,14 +21,24 @@ use dirs;
 /// * `clipboard` - If true, copy to clipboard instead of stdout
 /// * `output_file` - Optional file path to write output to instead of stdout
 /// * `process` - Optional processing action: None = no processing, Some("") = show picker, Some(id) = use action
+/// * `format` - Output format; formats other than plain text cannot be combined with processing
+/// * `options` - Per-run transcription options (diarization, language, translation, cache)
 pub async fn handle_retry(
     recording_index: Option<usize>,
     clipboard: bool,
     output_file: Option<String>,
     process: Option<String>,
+    format: OutputFormat,
+    options: TranscriptionOptions,
 ) -> Result<(), anyhow::Error> {
     tracing::info!("=== ostt Retry Command ===");
 
+    if format != OutputFormat::Txt && process.is_some() {
+        return Err(anyhow::anyhow!(
+            "--format cannot be combined with --process; processing works on plain text only"
+        ));
+    }
+
     let data_dir = dirs::home_dir()
         .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?
         .join(".local")
@@ -71,12 +83,16 @@ pub async fn handle_retry(
 
     if let Some(model_id) = selected_model_id {
         // Get API key
-        let model = transcription::TranscriptionModel::from_id(&model_id)
-            .ok_or_else(|| anyhow::anyhow!("Unknown model: {model_id}"))?;
+        let model = transcription::TranscriptionModel::from_id_with_config(
+            &model_id,
+            &config_data.providers,
+        )
+        .ok_or_else(|| anyhow::anyhow!("Unknown model: {model_id}"))?;
         let provider = model.provider();
 
         let api_key = match config::get_api_key(provider.id())? {
             Some(key) => key,
+            None if !provider.requires_api_key() => String::new(),
             None => {
                 return Err(anyhow::anyhow!(
                     "No API key for {}. Please run 'ostt auth'",
@@ -92,31 +108,58 @@ pub async fn handle_retry(
         let keywords = keywords_manager.load_keywords()?;
 
         // Prepare transcription config
-        let transcription_config = transcription::TranscriptionConfig::new(
+        let mut transcription_config = transcription::TranscriptionConfig::new(
             model,
             api_key,
             keywords.clone(),
             config_data.providers.clone(),
         );
+        options.apply(&config_data, &mut transcription_config);
+        let chain = transcription::fallback::build_chain(
+            transcription_config,
+            &config_data.transcription.fallback_models,
+        );
 
-        // Transcribe
+        // Transcribe, unless the same audio and settings are cached
         tracing::debug!("Starting transcription for retry...");
-        match transcription::transcribe(&transcription_config, audio_path).await {
-            Ok(text) => {
-                let trimmed_text = text.trim().to_string();
+        let mut history_manager = HistoryManager::new(&data_dir)?;
+        let result = transcription::cache::transcribe(
+            transcription::registry(),
+            &chain,
+            audio_path,
+            &mut history_manager,
+            !options.no_cache,
+            |config| tracing::info!("Transcribing with {}", config.model.id()),
+        )
+        .await;
+        match result {
+            Ok(transcribed) => {
+                let response = transcribed.response;
+                let trimmed_text = response.text.trim().to_string();
                 tracing::debug!("Retry transcription completed: {}", trimmed_text);
 
-                // Save raw transcription to history
-                let mut history_manager = HistoryManager::new(&data_dir)?;
-                if let Err(e) = history_manager.save_transcription(&trimmed_text) {
+                // Save raw transcription to history; cached transcripts cost nothing
+                let usage = if transcribed.cached {
+                    None
+                } else {
+                    Some(
+                        TranscriptionUsage::measure(
+                            &transcribed.config.model,
+                            audio_path,
+                            &response,
+                        )
+                        .await,
+                    )
+                };
+                if let Err(e) = history_manager.save_transcription(&response, usage.as_ref()) {
                     tracing::warn!("Failed to save transcription to history: {}", e);
                 }
 
                 // Processing flow: if -p was passed, chain processing after transcription
                 let output_text = match process.as_deref() {
                     None => {
-                        // No processing requested, output raw transcription
-                        trimmed_text
+                        // No processing requested, output raw transcription in the requested format
+                        transcription::format::render(&response, format)?
                     }
                     Some("") => {
                         // Show action picker
@@ -204,8 +247,8 @@ pub async fn handle_retry(
                 Ok(())
             }
             Err(e) => {
-                tracing::error!("Retry transcription failed: {e}");
-                Err(anyhow::anyhow!("Transcription failed: {e}"))
+                tracing::error!("Retry transcription failed: {e:#}");
+                Err(e.context("Transcription failed"))
             }
         }
     } else {
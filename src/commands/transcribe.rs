@@ -4,11 +4,13 @@
 //! reusing the same transcription pipeline as the `record` command.
 
 use crate::clipboard::copy_to_clipboard;
+use crate::commands::TranscriptionOptions;
 use crate::config;
 use crate::history::HistoryManager;
 use crate::keywords::KeywordsManager;
 use crate::process;
-use crate::transcription;
+use crate::transcription::pricing::TranscriptionUsage;
+use crate::transcription::{self, OutputFormat};
 use dirs;
 use std::path::PathBuf;
 
@@ -22,14 +24,24 @@ use std::path::PathBuf;
 /// * `clipboard` - If true, copy to clipboard instead of stdout
 /// * `output_file` - Optional file path to write output to instead of stdout
 /// * `process` - Optional processing action: None = no processing, Some("") = show picker, Some(id) = use action
+/// * `format` - Output format; formats other than plain text cannot be combined with processing
+/// * `options` - Per-run transcription options (diarization, language, translation, cache)
 pub async fn handle_transcribe(
     file: PathBuf,
     clipboard: bool,
     output_file: Option<String>,
     process: Option<String>,
+    format: OutputFormat,
+    options: TranscriptionOptions,
 ) -> Result<(), anyhow::Error> {
     tracing::info!("=== ostt Transcribe Command ===");
 
+    if format != OutputFormat::Txt && process.is_some() {
+        return Err(anyhow::anyhow!(
+            "--format cannot be combined with --process; processing works on plain text only"
+        ));
+    }
+
     // Validate the input file exists
     if !file.exists() {
         return Err(anyhow::anyhow!("Audio file not found: {}", file.display()));
@@ -50,13 +62,21 @@ pub async fn handle_transcribe(
         anyhow::anyhow!("No model selected. Please run 'ostt auth' to select a transcription model")
     })?;
 
-    let model = transcription::TranscriptionModel::from_id(&model_id)
-        .ok_or_else(|| anyhow::anyhow!("Unknown model: {model_id}"))?;
+    let model =
+        transcription::TranscriptionModel::from_id_with_config(&model_id, &config_data.providers)
+            .ok_or_else(|| anyhow::anyhow!("Unknown model: {model_id}"))?;
     let provider = model.provider();
 
-    let api_key = config::get_api_key(provider.id())?.ok_or_else(|| {
-        anyhow::anyhow!("No API key for {}. Please run 'ostt auth'", provider.name())
-    })?;
+    let api_key = match config::get_api_key(provider.id())? {
+        Some(key) => key,
+        None if !provider.requires_api_key() => String::new(),
+        None => {
+            return Err(anyhow::anyhow!(
+                "No API key for {}. Please run 'ostt auth'",
+                provider.name()
+            ));
+        }
+    };
 
     // Load keywords
     let config_dir = dirs::config_dir()
@@ -65,41 +85,61 @@ pub async fn handle_transcribe(
     let keywords = keywords_manager.load_keywords()?;
 
     // Prepare transcription config
-    let transcription_config = transcription::TranscriptionConfig::new(
+    let mut transcription_config = transcription::TranscriptionConfig::new(
         model,
         api_key,
         keywords,
         config_data.providers.clone(),
     );
+    options.apply(&config_data, &mut transcription_config);
+    let chain = transcription::fallback::build_chain(
+        transcription_config,
+        &config_data.transcription.fallback_models,
+    );
 
-    // Transcribe
-    tracing::debug!("Starting transcription...");
-    let text = transcription::transcribe(&transcription_config, &file)
-        .await
-        .map_err(|e| {
-            tracing::error!("Transcription failed: {e}");
-            anyhow::anyhow!("Transcription failed: {e}")
-        })?;
-
-    let trimmed_text = text.trim().to_string();
-    tracing::debug!("Transcription completed: {}", trimmed_text);
-
-    // Save raw transcription to history
     let data_dir = dirs::home_dir()
         .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?
         .join(".local")
         .join("share")
         .join("ostt");
     let mut history_manager = HistoryManager::new(&data_dir)?;
-    if let Err(e) = history_manager.save_transcription(&trimmed_text) {
+
+    // Transcribe, unless the same audio and settings are cached
+    tracing::debug!("Starting transcription...");
+    let transcribed = transcription::cache::transcribe(
+        transcription::registry(),
+        &chain,
+        &file,
+        &mut history_manager,
+        !options.no_cache,
+        |config| tracing::info!("Transcribing with {}", config.model.id()),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Transcription failed: {e:#}");
+        e.context("Transcription failed")
+    })?;
+    let response = transcribed.response;
+    // Cached transcripts cost nothing, so they are saved without usage
+    let usage = if transcribed.cached {
+        None
+    } else {
+        Some(TranscriptionUsage::measure(&transcribed.config.model, &file, &response).await)
+    };
+
+    let trimmed_text = response.text.trim().to_string();
+    tracing::debug!("Transcription completed: {}", trimmed_text);
+
+    // Save raw transcription to history
+    if let Err(e) = history_manager.save_transcription(&response, usage.as_ref()) {
         tracing::warn!("Failed to save transcription to history: {}", e);
     }
 
     // Processing flow: if -p was passed, chain processing after transcription
     let output_text = match process.as_deref() {
         None => {
-            // No processing requested, output raw transcription
-            trimmed_text
+            // No processing requested, output raw transcription in the requested format
+            transcription::format::render(&response, format)?
         }
         Some("") => {
             // Show action picker
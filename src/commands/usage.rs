@@ -0,0 +1,232 @@
+//! Transcription usage and cost totals.
+//!
+//! Sums the audio duration and estimated cost recorded in the history database for every
+//! transcription, optionally grouped by model or by day.
+
+use crate::history::{HistoryManager, UsageEntry};
+use crate::transcription::pricing;
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use clap::ValueEnum;
+
+/// How `ostt usage` groups its totals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum UsageGrouping {
+    /// One row per model
+    Model,
+    /// One row per calendar day
+    Day,
+}
+
+/// Totals for one group of transcriptions.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct UsageTotals {
+    /// Group label: a model ID, a date or "Total"
+    label: String,
+    /// Number of transcriptions
+    count: usize,
+    /// Sum of the known audio durations in seconds
+    seconds: f64,
+    /// Sum of the known costs in USD
+    cost: f64,
+    /// Number of transcriptions without a known cost
+    unpriced: usize,
+}
+
+impl UsageTotals {
+    fn add(&mut self, entry: &UsageEntry) {
+        self.count += 1;
+        self.seconds += entry.usage.duration_secs.unwrap_or(0.0);
+        match entry.usage.cost {
+            Some(cost) => self.cost += cost,
+            None => self.unpriced += 1,
+        }
+    }
+}
+
+/// Prints transcription counts, audio minutes and estimated costs from the history.
+///
+/// # Arguments
+/// * `since` - Only count transcriptions from this date (`YYYY-MM-DD`) or number of days
+///   back (`30d`); all history when `None`
+/// * `by` - Print one row per model or day in addition to the total
+///
+/// # Errors
+/// - If `since` cannot be parsed
+/// - If the history database cannot be read
+pub async fn handle_usage(
+    since: Option<String>,
+    by: Option<UsageGrouping>,
+) -> Result<(), anyhow::Error> {
+    tracing::info!("=== ostt Usage ===");
+
+    let since = since.as_deref().map(parse_since).transpose()?;
+
+    let data_dir = dirs::home_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?
+        .join(".local")
+        .join("share")
+        .join("ostt");
+    let entries = HistoryManager::new(&data_dir)?.get_usage(since)?;
+
+    if entries.is_empty() {
+        println!("No transcription usage recorded yet.");
+        return Ok(());
+    }
+
+    let mut total = UsageTotals::default();
+    for entry in &entries {
+        total.add(entry);
+    }
+
+    let label_header = match by {
+        Some(UsageGrouping::Model) => "Model",
+        Some(UsageGrouping::Day) => "Day",
+        None => "",
+    };
+    let groups = by.map(|by| group_usage(&entries, by)).unwrap_or_default();
+    let width = groups
+        .iter()
+        .map(|group| group.label.len())
+        .chain([label_header.len(), "Total".len()])
+        .max()
+        .unwrap_or(0);
+
+    match since {
+        Some(since) => println!("Usage since {}", since.format("%Y-%m-%d")),
+        None => println!("Usage for all recorded transcriptions"),
+    }
+    println!();
+    println!(
+        "{label_header:<width$}  {:>6}  {:>8}  {:>10}",
+        "Count", "Minutes", "Cost"
+    );
+    for group in &groups {
+        println!("{}", format_row(group, width));
+    }
+    let total = UsageTotals {
+        label: "Total".to_string(),
+        ..total
+    };
+    println!("{}", format_row(&total, width));
+
+    if total.unpriced > 0 {
+        println!();
+        println!(
+            "{} transcription(s) have no price (unknown model or duration) and are not included in the cost.",
+            total.unpriced
+        );
+    }
+    println!();
+    println!("Costs are estimates based on list prices per minute of audio.");
+
+    Ok(())
+}
+
+/// Sums usage per model or per day, ordered by label.
+fn group_usage(entries: &[UsageEntry], by: UsageGrouping) -> Vec<UsageTotals> {
+    let mut groups: Vec<UsageTotals> = Vec::new();
+    for entry in entries {
+        let label = match by {
+            UsageGrouping::Model => entry.usage.model_id.clone(),
+            UsageGrouping::Day => entry.created_at.format("%Y-%m-%d").to_string(),
+        };
+        match groups.iter_mut().find(|group| group.label == label) {
+            Some(group) => group.add(entry),
+            None => {
+                let mut group = UsageTotals {
+                    label,
+                    ..Default::default()
+                };
+                group.add(entry);
+                groups.push(group);
+            }
+        }
+    }
+    groups.sort_by(|a, b| a.label.cmp(&b.label));
+    groups
+}
+
+/// Formats one row of the usage table.
+fn format_row(totals: &UsageTotals, width: usize) -> String {
+    format!(
+        "{:<width$}  {:>6}  {:>8.1}  {:>10}",
+        totals.label,
+        totals.count,
+        totals.seconds / 60.0,
+        pricing::format_cost(totals.cost)
+    )
+}
+
+/// Parses `--since` as a date (`2026-05-01`) or a number of days back (`30d`).
+fn parse_since(value: &str) -> anyhow::Result<DateTime<Local>> {
+    let value = value.trim();
+    let date = if let Some(days) = value.strip_suffix('d') {
+        let days: i64 = days
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid --since value '{value}'"))?;
+        Local::now().date_naive() - chrono::Duration::days(days)
+    } else {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+            anyhow::anyhow!("Invalid --since value '{value}': use a date like 2026-05-01 or a number of days like 30d")
+        })?
+    };
+
+    let midnight = date
+        .and_hms_opt(0, 0, 0)
+        .ok_or_else(|| anyhow::anyhow!("Invalid --since value '{value}'"))?;
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .ok_or_else(|| anyhow::anyhow!("Invalid --since value '{value}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcription::pricing::TranscriptionUsage;
+    use crate::transcription::TranscriptionModel;
+
+    fn entry(date: &str, model_id: &str, seconds: f64) -> UsageEntry {
+        let model = TranscriptionModel::from_id(model_id).unwrap();
+        UsageEntry {
+            created_at: parse_since(date).unwrap(),
+            usage: TranscriptionUsage::new(&model, Some(seconds)),
+        }
+    }
+
+    #[test]
+    fn usage_is_grouped_by_model_and_day() {
+        let entries = [
+            entry("2026-05-01", "whisper", 60.0),
+            entry("2026-05-01", "custom", 30.0),
+            entry("2026-05-02", "whisper", 120.0),
+        ];
+
+        let by_model = group_usage(&entries, UsageGrouping::Model);
+        assert_eq!(by_model.len(), 2);
+        assert_eq!(by_model[1].label, "whisper");
+        assert_eq!(by_model[1].count, 2);
+        assert!((by_model[1].cost - 0.018).abs() < 1e-9);
+        assert_eq!(by_model[0].unpriced, 1);
+
+        let by_day = group_usage(&entries, UsageGrouping::Day);
+        let labels: Vec<&str> = by_day.iter().map(|g| g.label.as_str()).collect();
+        assert_eq!(labels, ["2026-05-01", "2026-05-02"]);
+        assert_eq!(by_day[0].seconds, 90.0);
+    }
+
+    #[test]
+    fn since_accepts_dates_and_days() {
+        let date = parse_since("2026-05-01").unwrap();
+        assert_eq!(
+            date.format("%Y-%m-%d %H:%M").to_string(),
+            "2026-05-01 00:00"
+        );
+        let week = parse_since("7d").unwrap();
+        assert_eq!(
+            week.date_naive(),
+            Local::now().date_naive() - chrono::Duration::days(7)
+        );
+        assert!(parse_since("last week").is_err());
+    }
+}
@@ -7,5 +7,5 @@
 pub mod storage;
 pub mod ui;
 
-pub use storage::{HistoryManager, TranscriptionEntry};
+pub use storage::{HistoryManager, QueuedRecording, TranscriptionEntry, UsageEntry};
 pub use ui::HistoryViewer;
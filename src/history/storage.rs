@@ -6,9 +6,18 @@
 use anyhow::Result;
 use chrono::{DateTime, Local};
 use rusqlite::OptionalExtension;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, Row};
 use std::path::{Path, PathBuf};
 
+use crate::transcription::pricing::TranscriptionUsage;
+use crate::transcription::{TranscriptSegment, TranscriptWord, TranscriptionResponse};
+
+/// Columns selected for a [`TranscriptionEntry`], in the order read by `entry_from_row`
+const ENTRY_COLUMNS: &str = "id, text, created_at, segments, words, language";
+
+/// Number of cached transcriptions kept; older entries are removed when a new one is stored
+const MAX_CACHED_RESPONSES: usize = 1000;
+
 /// A single transcription entry in the history.
 #[derive(Debug, Clone)]
 pub struct TranscriptionEntry {
@@ -18,6 +27,44 @@ pub struct TranscriptionEntry {
     pub text: String,
     /// When this transcription was created
     pub created_at: DateTime<Local>,
+    /// Timed segments reported by the provider (empty if none)
+    pub segments: Vec<TranscriptSegment>,
+    /// Timed words reported by the provider (empty if none)
+    pub words: Vec<TranscriptWord>,
+    /// Language detected by the provider or requested for the transcription
+    pub language: Option<String>,
+}
+
+/// Usage recorded for one transcription, as read by `ostt usage`.
+#[derive(Debug, Clone)]
+pub struct UsageEntry {
+    /// When the transcription was created
+    pub created_at: DateTime<Local>,
+    /// Model, audio duration and estimated cost
+    pub usage: TranscriptionUsage,
+}
+
+/// A recording waiting in the queue for another transcription attempt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuedRecording {
+    /// Unique identifier for this job, used by `ostt queue run` and `ostt queue drop`
+    pub id: i64,
+    /// The queued audio file
+    pub audio_path: PathBuf,
+    /// Model whose transcription failed
+    pub model: String,
+    /// Spoken language given on the command line, if any
+    pub language: Option<String>,
+    /// Whether translation to English was requested on the command line
+    pub translate: bool,
+    /// Whether speaker labels were requested on the command line
+    pub diarize: bool,
+    /// Error of the most recent attempt
+    pub error: String,
+    /// Number of failed attempts, including the one that queued the recording
+    pub attempts: u32,
+    /// When the recording was queued
+    pub queued_at: DateTime<Local>,
 }
 
 /// Manages the transcription history database.
@@ -65,6 +112,40 @@ impl HistoryManager {
                 [],
             )?;
 
+            // Timing columns were added after the first release; add them to older databases
+            add_column_if_missing(&connection, "transcriptions", "segments", "TEXT")?;
+            add_column_if_missing(&connection, "transcriptions", "words", "TEXT")?;
+            add_column_if_missing(&connection, "transcriptions", "language", "TEXT")?;
+            add_column_if_missing(&connection, "transcriptions", "model", "TEXT")?;
+            add_column_if_missing(&connection, "transcriptions", "provider", "TEXT")?;
+            add_column_if_missing(&connection, "transcriptions", "duration_secs", "REAL")?;
+            add_column_if_missing(&connection, "transcriptions", "cost", "REAL")?;
+
+            connection.execute(
+                "CREATE TABLE IF NOT EXISTS transcription_cache (
+                    key TEXT PRIMARY KEY,
+                    model TEXT NOT NULL,
+                    response TEXT NOT NULL,
+                    created_at TEXT NOT NULL
+                )",
+                [],
+            )?;
+
+            connection.execute(
+                "CREATE TABLE IF NOT EXISTS transcription_queue (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    audio_path TEXT NOT NULL,
+                    model TEXT NOT NULL,
+                    language TEXT,
+                    translate INTEGER NOT NULL,
+                    diarize INTEGER NOT NULL,
+                    error TEXT NOT NULL,
+                    attempts INTEGER NOT NULL,
+                    created_at TEXT NOT NULL
+                )",
+                [],
+            )?;
+
             self.connection = Some(connection);
         }
 
@@ -73,20 +154,41 @@ impl HistoryManager {
 
     /// Saves a new transcription to the history database.
     ///
+    /// Segment and word timings are stored as JSON alongside the text, if the provider
+    /// reported any, together with the transcript language and the usage for `ostt usage`.
+    ///
     /// # Arguments
-    /// * `text` - The transcribed text to save
+    /// * `response` - The transcription to save
+    /// * `usage` - Model, audio duration and estimated cost of the transcription
     ///
     /// # Errors
     /// - If database connection fails
     /// - If insertion fails
-    pub fn save_transcription(&mut self, text: &str) -> Result<()> {
+    pub fn save_transcription(
+        &mut self,
+        response: &TranscriptionResponse,
+        usage: Option<&TranscriptionUsage>,
+    ) -> Result<()> {
         let connection = self.get_connection()?;
         let now = Local::now();
         let timestamp = now.to_rfc3339();
+        let segments = to_json_column(&response.segments)?;
+        let words = to_json_column(&response.words)?;
 
         connection.execute(
-            "INSERT INTO transcriptions (text, created_at) VALUES (?1, ?2)",
-            params![text, timestamp],
+            "INSERT INTO transcriptions (text, created_at, segments, words, language, model, provider, duration_secs, cost)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                response.text.trim(),
+                timestamp,
+                segments,
+                words,
+                response.language,
+                usage.map(|u| &u.model_id),
+                usage.map(|u| &u.provider_id),
+                usage.and_then(|u| u.duration_secs),
+                usage.and_then(|u| u.cost),
+            ],
         )?;
 
         tracing::debug!("Transcription saved to history");
@@ -102,29 +204,12 @@ impl HistoryManager {
     pub fn get_all_transcriptions(&mut self) -> Result<Vec<TranscriptionEntry>> {
         let connection = self.get_connection()?;
 
-        let mut statement = connection
-            .prepare("SELECT id, text, created_at FROM transcriptions ORDER BY created_at DESC")?;
+        let mut statement = connection.prepare(&format!(
+            "SELECT {ENTRY_COLUMNS} FROM transcriptions ORDER BY created_at DESC"
+        ))?;
 
         let entries = statement
-            .query_map([], |row| {
-                let id = row.get::<_, i64>(0)?;
-                let text = row.get::<_, String>(1)?;
-                let timestamp_str = row.get::<_, String>(2)?;
-
-                let created_at = DateTime::parse_from_rfc3339(&timestamp_str)
-                    .map(|dt| dt.with_timezone(&Local))
-                    .map_err(|_| {
-                        rusqlite::Error::InvalidParameterName(
-                            "Invalid timestamp format".to_string(),
-                        )
-                    })?;
-
-                Ok(TranscriptionEntry {
-                    id,
-                    text,
-                    created_at,
-                })
-            })?
+            .query_map([], entry_from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(entries)
@@ -145,30 +230,12 @@ impl HistoryManager {
         let connection = self.get_connection()?;
         let offset = index.saturating_sub(1);
 
-        let mut statement = connection.prepare(
-            "SELECT id, text, created_at FROM transcriptions ORDER BY created_at DESC LIMIT 1 OFFSET ?1",
-        )?;
+        let mut statement = connection.prepare(&format!(
+            "SELECT {ENTRY_COLUMNS} FROM transcriptions ORDER BY created_at DESC LIMIT 1 OFFSET ?1"
+        ))?;
 
         let entry = statement
-            .query_row(params![offset], |row| {
-                let id = row.get::<_, i64>(0)?;
-                let text = row.get::<_, String>(1)?;
-                let timestamp_str = row.get::<_, String>(2)?;
-
-                let created_at = DateTime::parse_from_rfc3339(&timestamp_str)
-                    .map(|dt| dt.with_timezone(&Local))
-                    .map_err(|_| {
-                        rusqlite::Error::InvalidParameterName(
-                            "Invalid timestamp format".to_string(),
-                        )
-                    })?;
-
-                Ok(TranscriptionEntry {
-                    id,
-                    text,
-                    created_at,
-                })
-            })
+            .query_row(params![offset], entry_from_row)
             .optional()?;
 
         Ok(entry)
@@ -186,31 +253,467 @@ impl HistoryManager {
     pub fn get_transcription(&mut self, id: i64) -> Result<Option<TranscriptionEntry>> {
         let connection = self.get_connection()?;
 
-        let mut statement =
-            connection.prepare("SELECT id, text, created_at FROM transcriptions WHERE id = ?1")?;
+        let mut statement = connection.prepare(&format!(
+            "SELECT {ENTRY_COLUMNS} FROM transcriptions WHERE id = ?1"
+        ))?;
 
         let entry = statement
-            .query_row(params![id], |row| {
-                let entry_id = row.get::<_, i64>(0)?;
-                let text = row.get::<_, String>(1)?;
-                let timestamp_str = row.get::<_, String>(2)?;
-
-                let created_at = DateTime::parse_from_rfc3339(&timestamp_str)
-                    .map(|dt| dt.with_timezone(&Local))
-                    .map_err(|_| {
-                        rusqlite::Error::InvalidParameterName(
-                            "Invalid timestamp format".to_string(),
-                        )
-                    })?;
-
-                Ok(TranscriptionEntry {
-                    id: entry_id,
-                    text,
-                    created_at,
-                })
-            })
+            .query_row(params![id], entry_from_row)
             .optional()?;
 
         Ok(entry)
     }
+
+    /// Retrieves the usage of all transcriptions created at or after `since`, oldest first.
+    ///
+    /// Transcriptions saved before usage was recorded are skipped.
+    ///
+    /// # Errors
+    /// - If database connection fails
+    /// - If query execution fails
+    /// - If timestamp parsing fails
+    pub fn get_usage(&mut self, since: Option<DateTime<Local>>) -> Result<Vec<UsageEntry>> {
+        let connection = self.get_connection()?;
+
+        let mut statement = connection.prepare(
+            "SELECT created_at, model, provider, duration_secs, cost FROM transcriptions
+             WHERE model IS NOT NULL ORDER BY created_at ASC",
+        )?;
+
+        let entries = statement
+            .query_map([], |row| {
+                Ok(UsageEntry {
+                    created_at: parse_timestamp(&row.get::<_, String>(0)?)?,
+                    usage: TranscriptionUsage {
+                        model_id: row.get(1)?,
+                        provider_id: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                        duration_secs: row.get(3)?,
+                        cost: row.get(4)?,
+                    },
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Timestamps carry the offset they were saved with, so compare them as times
+        Ok(entries
+            .into_iter()
+            .filter(|entry| since.is_none_or(|since| entry.created_at >= since))
+            .collect())
+    }
+
+    /// Looks up a cached transcription by its cache key.
+    ///
+    /// # Arguments
+    /// * `key` - Hash of the audio and transcription settings
+    ///
+    /// # Errors
+    /// - If database connection fails
+    /// - If query execution fails
+    /// - If the cached response cannot be parsed
+    pub fn get_cached_response(&mut self, key: &str) -> Result<Option<TranscriptionResponse>> {
+        let connection = self.get_connection()?;
+
+        let response = connection
+            .query_row(
+                "SELECT response FROM transcription_cache WHERE key = ?1",
+                params![key],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?;
+
+        Ok(response
+            .map(|json| serde_json::from_str(&json))
+            .transpose()?)
+    }
+
+    /// Stores a transcription in the cache, replacing an older entry with the same key.
+    ///
+    /// Only the newest 1000 entries are kept, so the cache does not grow without bound.
+    ///
+    /// # Arguments
+    /// * `key` - Hash of the audio and transcription settings
+    /// * `model_id` - Model that produced the transcription
+    /// * `response` - The transcription to cache
+    ///
+    /// # Errors
+    /// - If database connection fails
+    /// - If insertion fails
+    pub fn save_cached_response(
+        &mut self,
+        key: &str,
+        model_id: &str,
+        response: &TranscriptionResponse,
+    ) -> Result<()> {
+        let connection = self.get_connection()?;
+        let json = serde_json::to_string(response)?;
+
+        connection.execute(
+            "INSERT OR REPLACE INTO transcription_cache (key, model, response, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![key, model_id, json, Local::now().to_rfc3339()],
+        )?;
+        let removed = trim_cache(connection, MAX_CACHED_RESPONSES)?;
+
+        tracing::debug!("Transcription cached for {model_id}, {removed} old entries removed");
+        Ok(())
+    }
+
+    /// Removes every cached transcription and returns how many were removed.
+    ///
+    /// # Errors
+    /// - If database connection fails
+    /// - If deletion fails
+    pub fn clear_cache(&mut self) -> Result<usize> {
+        let connection = self.get_connection()?;
+        let removed = connection.execute("DELETE FROM transcription_cache", [])?;
+        tracing::info!("Cleared {removed} cached transcriptions");
+        Ok(removed)
+    }
+
+    /// Adds a recording to the transcription queue and returns the new job's ID.
+    ///
+    /// # Arguments
+    /// * `job` - The recording and request options; `id`, `attempts` and `queued_at` are
+    ///   ignored and set by the database
+    ///
+    /// # Errors
+    /// - If database connection fails
+    /// - If insertion fails
+    pub fn enqueue(&mut self, job: &QueuedRecording) -> Result<i64> {
+        let connection = self.get_connection()?;
+
+        connection.execute(
+            "INSERT INTO transcription_queue
+                (audio_path, model, language, translate, diarize, error, attempts, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1, ?7)",
+            params![
+                job.audio_path.to_string_lossy(),
+                job.model,
+                job.language,
+                job.translate,
+                job.diarize,
+                job.error,
+                Local::now().to_rfc3339()
+            ],
+        )?;
+
+        let id = connection.last_insert_rowid();
+        tracing::info!("Queued {} as job #{id}", job.audio_path.display());
+        Ok(id)
+    }
+
+    /// Retrieves all queued recordings, oldest first.
+    ///
+    /// # Errors
+    /// - If database connection fails
+    /// - If query execution fails
+    pub fn get_queue(&mut self) -> Result<Vec<QueuedRecording>> {
+        let connection = self.get_connection()?;
+
+        let mut statement = connection.prepare(
+            "SELECT id, audio_path, model, language, translate, diarize, error, attempts, created_at
+             FROM transcription_queue ORDER BY id",
+        )?;
+        let jobs = statement
+            .query_map([], |row| {
+                Ok(QueuedRecording {
+                    id: row.get(0)?,
+                    audio_path: PathBuf::from(row.get::<_, String>(1)?),
+                    model: row.get(2)?,
+                    language: row.get(3)?,
+                    translate: row.get(4)?,
+                    diarize: row.get(5)?,
+                    error: row.get(6)?,
+                    attempts: row.get(7)?,
+                    queued_at: parse_timestamp(&row.get::<_, String>(8)?)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(jobs)
+    }
+
+    /// Records another failed attempt of a queued job.
+    ///
+    /// # Errors
+    /// - If database connection fails
+    /// - If the update fails
+    pub fn record_queue_failure(&mut self, id: i64, error: &str) -> Result<()> {
+        let connection = self.get_connection()?;
+
+        connection.execute(
+            "UPDATE transcription_queue SET error = ?2, attempts = attempts + 1 WHERE id = ?1",
+            params![id, error],
+        )?;
+        Ok(())
+    }
+
+    /// Removes a job from the transcription queue.
+    ///
+    /// Returns false if no job has this ID.
+    ///
+    /// # Errors
+    /// - If database connection fails
+    /// - If deletion fails
+    pub fn remove_from_queue(&mut self, id: i64) -> Result<bool> {
+        let connection = self.get_connection()?;
+
+        let removed =
+            connection.execute("DELETE FROM transcription_queue WHERE id = ?1", params![id])?;
+        Ok(removed > 0)
+    }
+}
+
+/// Builds a [`TranscriptionEntry`] from a row selected with [`ENTRY_COLUMNS`].
+fn entry_from_row(row: &Row) -> rusqlite::Result<TranscriptionEntry> {
+    let id = row.get::<_, i64>(0)?;
+    let text = row.get::<_, String>(1)?;
+    let created_at = parse_timestamp(&row.get::<_, String>(2)?)?;
+
+    Ok(TranscriptionEntry {
+        id,
+        text,
+        created_at,
+        segments: from_json_column(row.get(3)?),
+        words: from_json_column(row.get(4)?),
+        language: row.get(5)?,
+    })
+}
+
+/// Parses a stored RFC 3339 timestamp into local time.
+fn parse_timestamp(value: &str) -> rusqlite::Result<DateTime<Local>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Local))
+        .map_err(|_| rusqlite::Error::InvalidParameterName("Invalid timestamp format".to_string()))
+}
+
+/// Serializes timing data for storage; empty lists are stored as NULL.
+fn to_json_column<T: serde::Serialize>(items: &[T]) -> Result<Option<String>> {
+    if items.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::to_string(items)?))
+}
+
+/// Deserializes timing data, treating NULL or unreadable values as empty.
+fn from_json_column<T: serde::de::DeserializeOwned>(value: Option<String>) -> Vec<T> {
+    value
+        .and_then(|json| {
+            serde_json::from_str(&json)
+                .inspect_err(|e| tracing::warn!("Ignoring unreadable timing data in history: {e}"))
+                .ok()
+        })
+        .unwrap_or_default()
+}
+
+/// Adds a column to an existing table unless it is already present.
+/// Removes all but the `keep` most recently stored cache entries and returns how many were
+/// removed.
+fn trim_cache(connection: &Connection, keep: usize) -> Result<usize> {
+    Ok(connection.execute(
+        "DELETE FROM transcription_cache WHERE key NOT IN (
+            SELECT key FROM transcription_cache ORDER BY created_at DESC, rowid DESC LIMIT ?1
+        )",
+        params![keep as i64],
+    )?)
+}
+
+fn add_column_if_missing(
+    connection: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<()> {
+    let mut statement = connection.prepare(&format!("PRAGMA table_info({table})"))?;
+    let exists = statement
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?
+        .iter()
+        .any(|name| name == column);
+
+    if !exists {
+        connection.execute(
+            &format!("ALTER TABLE {table} ADD COLUMN {column} {definition}"),
+            [],
+        )?;
+        tracing::debug!("Added column {column} to {table}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_data_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ostt_test_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn timings_round_trip_through_history() {
+        let dir = temp_data_dir("history_timings");
+        let mut history = HistoryManager::new(&dir).unwrap();
+
+        let response = TranscriptionResponse {
+            text: " Hello world. ".to_string(),
+            segments: vec![TranscriptSegment {
+                text: "Hello world.".to_string(),
+                start: 0.0,
+                end: 1.2,
+                confidence: Some(0.9),
+                speaker: None,
+            }],
+            words: vec![TranscriptWord {
+                text: "Hello".to_string(),
+                start: 0.0,
+                end: 0.5,
+                confidence: None,
+                speaker: None,
+            }],
+            language: Some("sv".to_string()),
+        };
+        history.save_transcription(&response, None).unwrap();
+        history
+            .save_transcription(&TranscriptionResponse::new("plain"), None)
+            .unwrap();
+
+        let entries = history.get_all_transcriptions().unwrap();
+        let timed = entries.iter().find(|e| e.text == "Hello world.").unwrap();
+        assert_eq!(timed.segments, response.segments);
+        assert_eq!(timed.words, response.words);
+        assert_eq!(timed.language.as_deref(), Some("sv"));
+        let plain = entries.iter().find(|e| e.text == "plain").unwrap();
+        assert!(plain.segments.is_empty() && plain.words.is_empty());
+        assert_eq!(plain.language, None);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn usage_is_recorded_with_transcriptions() {
+        let dir = temp_data_dir("history_usage");
+        let mut history = HistoryManager::new(&dir).unwrap();
+        let model = crate::transcription::TranscriptionModel::from_id("whisper").unwrap();
+        let usage = TranscriptionUsage::new(&model, Some(30.0));
+
+        history
+            .save_transcription(&TranscriptionResponse::new("no usage"), None)
+            .unwrap();
+        history
+            .save_transcription(&TranscriptionResponse::new("billed"), Some(&usage))
+            .unwrap();
+
+        let entries = history.get_usage(None).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].usage, usage);
+        let tomorrow = Local::now() + chrono::Duration::days(1);
+        assert!(history.get_usage(Some(tomorrow)).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn cached_responses_are_replaced_by_key() {
+        let dir = temp_data_dir("history_cache");
+        let mut history = HistoryManager::new(&dir).unwrap();
+        assert_eq!(history.get_cached_response("abc").unwrap(), None);
+
+        history
+            .save_cached_response("abc", "whisper", &TranscriptionResponse::new("first"))
+            .unwrap();
+        let response = TranscriptionResponse {
+            language: Some("en".to_string()),
+            ..TranscriptionResponse::new("second")
+        };
+        history
+            .save_cached_response("abc", "whisper", &response)
+            .unwrap();
+
+        assert_eq!(history.get_cached_response("abc").unwrap(), Some(response));
+        assert!(history.get_all_transcriptions().unwrap().is_empty());
+
+        // Only the newest entries are kept
+        history
+            .save_cached_response("def", "whisper", &TranscriptionResponse::new("third"))
+            .unwrap();
+        assert_eq!(trim_cache(history.get_connection().unwrap(), 1).unwrap(), 1);
+        assert_eq!(history.get_cached_response("abc").unwrap(), None);
+        assert!(history.get_cached_response("def").unwrap().is_some());
+
+        assert_eq!(history.clear_cache().unwrap(), 1);
+        assert_eq!(history.get_cached_response("def").unwrap(), None);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn queued_recordings_track_failed_attempts() {
+        let dir = temp_data_dir("history_queue");
+        let mut history = HistoryManager::new(&dir).unwrap();
+        let job = QueuedRecording {
+            id: 0,
+            audio_path: dir.join("queue").join("ostt-recording-1.mp3"),
+            model: "whisper".to_string(),
+            language: Some("sv".to_string()),
+            translate: false,
+            diarize: true,
+            error: "Network error".to_string(),
+            attempts: 0,
+            queued_at: Local::now(),
+        };
+        let first = history.enqueue(&job).unwrap();
+        let second = history.enqueue(&job).unwrap();
+
+        history.record_queue_failure(first, "Server error").unwrap();
+        let queue = history.get_queue().unwrap();
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue[0].id, first);
+        assert_eq!(queue[0].audio_path, job.audio_path);
+        assert_eq!(queue[0].language.as_deref(), Some("sv"));
+        assert!(queue[0].diarize && !queue[0].translate);
+        assert_eq!(queue[0].error, "Server error");
+        assert_eq!(queue[0].attempts, 2);
+        assert_eq!(queue[1].attempts, 1);
+
+        assert!(history.remove_from_queue(first).unwrap());
+        assert!(!history.remove_from_queue(first).unwrap());
+        let queue = history.get_queue().unwrap();
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].id, second);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn databases_without_timing_columns_are_migrated() {
+        let dir = temp_data_dir("history_migration");
+        let connection = Connection::open(dir.join("transcription_history.db")).unwrap();
+        connection
+            .execute(
+                "CREATE TABLE transcriptions (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    text TEXT NOT NULL,
+                    created_at TEXT NOT NULL
+                )",
+                [],
+            )
+            .unwrap();
+        connection
+            .execute(
+                "INSERT INTO transcriptions (text, created_at) VALUES ('old', ?1)",
+                params![Local::now().to_rfc3339()],
+            )
+            .unwrap();
+        drop(connection);
+
+        let mut history = HistoryManager::new(&dir).unwrap();
+        let entry = history.get_transcription_by_index(1).unwrap().unwrap();
+        assert_eq!(entry.text, "old");
+        assert!(entry.segments.is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -229,10 +229,11 @@ impl HistoryViewer {
                 .iter()
                 .enumerate()
                 .map(|(i, entry)| {
-                    let timestamp = Line::styled(
-                        entry.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                        Style::default().fg(TIMESTAMP_FG),
-                    );
+                    let mut timestamp = entry.created_at.format("%Y-%m-%d %H:%M:%S").to_string();
+                    if let Some(language) = &entry.language {
+                        timestamp.push_str(&format!(" [{language}]"));
+                    }
+                    let timestamp = Line::styled(timestamp, Style::default().fg(TIMESTAMP_FG));
                     let text = Line::styled(entry.text.clone(), Style::default().fg(FG));
                     let mut item = ListItem::new(vec![timestamp, text]);
                     if Some(i) == hovered_index && Some(i) != selected_index {
@@ -88,19 +88,15 @@ impl KeywordsViewer {
                         break;
                     }
                 }
-                Event::Mouse(mouse) => {
-                    if !self.input_mode {
-                        match mouse.kind {
-                            MouseEventKind::ScrollUp => {
-                                self.list_state.select_previous();
-                            }
-                            MouseEventKind::ScrollDown => {
-                                self.list_state.select_next();
-                            }
-                            _ => {}
-                        }
+                Event::Mouse(mouse) if !self.input_mode => match mouse.kind {
+                    MouseEventKind::ScrollUp => {
+                        self.list_state.select_previous();
                     }
-                }
+                    MouseEventKind::ScrollDown => {
+                        self.list_state.select_next();
+                    }
+                    _ => {}
+                },
                 _ => {}
             }
         }
@@ -107,7 +107,7 @@ fn cleanup_old_logs(log_dir: &PathBuf) -> Result<(), anyhow::Error> {
         .collect();
 
     // Sort by modification time (newest first)
-    log_files.sort_by(|a, b| b.1.cmp(&a.1));
+    log_files.sort_by_key(|entry| std::cmp::Reverse(entry.1));
 
     // Remove files beyond the max count
     for (path, _) in log_files.iter().skip(MAX_LOG_FILES) {
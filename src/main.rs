@@ -1,4 +1,12 @@
+use std::process::ExitCode;
+
 #[tokio::main]
-async fn main() -> Result<(), anyhow::Error> {
-    ostt::run().await
+async fn main() -> ExitCode {
+    match ostt::run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            ExitCode::from(ostt::transcription::TranscriptionError::exit_code_for(&e))
+        }
+    }
 }
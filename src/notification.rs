@@ -0,0 +1,50 @@
+//! Desktop notifications for ostt.
+//!
+//! Shows a notification using osascript (macOS) or notify-send (Linux), so work that runs
+//! without a terminal in view, such as `ostt queue run`, can report when it is done.
+
+use std::process::Command;
+
+/// Shows a desktop notification with a title and a body.
+///
+/// Uses osascript on macOS and notify-send elsewhere. Does not fail if no notification tool
+/// is available; the failure is only logged.
+pub fn notify(title: &str, body: &str) {
+    #[cfg(target_os = "macos")]
+    let result = Command::new("osascript")
+        .arg("-e")
+        .arg(format!(
+            "display notification {} with title {}",
+            applescript_string(body),
+            applescript_string(title)
+        ))
+        .output();
+    #[cfg(not(target_os = "macos"))]
+    let result = Command::new("notify-send")
+        .args(["--app-name", "ostt", title, body])
+        .output();
+
+    match result {
+        Ok(output) if output.status.success() => {
+            tracing::debug!("Desktop notification shown: {title}");
+        }
+        Ok(output) => {
+            tracing::warn!(
+                "Failed to show desktop notification: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Err(e) => {
+            #[cfg(target_os = "macos")]
+            tracing::warn!("No notification tool available (osascript failed: {e})");
+            #[cfg(not(target_os = "macos"))]
+            tracing::warn!("No notification tool available (notify-send not found: {e})");
+        }
+    }
+}
+
+/// Quotes text as an AppleScript string literal.
+#[cfg(target_os = "macos")]
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
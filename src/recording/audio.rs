@@ -11,6 +11,7 @@ use hound::WavWriter;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::UnboundedSender;
 
 #[cfg(target_os = "linux")]
 use std::fs::OpenOptions;
@@ -25,6 +26,7 @@ use std::os::unix::io::AsRawFd;
 /// - Saves audio via ffmpeg for format flexibility
 /// - Automatic cleanup of temporary files
 /// - Pause and resume support
+/// - Optional forwarding of captured audio for live transcription
 pub struct AudioRecorder {
     /// Actual recording sample rate from device
     sample_rate: u32,
@@ -38,6 +40,8 @@ pub struct AudioRecorder {
     is_paused: Arc<Mutex<bool>>,
     /// Device name or "default" to use the system default device
     device_name: String,
+    /// Receives each captured mono chunk while recording (used for live transcription)
+    audio_sink: Option<UnboundedSender<Vec<i16>>>,
 }
 
 impl AudioRecorder {
@@ -57,9 +61,18 @@ impl AudioRecorder {
             device_channels: 1,
             is_paused: Arc::new(Mutex::new(false)),
             device_name,
+            audio_sink: None,
         }
     }
 
+    /// Forwards every captured mono chunk to `sink` while recording is not paused.
+    ///
+    /// Must be called before `start_recording()`. The sink is dropped when recording stops,
+    /// which closes the channel.
+    pub fn set_audio_sink(&mut self, sink: UnboundedSender<Vec<i16>>) {
+        self.audio_sink = Some(sink);
+    }
+
     /// Starts recording from the configured input device.
     ///
     /// # Errors
@@ -112,13 +125,19 @@ impl AudioRecorder {
         let samples_arc = Arc::clone(&self.samples);
         let pause_arc = Arc::clone(&self.is_paused);
         let callback_channels = num_channels;
+        let callback_sink = self.audio_sink.clone();
 
         let stream = device.build_input_stream(
             &device_config.into(),
             move |data: &[i16], _: &cpal::InputCallbackInfo| {
                 let is_paused = *pause_arc.lock().unwrap();
                 if !is_paused {
-                    Self::handle_audio_callback(data, &samples_arc, callback_channels);
+                    Self::handle_audio_callback(
+                        data,
+                        &samples_arc,
+                        callback_channels,
+                        callback_sink.as_ref(),
+                    );
                 }
             },
             |err| {
@@ -149,8 +168,9 @@ impl AudioRecorder {
     /// - If temporary WAV creation fails
     /// - If ffmpeg conversion fails
     pub fn stop_recording(&mut self, output_path: Option<PathBuf>, format: &str) -> Result<()> {
-        // Stop the audio stream
+        // Stop the audio stream and close the live audio channel
         self.stream = None;
+        self.audio_sink = None;
 
         let samples = self.samples.lock().unwrap().clone();
         let sample_count = samples.len();
@@ -196,13 +216,16 @@ impl AudioRecorder {
 
     /// Handles incoming audio data from the audio callback.
     ///
-    /// Converts multi-channel audio to mono by averaging all channels.
+    /// Converts multi-channel audio to mono by averaging all channels, and forwards the
+    /// new mono samples to the audio sink if one is set.
     fn handle_audio_callback(
         data: &[i16],
         samples_arc: &Arc<Mutex<Vec<i16>>>,
         num_channels: usize,
+        sink: Option<&UnboundedSender<Vec<i16>>>,
     ) {
         let mut samples = samples_arc.lock().unwrap();
+        let chunk_start = samples.len();
 
         match num_channels {
             1 => {
@@ -227,6 +250,11 @@ impl AudioRecorder {
                 }
             }
         }
+
+        if let Some(sink) = sink {
+            // The receiver is gone if the live session ended early; recording continues
+            let _ = sink.send(samples[chunk_start..].to_vec());
+        }
     }
 
     /// Saves audio samples as a temporary WAV file.
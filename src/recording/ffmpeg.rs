@@ -1,11 +1,15 @@
-//! FFmpeg locator utility.
+//! FFmpeg locator and helpers.
 //!
 //! Provides cross-platform ffmpeg binary discovery. Checks standard installation
 //! locations before falling back to PATH search. This ensures ffmpeg can be found
 //! even when running in environments with limited PATH setup (e.g., iTerm commands).
+//!
+//! Also provides the probing, silence detection and extraction used to split long
+//! audio files for transcription.
 
 use anyhow::{anyhow, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 /// Locates the ffmpeg binary on the system.
 ///
@@ -86,10 +90,235 @@ fn find_in_path(binary_name: &str) -> Result<PathBuf> {
     ))
 }
 
+/// Returns the file extension for recordings made with an `[audio] output_format`.
+///
+/// The first word of the format is the ffmpeg codec, e.g. `libopus` in `"libopus -b:a 24k"`.
+pub fn extension_for_output_format(output_format: &str) -> &str {
+    let codec = output_format.split_whitespace().next().unwrap_or("mp3");
+    match codec {
+        "libopus" => "ogg",
+        "libvorbis" => "ogg",
+        "flac" => "flac",
+        "aac" => "m4a",
+        "pcm_s16le" => "wav",
+        _ => codec,
+    }
+}
+
+/// A span of silence detected in an audio file, in seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Silence {
+    pub start: f64,
+    pub end: f64,
+}
+
+impl Silence {
+    /// Returns the middle of the silence, the safest place to cut.
+    pub fn midpoint(&self) -> f64 {
+        (self.start + self.end) / 2.0
+    }
+}
+
+/// Returns the duration of an audio file in seconds.
+///
+/// Reads the `Duration:` line that ffmpeg prints for its input, so ffprobe is not required.
+///
+/// # Errors
+/// - If ffmpeg cannot be found or run
+/// - If the duration cannot be determined
+pub fn probe_duration(input: &Path) -> Result<f64> {
+    let ffmpeg_path = find_ffmpeg()?;
+    let output = Command::new(&ffmpeg_path)
+        .arg("-hide_banner")
+        .arg("-i")
+        .arg(input)
+        .output()
+        .map_err(|e| anyhow!("Failed to run ffmpeg: {e}"))?;
+
+    // ffmpeg exits with an error because no output is given; the input info is still printed
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    parse_duration(&stderr)
+        .ok_or_else(|| anyhow!("Could not determine duration of {}", input.display()))
+}
+
+/// Detects silences of at least `min_silence_secs` below `noise_db` (e.g. -30).
+///
+/// # Errors
+/// - If ffmpeg cannot be found or fails to decode the input
+pub fn detect_silences(input: &Path, noise_db: i32, min_silence_secs: f64) -> Result<Vec<Silence>> {
+    let ffmpeg_path = find_ffmpeg()?;
+    let output = Command::new(&ffmpeg_path)
+        .arg("-hide_banner")
+        .arg("-nostats")
+        .arg("-i")
+        .arg(input)
+        .arg("-af")
+        .arg(format!(
+            "silencedetect=noise={noise_db}dB:d={min_silence_secs}"
+        ))
+        .arg("-f")
+        .arg("null")
+        .arg("-")
+        .output()
+        .map_err(|e| anyhow!("Failed to run ffmpeg: {e}"))?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(anyhow!("ffmpeg silence detection failed: {stderr}"));
+    }
+    Ok(parse_silences(&stderr))
+}
+
+/// Extracts `duration` seconds starting at `start` into a mono MP3 file.
+///
+/// The audio is re-encoded at a low, speech-friendly bitrate so chunk sizes are predictable.
+///
+/// # Errors
+/// - If ffmpeg cannot be found or the extraction fails
+pub fn extract_segment(input: &Path, start: f64, duration: f64, output_path: &Path) -> Result<()> {
+    let ffmpeg_path = find_ffmpeg()?;
+    let output = Command::new(&ffmpeg_path)
+        .arg("-loglevel")
+        .arg("error")
+        .arg("-ss")
+        .arg(format!("{start:.3}"))
+        .arg("-t")
+        .arg(format!("{duration:.3}"))
+        .arg("-i")
+        .arg(input)
+        .arg("-vn")
+        .arg("-ac")
+        .arg("1")
+        .arg("-ar")
+        .arg("16000")
+        .arg("-acodec")
+        .arg("libmp3lame")
+        .arg("-b:a")
+        .arg("32k")
+        .arg("-y")
+        .arg(output_path)
+        .output()
+        .map_err(|e| anyhow!("Failed to run ffmpeg: {e}"))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        tracing::error!("ffmpeg extraction failed: {}", error_msg);
+        Err(anyhow!("Failed to split audio: {error_msg}"))
+    }
+}
+
+/// Decodes an audio file to mono 32-bit float samples at `sample_rate`.
+///
+/// Used by the offline whisper.cpp backend, which takes raw 16 kHz PCM.
+///
+/// # Errors
+/// - If ffmpeg cannot be found or fails to decode the input
+#[cfg(feature = "local-whisper")]
+pub fn decode_pcm(input: &Path, sample_rate: u32) -> Result<Vec<f32>> {
+    let ffmpeg_path = find_ffmpeg()?;
+    let output = Command::new(&ffmpeg_path)
+        .arg("-loglevel")
+        .arg("error")
+        .arg("-i")
+        .arg(input)
+        .arg("-vn")
+        .arg("-ac")
+        .arg("1")
+        .arg("-ar")
+        .arg(sample_rate.to_string())
+        .arg("-f")
+        .arg("f32le")
+        .arg("-")
+        .output()
+        .map_err(|e| anyhow!("Failed to run ffmpeg: {e}"))?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        tracing::error!("ffmpeg decoding failed: {}", error_msg);
+        return Err(anyhow!("Failed to decode audio: {error_msg}"));
+    }
+    Ok(output
+        .stdout
+        .chunks_exact(4)
+        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .collect())
+}
+
+/// Parses `Duration: HH:MM:SS.ss` from ffmpeg output.
+fn parse_duration(stderr: &str) -> Option<f64> {
+    let line = stderr
+        .lines()
+        .find(|l| l.trim_start().starts_with("Duration:"))?;
+    let value = line
+        .trim_start()
+        .strip_prefix("Duration:")?
+        .split(',')
+        .next()?
+        .trim();
+    let mut parts = value.split(':');
+    let hours: f64 = parts.next()?.parse().ok()?;
+    let minutes: f64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+/// Parses `silence_start` / `silence_end` pairs printed by the silencedetect filter.
+fn parse_silences(stderr: &str) -> Vec<Silence> {
+    let mut silences = Vec::new();
+    let mut start = None;
+    for line in stderr.lines() {
+        if let Some(value) = value_after(line, "silence_start:") {
+            start = Some(value);
+        } else if let Some(end) = value_after(line, "silence_end:") {
+            if let Some(start) = start.take() {
+                silences.push(Silence { start, end });
+            }
+        }
+    }
+    silences
+}
+
+/// Returns the number following `key` in a line of ffmpeg output.
+fn value_after(line: &str, key: &str) -> Option<f64> {
+    let rest = &line[line.find(key)? + key.len()..];
+    rest.split_whitespace().next()?.parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parses_duration_from_input_info() {
+        let stderr = "Input #0, mp3, from 'talk.mp3':\n  Duration: 01:02:03.50, start: 0.025057, bitrate: 64 kb/s\n";
+        assert_eq!(parse_duration(stderr), Some(3723.5));
+        assert_eq!(parse_duration("  Duration: N/A, bitrate: N/A"), None);
+    }
+
+    #[test]
+    fn parses_silence_pairs() {
+        let stderr = "[silencedetect @ 0x1] silence_start: 12.5\n\
+                      [silencedetect @ 0x1] silence_end: 13.25 | silence_duration: 0.75\n\
+                      [silencedetect @ 0x1] silence_start: 40\n";
+        assert_eq!(
+            parse_silences(stderr),
+            vec![Silence {
+                start: 12.5,
+                end: 13.25
+            }]
+        );
+    }
+
+    #[test]
+    fn recording_extension_follows_the_codec() {
+        assert_eq!(extension_for_output_format("mp3 -ab 16k -ar 12000"), "mp3");
+        assert_eq!(extension_for_output_format("libopus -b:a 24k"), "ogg");
+        assert_eq!(extension_for_output_format("pcm_s16le"), "wav");
+        assert_eq!(extension_for_output_format(""), "mp3");
+    }
+
     #[test]
     fn test_find_ffmpeg() {
         // This test will succeed if ffmpeg is installed
@@ -2,6 +2,7 @@
 //!
 //! Supports frequency spectrum and time-domain waveform visualization modes.
 //! Handles real-time display updates, volume metering, and user input during recording.
+//! During live transcription, interim and final text is shown under the visualization.
 
 use crossterm::{
     event::{self, Event, KeyCode, MouseEventKind},
@@ -11,7 +12,7 @@ use crossterm::{
 use ratatui::{
     prelude::*,
     style::{Color, Style},
-    widgets::{ListState, Sparkline},
+    widgets::{ListState, Paragraph, Sparkline, Wrap},
 };
 use std::error::Error;
 use std::io::{stdout, Stdout};
@@ -19,7 +20,7 @@ use std::io::{stdout, Stdout};
 use crate::config::file::ProcessAction;
 use crate::config::VisualizationType;
 use crate::process::picker::render_picker_frame;
-use crate::transcription::TranscriptionAnimation;
+use crate::transcription::{LiveTranscript, TranscriptionAnimation};
 
 use super::visualizations::{resize_waveform, update_waveform, SpectrumAnalyzer};
 
@@ -72,8 +73,13 @@ pub struct OsttTui {
     visualization_type: VisualizationType,
     /// Spectrum analyzer (used when visualization_type is Spectrum)
     spectrum_analyzer: Option<SpectrumAnalyzer>,
+    /// Live transcript shown under the visualization (None when not streaming)
+    live_transcript: Option<LiveTranscript>,
 }
 
+/// Number of terminal rows reserved for the live transcript
+const LIVE_CAPTION_HEIGHT: u16 = 3;
+
 impl OsttTui {
     /// Creates a new TUI instance and enters alternate screen mode.
     ///
@@ -126,9 +132,15 @@ impl OsttTui {
             pause_start_time: None,
             visualization_type,
             spectrum_analyzer,
+            live_transcript: None,
         })
     }
 
+    /// Sets the live transcript to show under the visualization on the next render.
+    pub fn set_live_transcript(&mut self, transcript: LiveTranscript) {
+        self.live_transcript = Some(transcript);
+    }
+
     /// Renders the visualization with current volume and recording duration.
     ///
     /// # Errors
@@ -182,6 +194,7 @@ impl OsttTui {
         let last_peak = self.last_peak;
         let peak_volume_threshold = self.peak_volume_threshold;
         let recording_duration = self.get_recording_duration();
+        let live_transcript = self.live_transcript.as_ref();
 
         self.terminal.draw(|frame| {
             let area = frame.area();
@@ -200,12 +213,19 @@ impl OsttTui {
             };
 
             let footer_height = 1;
+            let caption_height = if live_transcript.is_some() {
+                LIVE_CAPTION_HEIGHT
+            } else {
+                0
+            };
 
             let content_area = Rect {
                 x: padded_area.x,
                 y: padded_area.y,
                 width: padded_area.width,
-                height: padded_area.height.saturating_sub(footer_height),
+                height: padded_area
+                    .height
+                    .saturating_sub(footer_height + caption_height),
             };
 
             let top_area_height = content_area.height / 3 * 2;
@@ -249,6 +269,16 @@ impl OsttTui {
 
             frame.render_widget(bottom_sparkline, bottom_area);
 
+            if let Some(transcript) = live_transcript {
+                let caption_area = Rect {
+                    x: content_area.x,
+                    y: content_area.y + content_area.height,
+                    width: content_area.width,
+                    height: caption_height,
+                };
+                render_live_caption(frame, caption_area, transcript);
+            }
+
             let footer_area = Rect {
                 x: padded_area.x,
                 y: padded_area.y + padded_area.height.saturating_sub(footer_height),
@@ -516,3 +546,71 @@ impl OsttTui {
         Ok(())
     }
 }
+
+/// Renders the live transcript, keeping the most recent text visible.
+///
+/// Final text is shown in the regular color, the utterance in progress is dimmed.
+fn render_live_caption(frame: &mut Frame, area: Rect, transcript: &LiveTranscript) {
+    let final_text = transcript.final_text();
+    let interim_text = transcript.interim_text();
+
+    // Leave one row of slack since word wrapping can use more rows than a plain character count
+    let max_chars = (area.width as usize) * (area.height.saturating_sub(1).max(1) as usize);
+    let (final_text, interim_text) = caption_tail(&final_text, interim_text, max_chars);
+
+    let line = if final_text.is_empty() && interim_text.is_empty() {
+        Line::from(Span::styled(
+            "Listening...",
+            Style::default().fg(Color::DarkGray),
+        ))
+    } else {
+        let separator = if final_text.is_empty() || interim_text.is_empty() {
+            ""
+        } else {
+            " "
+        };
+        Line::from(vec![
+            Span::styled(final_text, Style::default().fg(Color::Rgb(185, 207, 212))),
+            Span::raw(separator),
+            Span::styled(
+                interim_text,
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::ITALIC),
+            ),
+        ])
+    };
+
+    let caption = Paragraph::new(line)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().bg(Color::Rgb(0, 0, 0)));
+    frame.render_widget(caption, area);
+}
+
+/// Returns the end of the final and interim text so that together they fit in `max_chars`.
+///
+/// Cuts from the start, preferring the interim text since it is the most recent speech.
+fn caption_tail(final_text: &str, interim_text: &str, max_chars: usize) -> (String, String) {
+    let interim_len = interim_text.chars().count();
+    if interim_len >= max_chars {
+        return (String::new(), tail_chars(interim_text, max_chars));
+    }
+    let final_budget = max_chars - interim_len;
+    (
+        tail_chars(final_text, final_budget.saturating_sub(1)),
+        interim_text.to_string(),
+    )
+}
+
+/// Returns the last `max_chars` characters of `text`, prefixed with an ellipsis when cut.
+fn tail_chars(text: &str, max_chars: usize) -> String {
+    let len = text.chars().count();
+    if len <= max_chars {
+        return text.to_string();
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+    let tail: String = text.chars().skip(len - max_chars + 1).collect();
+    format!("…{tail}")
+}
@@ -6,6 +6,8 @@
 //! 2. Submit a transcription request with the upload URL and options
 //! 3. Poll for the completed transcript
 //!
+//! Live transcription uses the Universal Streaming WebSocket API instead.
+//!
 //! Performance optimizations based on AssemblyAI best practices:
 //! - 3-second polling intervals (AssemblyAI recommended, not too aggressive)
 //! - Exponential backoff retry for upload failures
@@ -13,9 +15,24 @@
 use std::path::Path;
 use std::time::Duration;
 
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
-use super::TranscriptionConfig;
+use super::{
+    base_url, http_client_builder, AudioUpload, KeywordSupport, Provider, ProviderCapabilities,
+    TranscriptSegment, TranscriptWord, TranscriptionConfig, TranscriptionResponse,
+};
+use crate::transcription::diarization::SpeakerNumbers;
+use crate::transcription::error::TranscriptionError;
+use crate::transcription::streaming::{LiveRequest, LiveUpdate};
+use crate::transcription::{TranscriptionModel, TranscriptionProvider};
+
+const PROVIDER: TranscriptionProvider = TranscriptionProvider::new("assemblyai", "AssemblyAI");
+
+const BASE_URL: &str = "https://api.assemblyai.com/v2";
+
+/// Universal Streaming (v3) WebSocket endpoint for live transcription
+const LIVE_ENDPOINT: &str = "wss://streaming.assemblyai.com/v3/ws";
 
 /// Maximum number of poll attempts before timing out (5 minutes at 3-second intervals)
 const MAX_POLL_ATTEMPTS: u32 = 100;
@@ -57,6 +74,8 @@ struct TranscriptRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     filter_profanity: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    language_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     language_detection: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     language_detection_options: Option<LanguageDetectionOptionsRequest>,
@@ -64,6 +83,8 @@ struct TranscriptRequest {
     punctuate: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     keyterms_prompt: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speaker_labels: Option<bool>,
 }
 
 /// Response from the transcription endpoint (both submit and poll)
@@ -73,291 +94,501 @@ struct TranscriptResponse {
     status: String,
     text: Option<String>,
     error: Option<String>,
+    /// Word timings of a completed transcript
+    #[serde(default)]
+    words: Option<Vec<TimedText>>,
+    /// Utterances of a completed transcript (only with speaker labels)
+    #[serde(default)]
+    utterances: Option<Vec<TimedText>>,
+    /// Language of a completed transcript, detected or requested
+    #[serde(default)]
+    language_code: Option<String>,
+}
+
+/// A word or utterance of a completed transcript, with times in milliseconds
+#[derive(Debug, Deserialize)]
+struct TimedText {
+    text: String,
+    start: u64,
+    end: u64,
+    confidence: Option<f64>,
+    /// Speaker label (`A`, `B`, ...) when speaker labels are enabled
+    speaker: Option<String>,
+}
+
+/// Message received on a Universal Streaming connection
+#[derive(Debug, Deserialize)]
+struct LiveMessage {
+    #[serde(rename = "type", default)]
+    message_type: String,
+    #[serde(default)]
+    transcript: String,
+    #[serde(default)]
+    end_of_turn: bool,
+    #[serde(default)]
+    turn_is_formatted: bool,
+    error: Option<String>,
 }
 
-/// Transcribes an audio file using AssemblyAI's API.
+/// AssemblyAI transcription provider.
 ///
 /// Uses a three-step process: upload audio, submit transcription request, poll for result.
 /// Polls at 3-second intervals with a maximum timeout of 5 minutes.
 /// Implements retry logic with exponential backoff for upload failures.
-pub async fn transcribe(config: &TranscriptionConfig, audio_path: &Path) -> anyhow::Result<String> {
-    let audio_data =
-        std::fs::read(audio_path).map_err(|e| anyhow::anyhow!("Failed to read audio file: {e}"))?;
-
-    // Configure client with timeouts
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(60))
-        .connect_timeout(Duration::from_secs(10))
-        .build()
-        .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {e}"))?;
-
-    let base_url = config.model.endpoint();
-
-    // Step 1: Upload audio with retry logic for transient failures
-    let upload_url = upload_with_retry(&client, base_url, &config.api_key, audio_data).await?;
-
-    // Step 2: Submit transcription request
-    let assemblyai_config = &config.providers.assemblyai;
-
-    // Build language_detection_options if any values are set
-    let language_detection_options = if assemblyai_config
-        .language_detection_options
-        .expected_languages
-        .is_some()
-        || assemblyai_config
-            .language_detection_options
-            .fallback_language
-            .is_some()
-    {
-        Some(LanguageDetectionOptionsRequest {
-            expected_languages: assemblyai_config
-                .language_detection_options
-                .expected_languages
-                .clone(),
-            fallback_language: assemblyai_config
-                .language_detection_options
-                .fallback_language
-                .clone(),
-        })
-    } else {
-        None
-    };
-
-    let mut request = TranscriptRequest {
-        audio_url: upload_url,
-        speech_models: Some(vec![config.model.api_model_name().to_string()]),
-        format_text: Some(assemblyai_config.format_text),
-        disfluencies: Some(assemblyai_config.disfluencies),
-        filter_profanity: Some(assemblyai_config.filter_profanity),
-        language_detection: Some(assemblyai_config.language_detection),
-        language_detection_options,
-        punctuate: Some(assemblyai_config.punctuate),
-        keyterms_prompt: None,
-    };
-
-    // Add keywords as keyterms_prompt if any
-    if !config.keywords.is_empty() {
-        request.keyterms_prompt = Some(config.keywords.clone());
-    }
+pub struct AssemblyAi;
 
-    tracing::debug!("Submitting transcription request...");
-    let submit_response = match client
-        .post(format!("{base_url}/transcript"))
-        .header("Authorization", &config.api_key)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await
-    {
-        Ok(resp) => resp,
-        Err(e) => {
-            let error_msg = if e.is_connect() {
-                "Failed to connect to AssemblyAI API server. Check your internet connection."
-                    .to_string()
-            } else if e.is_timeout() {
-                "Request to AssemblyAI timed out. The API server is not responding.".to_string()
-            } else {
-                format!("AssemblyAI network error: {e}")
-            };
-            return Err(anyhow::anyhow!(error_msg));
-        }
-    };
-
-    if !submit_response.status().is_success() {
-        let status = submit_response.status();
-        let error_body = submit_response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(anyhow::anyhow!(format_error(status.as_u16(), &error_body)));
+#[async_trait]
+impl Provider for AssemblyAi {
+    fn info(&self) -> TranscriptionProvider {
+        PROVIDER
     }
 
-    let transcript: TranscriptResponse = submit_response
-        .json()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to parse AssemblyAI submit response: {e}"))?;
+    fn models(&self) -> Vec<TranscriptionModel> {
+        vec![TranscriptionModel::new(
+            PROVIDER,
+            "assemblyai-universal-3-pro",
+            "universal-3-pro",
+            "Universal 3 Pro (best accuracy)",
+        )]
+    }
 
-    let transcript_id = transcript.id;
-    tracing::debug!("Transcription submitted, id: {transcript_id}");
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            keywords: KeywordSupport::Keyterms,
+            streaming: true,
+            diarization: true,
+            timestamps: true,
+            ..Default::default()
+        }
+    }
 
-    // Step 3: Poll for result with timeout
-    // Poll at 3-second intervals (AssemblyAI recommended)
-    let poll_url = format!("{base_url}/transcript/{transcript_id}");
-    let mut attempts: u32 = 0;
+    fn http_client(&self, config: &TranscriptionConfig) -> anyhow::Result<reqwest::Client> {
+        // Use shorter default timeouts than reqwest's unless [network] sets them
+        let network = &config.network;
+        let mut builder = http_client_builder(network)?;
+        if network.request_timeout_secs.is_none() {
+            builder = builder.timeout(Duration::from_secs(60));
+        }
+        if network.connect_timeout_secs.is_none() {
+            builder = builder.connect_timeout(Duration::from_secs(10));
+        }
+        builder
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {e}"))
+    }
 
-    loop {
-        attempts += 1;
+    /// Uploads audio to AssemblyAI with exponential backoff retry logic.
+    ///
+    /// AssemblyAI recommends implementing retry logic for transient upload errors
+    /// that may occur due to temporary server issues.
+    async fn upload(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio_path: &Path,
+    ) -> anyhow::Result<AudioUpload> {
+        let (audio_data, _) = AudioUpload::from_path(audio_path)?.into_bytes()?;
+
+        let mut retries = 0;
+        let mut delay_ms = INITIAL_RETRY_DELAY_MS;
+
+        loop {
+            tracing::debug!(
+                "Uploading audio to AssemblyAI (attempt {} of {})...",
+                retries + 1,
+                MAX_UPLOAD_RETRIES + 1
+            );
+
+            match self.try_upload(client, config, &audio_data).await {
+                Ok(upload_url) => return Ok(AudioUpload::Url(upload_url)),
+                // An invalid key or a rejected file fails the same way on every attempt
+                Err(e) if !TranscriptionError::is_transient_error(&e) => return Err(e),
+                Err(e) => {
+                    retries += 1;
+                    if retries > MAX_UPLOAD_RETRIES {
+                        return Err(e.context(format!(
+                            "Failed to upload audio after {} attempts",
+                            MAX_UPLOAD_RETRIES + 1
+                        )));
+                    }
+
+                    tracing::warn!(
+                        "Upload attempt {} failed: {}. Retrying in {}ms...",
+                        retries,
+                        e,
+                        delay_ms
+                    );
+                    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                    delay_ms *= 2; // Exponential backoff
+                }
+            }
+        }
+    }
 
-        if attempts > MAX_POLL_ATTEMPTS {
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let AudioUpload::Url(upload_url) = audio else {
             return Err(anyhow::anyhow!(
-                "AssemblyAI transcription timed out after {} seconds. The audio may be too long or the API is experiencing delays.",
-                MAX_POLL_ATTEMPTS as u64 * POLL_INTERVAL_SECS
+                "AssemblyAI requires the audio to be uploaded before transcription"
             ));
-        }
+        };
 
-        let poll_response = match client
-            .get(&poll_url)
-            .header("Authorization", &config.api_key)
-            .send()
-            .await
+        let assemblyai_config = &config.providers.assemblyai;
+
+        // A requested language turns off detection; otherwise build
+        // language_detection_options if any values are set
+        let language_detection_options = if config.language.is_some() {
+            None
+        } else if assemblyai_config
+            .language_detection_options
+            .expected_languages
+            .is_some()
+            || assemblyai_config
+                .language_detection_options
+                .fallback_language
+                .is_some()
         {
-            Ok(resp) => resp,
-            Err(e) => {
-                let error_msg = if e.is_connect() {
-                    "Failed to connect to AssemblyAI API server while polling. Check your internet connection.".to_string()
-                } else if e.is_timeout() {
-                    "AssemblyAI poll request timed out. The API server is not responding."
-                        .to_string()
-                } else {
-                    format!("AssemblyAI poll network error: {e}")
-                };
-                return Err(anyhow::anyhow!(error_msg));
-            }
+            Some(LanguageDetectionOptionsRequest {
+                expected_languages: assemblyai_config
+                    .language_detection_options
+                    .expected_languages
+                    .clone(),
+                fallback_language: assemblyai_config
+                    .language_detection_options
+                    .fallback_language
+                    .clone(),
+            })
+        } else {
+            None
         };
 
-        if !poll_response.status().is_success() {
-            let status = poll_response.status();
-            let error_body = poll_response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(anyhow::anyhow!(format_error(status.as_u16(), &error_body)));
+        let mut request = TranscriptRequest {
+            audio_url: upload_url,
+            speech_models: Some(vec![config.model.api_model_name().to_string()]),
+            format_text: Some(assemblyai_config.format_text),
+            disfluencies: Some(assemblyai_config.disfluencies),
+            filter_profanity: Some(assemblyai_config.filter_profanity),
+            language_code: config.language.clone(),
+            language_detection: Some(
+                config.language.is_none() && assemblyai_config.language_detection,
+            ),
+            language_detection_options,
+            punctuate: Some(assemblyai_config.punctuate),
+            keyterms_prompt: None,
+            speaker_labels: config.diarize.then_some(true),
+        };
+
+        // Add keywords as keyterms_prompt if any
+        if !config.keywords.is_empty() {
+            request.keyterms_prompt = Some(config.keywords.clone());
         }
 
-        let result: TranscriptResponse = poll_response
-            .json()
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to parse AssemblyAI poll response: {e}"))?;
+        Ok(client
+            .post(format!("{}/transcript", base_url(config, BASE_URL)))
+            .header("Authorization", &config.api_key)
+            .header("Content-Type", "application/json")
+            .json(&request))
+    }
 
-        tracing::debug!(
-            "Poll attempt {}/{}: status={}, id={}",
-            attempts,
-            MAX_POLL_ATTEMPTS,
-            result.status,
-            result.id
-        );
+    fn parse_response(
+        &self,
+        _config: &TranscriptionConfig,
+        body: &str,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let result: TranscriptResponse = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse AssemblyAI poll response: {e}"))?;
 
         match result.status.as_str() {
             "completed" => {
                 let text = result.text.ok_or_else(|| {
                     anyhow::anyhow!("AssemblyAI returned completed status but no transcript text")
                 })?;
-                let trimmed = text.trim().to_string();
-                tracing::debug!("Transcription completed: {} chars", trimmed.len());
-                return Ok(trimmed);
+                tracing::debug!("Transcription completed: {} chars", text.trim().len());
+                let mut speakers = SpeakerNumbers::default();
+                let segments = result
+                    .utterances
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|utterance| TranscriptSegment {
+                        text: utterance.text,
+                        start: millis_to_secs(utterance.start),
+                        end: millis_to_secs(utterance.end),
+                        confidence: utterance.confidence,
+                        speaker: utterance.speaker.map(|label| speakers.number(&label)),
+                    })
+                    .collect();
+                let words = result
+                    .words
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|word| TranscriptWord {
+                        text: word.text,
+                        start: millis_to_secs(word.start),
+                        end: millis_to_secs(word.end),
+                        confidence: word.confidence,
+                        speaker: word.speaker.map(|label| speakers.number(&label)),
+                    })
+                    .collect();
+                Ok(TranscriptionResponse {
+                    text,
+                    segments,
+                    words,
+                    language: result.language_code,
+                })
             }
             "error" => {
                 let error = result
                     .error
                     .unwrap_or_else(|| "Unknown transcription error".to_string());
-                return Err(anyhow::anyhow!("AssemblyAI transcription failed: {error}"));
+                Err(TranscriptionError::JobFailed {
+                    message: format!("AssemblyAI transcription failed: {error}"),
+                }
+                .into())
+            }
+            status => Err(anyhow::anyhow!(
+                "AssemblyAI transcript {} is not finished (status: {status})",
+                result.id
+            )),
+        }
+    }
+
+    fn live_request(&self, config: &TranscriptionConfig, sample_rate: u32) -> Option<LiveRequest> {
+        Self::universal_streaming_request(config, sample_rate)
+            .inspect_err(|e| tracing::warn!("Failed to build AssemblyAI live request: {e}"))
+            .ok()
+    }
+
+    /// Maps Universal Streaming turns to live updates.
+    ///
+    /// With `format_turns=true`, a finished turn is sent twice: first unformatted, then
+    /// punctuated and cased. Only the formatted one is final.
+    fn parse_live_message(&self, message: &str) -> anyhow::Result<Option<LiveUpdate>> {
+        let live_message: LiveMessage = serde_json::from_str(message)
+            .map_err(|e| anyhow::anyhow!("Failed to parse AssemblyAI live message: {e}"))?;
+
+        if let Some(error) = live_message.error {
+            return Err(anyhow::anyhow!(
+                "AssemblyAI live transcription error: {error}"
+            ));
+        }
+        if live_message.message_type != "Turn" {
+            tracing::debug!("AssemblyAI live event: {}", live_message.message_type);
+            return Ok(None);
+        }
+
+        Ok(Some(
+            if live_message.end_of_turn && live_message.turn_is_formatted {
+                LiveUpdate::Final(live_message.transcript)
+            } else {
+                LiveUpdate::Interim(live_message.transcript)
+            },
+        ))
+    }
+
+    fn live_close_message(&self) -> Option<&'static str> {
+        Some(r#"{"type":"Terminate"}"#)
+    }
+
+    async fn transcribe(
+        &self,
+        config: &TranscriptionConfig,
+        audio_path: &Path,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let client = self.http_client(config)?;
+
+        // Step 1: Upload audio with retry logic for transient failures
+        let audio = self.upload(&client, config, audio_path).await?;
+
+        // Step 2: Submit transcription request
+        tracing::debug!("Submitting transcription request...");
+        let request = self.build_request(&client, config, audio)?;
+        let body = self.send(config, request).await?;
+        let transcript: TranscriptResponse = serde_json::from_str(&body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse AssemblyAI submit response: {e}"))?;
+
+        let transcript_id = transcript.id;
+        tracing::debug!("Transcription submitted, id: {transcript_id}");
+
+        // Step 3: Poll for result with timeout
+        // Poll at 3-second intervals (AssemblyAI recommended)
+        let poll_url = format!("{}/transcript/{transcript_id}", base_url(config, BASE_URL));
+        let mut attempts: u32 = 0;
+
+        loop {
+            attempts += 1;
+
+            if attempts > MAX_POLL_ATTEMPTS {
+                return Err(TranscriptionError::poll_timeout(
+                    PROVIDER.name(),
+                    Duration::from_secs(MAX_POLL_ATTEMPTS as u64 * POLL_INTERVAL_SECS),
+                )
+                .into());
             }
-            _ => {
-                // Still processing (queued, processing, etc.)
-                tokio::time::sleep(Duration::from_secs(POLL_INTERVAL_SECS)).await;
+
+            let poll_request = client
+                .get(&poll_url)
+                .header("Authorization", &config.api_key);
+            let body = self.send(config, poll_request).await?;
+
+            let result: TranscriptResponse = serde_json::from_str(&body)
+                .map_err(|e| anyhow::anyhow!("Failed to parse AssemblyAI poll response: {e}"))?;
+
+            tracing::debug!(
+                "Poll attempt {}/{}: status={}, id={}",
+                attempts,
+                MAX_POLL_ATTEMPTS,
+                result.status,
+                result.id
+            );
+
+            match result.status.as_str() {
+                "completed" | "error" => return self.parse_response(config, &body),
+                _ => {
+                    // Still processing (queued, processing, etc.)
+                    tokio::time::sleep(Duration::from_secs(POLL_INTERVAL_SECS)).await;
+                }
             }
         }
     }
 }
 
-/// Uploads audio to AssemblyAI with exponential backoff retry logic.
-///
-/// AssemblyAI recommends implementing retry logic for transient upload errors
-/// that may occur due to temporary server issues.
-async fn upload_with_retry(
-    client: &reqwest::Client,
-    base_url: &str,
-    api_key: &str,
-    audio_data: Vec<u8>,
-) -> anyhow::Result<String> {
-    let mut retries = 0;
-    let mut delay_ms = INITIAL_RETRY_DELAY_MS;
-
-    loop {
-        tracing::debug!(
-            "Uploading audio to AssemblyAI (attempt {} of {})...",
-            retries + 1,
-            MAX_UPLOAD_RETRIES + 1
+impl AssemblyAi {
+    /// Builds the Universal Streaming request for a live session.
+    fn universal_streaming_request(
+        config: &TranscriptionConfig,
+        sample_rate: u32,
+    ) -> anyhow::Result<LiveRequest> {
+        let mut url = format!(
+            "{LIVE_ENDPOINT}?sample_rate={sample_rate}&encoding=pcm_s16le&format_turns=true"
         );
+        if !config.keywords.is_empty() {
+            let keyterms = serde_json::to_string(&config.keywords)?;
+            url.push_str(&format!(
+                "&keyterms_prompt={}",
+                urlencoding::encode(&keyterms)
+            ));
+        }
+        Ok(LiveRequest {
+            url,
+            headers: vec![("Authorization", config.api_key.clone())],
+        })
+    }
 
-        match try_upload(client, base_url, api_key, &audio_data).await {
-            Ok(upload_url) => return Ok(upload_url),
-            Err(e) => {
-                retries += 1;
-                if retries > MAX_UPLOAD_RETRIES {
-                    return Err(anyhow::anyhow!(
-                        "Failed to upload audio after {} attempts: {}",
-                        MAX_UPLOAD_RETRIES + 1,
-                        e
-                    ));
-                }
+    /// Attempts a single upload request to AssemblyAI.
+    async fn try_upload(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio_data: &[u8],
+    ) -> anyhow::Result<String> {
+        let request = client
+            .post(format!("{}/upload", base_url(config, BASE_URL)))
+            .header("Authorization", &config.api_key)
+            .header("Content-Type", "application/octet-stream")
+            .body(audio_data.to_vec());
+        let body = self.send(config, request).await?;
 
-                tracing::warn!(
-                    "Upload attempt {} failed: {}. Retrying in {}ms...",
-                    retries,
-                    e,
-                    delay_ms
-                );
-                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
-                delay_ms *= 2; // Exponential backoff
-            }
-        }
+        let upload: UploadResponse = serde_json::from_str(&body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse AssemblyAI upload response: {e}"))?;
+
+        tracing::debug!("Audio uploaded successfully");
+        Ok(upload.upload_url)
     }
 }
 
-/// Attempts a single upload request to AssemblyAI.
-async fn try_upload(
-    client: &reqwest::Client,
-    base_url: &str,
-    api_key: &str,
-    audio_data: &[u8],
-) -> anyhow::Result<String> {
-    let upload_response = client
-        .post(format!("{base_url}/upload"))
-        .header("Authorization", api_key)
-        .header("Content-Type", "application/octet-stream")
-        .body(audio_data.to_vec())
-        .send()
-        .await
-        .map_err(|e| {
-            if e.is_connect() {
-                anyhow::anyhow!(
-                    "Failed to connect to AssemblyAI API server. Check your internet connection."
-                )
-            } else if e.is_timeout() {
-                anyhow::anyhow!(
-                    "Request to AssemblyAI timed out. The API server is not responding."
-                )
-            } else {
-                anyhow::anyhow!("AssemblyAI network error: {e}")
+/// Converts AssemblyAI's millisecond timestamps to seconds
+fn millis_to_secs(millis: u64) -> f64 {
+    millis as f64 / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProvidersConfig;
+
+    #[test]
+    fn completed_transcript_includes_word_timings() {
+        let config = TranscriptionConfig::new(
+            AssemblyAi.models().remove(0),
+            String::new(),
+            Vec::new(),
+            ProvidersConfig::default(),
+        );
+        let body = r#"{"id":"abc","status":"completed","text":"Hello world.",
+            "words":[{"text":"Hello","start":250,"end":650,"confidence":0.97,"speaker":null},
+                     {"text":"world.","start":700,"end":1100,"confidence":0.91,"speaker":null}],
+            "utterances":null}"#;
+
+        let response = AssemblyAi.parse_response(&config, body).unwrap();
+        assert_eq!(response.text, "Hello world.");
+        assert!(response.segments.is_empty());
+        assert_eq!(
+            response.words[0],
+            TranscriptWord {
+                text: "Hello".to_string(),
+                start: 0.25,
+                end: 0.65,
+                confidence: Some(0.97),
+                speaker: None,
             }
-        })?;
-
-    if !upload_response.status().is_success() {
-        let status = upload_response.status();
-        let error_body = upload_response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(anyhow::anyhow!(format_error(status.as_u16(), &error_body)));
+        );
+        assert_eq!(response.words[1].end, 1.1);
     }
 
-    let upload: UploadResponse = upload_response
-        .json()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to parse AssemblyAI upload response: {e}"))?;
+    #[test]
+    fn utterances_carry_speaker_numbers() {
+        let config = TranscriptionConfig::new(
+            AssemblyAi.models().remove(0),
+            String::new(),
+            Vec::new(),
+            ProvidersConfig::default(),
+        );
+        let body = r#"{"id":"abc","status":"completed","text":"Hi. Hello.",
+            "words":[{"text":"Hi.","start":0,"end":300,"confidence":0.9,"speaker":"A"},
+                     {"text":"Hello.","start":500,"end":900,"confidence":0.9,"speaker":"B"}],
+            "utterances":[{"text":"Hi.","start":0,"end":300,"confidence":0.9,"speaker":"A"},
+                          {"text":"Hello.","start":500,"end":900,"confidence":0.9,"speaker":"B"}]}"#;
+
+        let response = AssemblyAi.parse_response(&config, body).unwrap();
+        assert_eq!(response.segments.len(), 2);
+        assert_eq!(response.segments[0].speaker, Some(1));
+        assert_eq!(response.segments[1].speaker, Some(2));
+        assert_eq!(response.words[1].speaker, Some(2));
+    }
 
-    tracing::debug!("Audio uploaded successfully");
-    Ok(upload.upload_url)
-}
+    #[test]
+    fn live_turns_are_final_only_once_formatted() {
+        let partial = r#"{"type":"Turn","turn_order":0,"transcript":"hello world",
+            "end_of_turn":false,"turn_is_formatted":false}"#;
+        let unformatted = r#"{"type":"Turn","turn_order":0,"transcript":"hello world",
+            "end_of_turn":true,"turn_is_formatted":false}"#;
+        let formatted = r#"{"type":"Turn","turn_order":0,"transcript":"Hello world.",
+            "end_of_turn":true,"turn_is_formatted":true}"#;
+
+        assert_eq!(
+            AssemblyAi.parse_live_message(partial).unwrap(),
+            Some(LiveUpdate::Interim("hello world".to_string()))
+        );
+        assert_eq!(
+            AssemblyAi.parse_live_message(unformatted).unwrap(),
+            Some(LiveUpdate::Interim("hello world".to_string()))
+        );
+        assert_eq!(
+            AssemblyAi.parse_live_message(formatted).unwrap(),
+            Some(LiveUpdate::Final("Hello world.".to_string()))
+        );
+    }
+
+    #[test]
+    fn live_session_events_and_errors() {
+        let begin = r#"{"type":"Begin","id":"abc","expires_at":1700000000}"#;
+        assert_eq!(AssemblyAi.parse_live_message(begin).unwrap(), None);
 
-/// Formats HTTP error codes into human-readable messages.
-fn format_error(status: u16, error_body: &str) -> String {
-    match status {
-        401 => "AssemblyAI API key is invalid or expired. Please run 'ostt auth' to update your API key.".to_string(),
-        403 => "You don't have permission to use AssemblyAI's API. Check your API key and account status.".to_string(),
-        429 => "Too many requests to AssemblyAI. You've hit the API rate limit. Please wait and try again.".to_string(),
-        500 | 502 | 503 | 504 => "AssemblyAI API server is experiencing issues. Please try again later.".to_string(),
-        _ => format!("AssemblyAI API error (status {status}): {error_body}"),
+        let error = r#"{"error":"Invalid API key"}"#;
+        assert!(AssemblyAi.parse_live_message(error).is_err());
     }
 }
@@ -0,0 +1,227 @@
+//! Azure OpenAI API implementation.
+//!
+//! Handles transcription requests to Whisper and GPT-4o Transcribe deployments of an Azure
+//! OpenAI resource. The multipart request is the same as OpenAI's; the URL names the
+//! deployment, carries an `api-version` query parameter and is authenticated with an
+//! `api-key` header.
+
+use async_trait::async_trait;
+
+use super::openai::{self, OpenAi};
+use super::verbose_json::VerboseJsonResponse;
+use super::{
+    AudioUpload, ModelCapabilities, Provider, ProviderCapabilities, TranscriptionConfig,
+    TranscriptionResponse,
+};
+use crate::transcription::{TranscriptionModel, TranscriptionProvider};
+
+const PROVIDER: TranscriptionProvider = TranscriptionProvider::new("azure", "Azure OpenAI");
+
+/// API version used when `[providers.azure].api_version` is not set
+const DEFAULT_API_VERSION: &str = "2025-03-01-preview";
+
+/// Azure OpenAI transcription provider.
+///
+/// Each model is served from a deployment at `{endpoint}/openai/deployments/{deployment}`,
+/// where the endpoint, deployment names and API version come from `[providers.azure]`.
+/// Models without a configured deployment use a deployment named after the model.
+///
+/// Keywords are passed as the `prompt` parameter, as for OpenAI.
+pub struct Azure;
+
+#[async_trait]
+impl Provider for Azure {
+    fn info(&self) -> TranscriptionProvider {
+        PROVIDER
+    }
+
+    fn models(&self) -> Vec<TranscriptionModel> {
+        vec![
+            TranscriptionModel::new(
+                PROVIDER,
+                "azure-gpt-4o-transcribe",
+                "gpt-4o-transcribe",
+                "GPT-4o Transcribe (Azure deployment)",
+            ),
+            TranscriptionModel::new(
+                PROVIDER,
+                "azure-gpt-4o-mini-transcribe",
+                "gpt-4o-mini-transcribe",
+                "GPT-4o Mini Transcribe (Azure deployment)",
+            ),
+            TranscriptionModel::new(
+                PROVIDER,
+                "azure-whisper",
+                "whisper-1",
+                "Whisper (Azure deployment)",
+            ),
+        ]
+    }
+
+    /// Deployments accept the same uploads as the OpenAI API.
+    fn capabilities(&self) -> ProviderCapabilities {
+        OpenAi.capabilities()
+    }
+
+    fn supports_translation(&self, model: &TranscriptionModel) -> bool {
+        OpenAi.supports_translation(model)
+    }
+
+    fn model_capabilities(&self, model: &TranscriptionModel) -> ModelCapabilities {
+        OpenAi.model_capabilities(model)
+    }
+
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let deployment = deployment_url(config)?;
+        let (form, path, debug_params) = openai::transcription_form(config, audio)?;
+        let url = format!("{deployment}{path}");
+        let api_version = config
+            .providers
+            .azure
+            .api_version
+            .as_deref()
+            .unwrap_or(DEFAULT_API_VERSION);
+
+        tracing::debug!(
+            "Azure OpenAI API Call:\n  URL: {}\n  Query: api-version={}\n  Method: POST\n  Headers:\n    api-key: <redacted>\n    Content-Type: multipart/form-data\n  Body parameters: {}",
+            url,
+            api_version,
+            debug_params.join("\n    ")
+        );
+
+        Ok(client
+            .post(url)
+            .query(&[("api-version", api_version)])
+            .header("api-key", &config.api_key)
+            .multipart(form))
+    }
+
+    fn parse_response(
+        &self,
+        _config: &TranscriptionConfig,
+        body: &str,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let transcription: VerboseJsonResponse = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse Azure OpenAI response: {e}"))?;
+
+        tracing::debug!(
+            "Azure OpenAI API Response:\n  Status: Success\n  Transcription length: {} characters",
+            transcription.text_len()
+        );
+
+        Ok(transcription.into())
+    }
+
+    fn describe_status(&self, status: reqwest::StatusCode, body: &str) -> String {
+        match status.as_u16() {
+            401 => "Azure OpenAI rejected the API key. Please run 'ostt auth' to set the key of your resource.".to_string(),
+            404 => "Azure OpenAI deployment not found. Check endpoint, deployments and api_version under [providers.azure] in ostt.toml.".to_string(),
+            _ => super::default_status_message(PROVIDER.name(), status, body),
+        }
+    }
+}
+
+/// Returns the URL of the deployment serving the configured model, without a trailing slash.
+///
+/// `[providers.azure].deployments` maps the model name to a deployment name on the
+/// resource endpoint, or to the full URL of a deployment on another resource. An
+/// `OSTT_AZURE_BASE_URL` or `[providers.base_urls]` override replaces the resource endpoint.
+///
+/// # Errors
+/// - If the deployment is given by name and no resource endpoint is configured
+fn deployment_url(config: &TranscriptionConfig) -> anyhow::Result<String> {
+    let azure_config = &config.providers.azure;
+    let model_name = config.model.api_model_name();
+    let deployment = azure_config
+        .deployments
+        .get(model_name)
+        .map(|deployment| deployment.trim().trim_end_matches('/'))
+        .filter(|deployment| !deployment.is_empty())
+        .unwrap_or(model_name);
+    if deployment.contains("://") {
+        return Ok(deployment.to_string());
+    }
+
+    let endpoint = super::base_url(config, azure_config.endpoint.as_deref().unwrap_or(""));
+    if endpoint.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Azure OpenAI needs the endpoint of your resource. Set [providers.azure].endpoint in ostt.toml (e.g. \"https://my-resource.openai.azure.com\")."
+        ));
+    }
+    Ok(format!("{endpoint}/openai/deployments/{deployment}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProvidersConfig;
+
+    fn config(model_id: &str, providers: ProvidersConfig) -> TranscriptionConfig {
+        let model = Azure
+            .models()
+            .into_iter()
+            .find(|m| m.id() == model_id)
+            .unwrap();
+        TranscriptionConfig::new(model, "secret".to_string(), Vec::new(), providers)
+    }
+
+    #[test]
+    fn deployments_are_resolved_by_name_or_url() {
+        let mut providers = ProvidersConfig::default();
+        assert!(deployment_url(&config("azure-whisper", providers.clone())).is_err());
+
+        providers.azure.endpoint = Some("https://contoso.openai.azure.com/".to_string());
+        providers
+            .azure
+            .deployments
+            .insert("whisper-1".to_string(), "whisper-prod".to_string());
+        providers.azure.deployments.insert(
+            "gpt-4o-mini-transcribe".to_string(),
+            "https://contoso-eu.openai.azure.com/openai/deployments/mini/".to_string(),
+        );
+        assert_eq!(
+            deployment_url(&config("azure-whisper", providers.clone())).unwrap(),
+            "https://contoso.openai.azure.com/openai/deployments/whisper-prod"
+        );
+        assert_eq!(
+            deployment_url(&config("azure-gpt-4o-mini-transcribe", providers.clone())).unwrap(),
+            "https://contoso-eu.openai.azure.com/openai/deployments/mini"
+        );
+        assert_eq!(
+            deployment_url(&config("azure-gpt-4o-transcribe", providers)).unwrap(),
+            "https://contoso.openai.azure.com/openai/deployments/gpt-4o-transcribe"
+        );
+    }
+
+    #[test]
+    fn requests_carry_api_version_and_api_key() {
+        let mut providers = ProvidersConfig::default();
+        providers.azure.endpoint = Some("https://contoso.openai.azure.com".to_string());
+        providers.azure.api_version = Some("2024-06-01".to_string());
+        let audio = AudioUpload::Bytes {
+            data: b"audio".to_vec(),
+            file_name: "a.mp3".to_string(),
+        };
+
+        let request = Azure
+            .build_request(
+                &reqwest::Client::new(),
+                &config("azure-gpt-4o-transcribe", providers),
+                audio,
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://contoso.openai.azure.com/openai/deployments/gpt-4o-transcribe/audio/transcriptions?response_format=json&api-version=2024-06-01"
+        );
+        assert_eq!(request.headers()["api-key"], "secret");
+        assert!(!request.headers().contains_key("authorization"));
+    }
+}
@@ -2,10 +2,24 @@
 //!
 //! Handles transcription requests to Berget's OpenAI-compatible Whisper API using multipart form data.
 
+use async_trait::async_trait;
 use serde::Deserialize;
-use std::path::Path;
 
-use super::TranscriptionConfig;
+use super::{
+    base_url, AudioUpload, KeywordSupport, ModelCapabilities, Provider, ProviderCapabilities,
+    TranscriptionConfig, TranscriptionResponse,
+};
+use crate::transcription::{TranscriptionModel, TranscriptionProvider};
+
+const PROVIDER: TranscriptionProvider = TranscriptionProvider::new("berget", "Berget");
+
+const BASE_URL: &str = "https://api.berget.ai/v1";
+
+/// Languages of the Swedish fine-tune by KBLab, which also keeps Whisper's English
+const KB_LANGUAGES: &[&str] = &["sv", "en"];
+
+/// Languages of the Norwegian fine-tune by NbAiLab (Bokmål, Nynorsk and English)
+const NB_LANGUAGES: &[&str] = &["no", "nb", "nn", "en"];
 
 /// Berget API response wrapper
 #[derive(Debug, Deserialize)]
@@ -13,109 +27,120 @@ struct BergetResponse {
     text: String,
 }
 
-/// Transcribes an audio file using Berget's Whisper API.
+/// Berget transcription provider.
 ///
 /// Uses multipart form data with bearer token authentication.
 /// Berget provides an OpenAI-compatible API endpoint.
 ///
 /// Keywords are passed as the `hotwords` parameter (Berget's dedicated keyword boosting)
 /// and as the `prompt` parameter (Whisper-compatible context hint).
-pub async fn transcribe(config: &TranscriptionConfig, audio_path: &Path) -> anyhow::Result<String> {
-    let audio_data =
-        std::fs::read(audio_path).map_err(|e| anyhow::anyhow!("Failed to read audio file: {e}"))?;
-
-    let client = reqwest::Client::new();
-
-    let file_name = audio_path
-        .file_name()
-        .unwrap_or_default()
-        .to_string_lossy()
-        .to_string();
-
-    let file_part = reqwest::multipart::Part::bytes(audio_data)
-        .file_name(file_name)
-        .mime_str("audio/mpeg")
-        .map_err(|e| anyhow::anyhow!("Failed to create file part for upload: {e}"))?;
-
-    let mut form = reqwest::multipart::Form::new()
-        .part("file", file_part)
-        .text("model", config.model.api_model_name().to_string());
-
-    // Debug log: Log the API call details (without the audio data)
-    let mut debug_params = vec![format!("model={}", config.model.api_model_name())];
-
-    // Add keywords as hotwords (Berget-specific) and prompt (Whisper-compatible)
-    if !config.keywords.is_empty() {
-        let keywords_csv = config.keywords.join(", ");
-        form = form.text("hotwords", keywords_csv.clone());
-        form = form.text("prompt", keywords_csv.clone());
-        debug_params.push(format!("hotwords={keywords_csv}"));
-        debug_params.push(format!("prompt={keywords_csv}"));
-        tracing::debug!("Keywords used for Berget model: {:?}", config.keywords);
+pub struct Berget;
+
+#[async_trait]
+impl Provider for Berget {
+    fn info(&self) -> TranscriptionProvider {
+        PROVIDER
+    }
+
+    fn models(&self) -> Vec<TranscriptionModel> {
+        vec![
+            TranscriptionModel::new(
+                PROVIDER,
+                "berget-whisper-kb-large",
+                "KBLab/kb-whisper-large",
+                "KB Whisper Large (Swedish optimized)",
+            ),
+            TranscriptionModel::new(
+                PROVIDER,
+                "berget-whisper-nb-large",
+                "NbAiLab/nb-whisper-large",
+                "NB Whisper Large (Norwegian optimized)",
+            ),
+            TranscriptionModel::new(
+                PROVIDER,
+                "berget-whisper-large-v3",
+                "openai/whisper-large-v3",
+                "Whisper Large V3 (general-purpose)",
+            ),
+        ]
     }
 
-    let endpoint = config.model.endpoint();
-
-    tracing::debug!(
-        "Berget API Call:\n  URL: {}\n  Method: POST\n  Headers:\n    Authorization: Bearer <redacted>\n    Content-Type: multipart/form-data\n  Body parameters: {}",
-        endpoint,
-        debug_params.join("\n    ")
-    );
-
-    let response = match client
-        .post(endpoint)
-        .bearer_auth(&config.api_key)
-        .multipart(form)
-        .send()
-        .await
-    {
-        Ok(resp) => resp,
-        Err(e) => {
-            let error_msg = if e.is_connect() {
-                "Failed to connect to Berget API server. Check your internet connection."
-                    .to_string()
-            } else if e.is_timeout() {
-                "Request to Berget timed out. The API server is not responding.".to_string()
-            } else if e.to_string().contains("builder") {
-                format!(
-                    "Failed to build Berget API request: {e}. This may be a configuration error."
-                )
-            } else {
-                format!("Berget network error: {e}")
-            };
-            return Err(anyhow::anyhow!(error_msg));
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            keywords: KeywordSupport::Hotwords,
+            ..Default::default()
         }
-    };
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-
-        let human_readable = match status.as_u16() {
-            401 => "Berget API key is invalid or expired. Please run 'ostt auth' to update your API key.".to_string(),
-            403 => "You don't have permission to use Berget's API. Check your API key and account status.".to_string(),
-            429 => "Too many requests to Berget. You've hit the API rate limit. Please wait and try again.".to_string(),
-            500 | 502 | 503 | 504 => "Berget API server is experiencing issues. Please try again later.".to_string(),
-            _ => format!("Berget API error (status {status}): {error_body}"),
-        };
+    }
 
-        return Err(anyhow::anyhow!(human_readable));
+    /// The KBLab and NbAiLab fine-tunes only transcribe their own languages.
+    fn model_capabilities(&self, model: &TranscriptionModel) -> ModelCapabilities {
+        let languages = match model.api_model_name() {
+            "KBLab/kb-whisper-large" => KB_LANGUAGES,
+            "NbAiLab/nb-whisper-large" => NB_LANGUAGES,
+            _ => &[],
+        };
+        ModelCapabilities {
+            languages,
+            ..ModelCapabilities::from_provider(self.capabilities(), false)
+        }
     }
 
-    let berget_response: BergetResponse = response
-        .json()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to parse Berget response: {e}"))?;
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let mut form = reqwest::multipart::Form::new()
+            .part("file", audio.into_file_part()?)
+            .text("model", config.model.api_model_name().to_string());
+
+        // Debug log: Log the API call details (without the audio data)
+        let mut debug_params = vec![format!("model={}", config.model.api_model_name())];
+
+        if let Some(language) = &config.language {
+            form = form.text("language", language.clone());
+            debug_params.push(format!("language={language}"));
+        }
+
+        // Add keywords as hotwords (Berget-specific) and prompt (Whisper-compatible)
+        if !config.keywords.is_empty() {
+            let keywords_csv = config.keywords.join(", ");
+            form = form.text("hotwords", keywords_csv.clone());
+            form = form.text("prompt", keywords_csv.clone());
+            debug_params.push(format!("hotwords={keywords_csv}"));
+            debug_params.push(format!("prompt={keywords_csv}"));
+            tracing::debug!("Keywords used for Berget model: {:?}", config.keywords);
+        }
 
-    // Debug log: Log the full response for debugging
-    tracing::debug!(
-        "Berget API Response:\n  Status: Success\n  Transcription length: {} characters\n  Full response: {:#?}",
-        berget_response.text.len(),
-        berget_response
-    );
+        let endpoint = format!("{}/audio/transcriptions", base_url(config, BASE_URL));
+        tracing::debug!(
+            "Berget API Call:\n  URL: {}\n  Method: POST\n  Headers:\n    Authorization: Bearer <redacted>\n    Content-Type: multipart/form-data\n  Body parameters: {}",
+            endpoint,
+            debug_params.join("\n    ")
+        );
+
+        Ok(client
+            .post(&endpoint)
+            .bearer_auth(&config.api_key)
+            .multipart(form))
+    }
 
-    Ok(berget_response.text.trim().to_string())
+    fn parse_response(
+        &self,
+        _config: &TranscriptionConfig,
+        body: &str,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let berget_response: BergetResponse = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse Berget response: {e}"))?;
+
+        // Debug log: Log the full response for debugging
+        tracing::debug!(
+            "Berget API Response:\n  Status: Success\n  Transcription length: {} characters\n  Full response: {:#?}",
+            berget_response.text.len(),
+            berget_response
+        );
+
+        Ok(TranscriptionResponse::new(berget_response.text))
+    }
 }
@@ -0,0 +1,216 @@
+//! Custom OpenAI-compatible API implementation.
+//!
+//! Handles transcription requests to self-hosted or third-party servers that implement the
+//! OpenAI `/v1/audio/transcriptions` endpoint (faster-whisper-server, LocalAI, whisper.cpp
+//! server, ...) using multipart form data.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::{
+    AudioUpload, Provider, ProviderCapabilities, TranscriptionConfig, TranscriptionResponse,
+};
+use crate::transcription::{discovery, TranscriptionModel, TranscriptionProvider};
+
+const PROVIDER: TranscriptionProvider = TranscriptionProvider::new("custom", "Custom");
+
+/// Base URL used when `[providers.custom].base_url` is not set
+const DEFAULT_BASE_URL: &str = "http://localhost:8000/v1";
+
+/// Path of the endpoint that lists the models served
+const MODELS_PATH: &str = "/models";
+
+/// OpenAI-compatible API response wrapper
+#[derive(Debug, Deserialize)]
+struct CustomResponse {
+    text: String,
+}
+
+/// Custom OpenAI-compatible transcription provider.
+///
+/// The base URL, model name and extra form fields come from `[providers.custom]`.
+/// Bearer authentication is only sent when an API key has been saved, since many
+/// self-hosted servers run without authentication.
+///
+/// Keywords are passed as the `prompt` parameter, which Whisper-based servers use as context.
+pub struct Custom;
+
+/// Returns the server base URL without a trailing slash.
+///
+/// `[providers.custom].base_url` replaces the default; an `OSTT_CUSTOM_BASE_URL` or
+/// `[providers.base_urls]` override takes precedence over both.
+fn base_url(config: &TranscriptionConfig) -> String {
+    let configured = config
+        .providers
+        .custom
+        .base_url
+        .as_deref()
+        .unwrap_or(DEFAULT_BASE_URL);
+    super::base_url(config, configured)
+}
+
+#[async_trait]
+impl Provider for Custom {
+    fn info(&self) -> TranscriptionProvider {
+        PROVIDER
+    }
+
+    fn models(&self) -> Vec<TranscriptionModel> {
+        vec![TranscriptionModel::new(
+            PROVIDER,
+            "custom",
+            "whisper-1",
+            "OpenAI-compatible server (configured in ostt.toml)",
+        )]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            requires_api_key: false,
+            ..Default::default()
+        }
+    }
+
+    /// The server decides; OpenAI-compatible servers such as faster-whisper-server
+    /// implement `/audio/translations` alongside `/audio/transcriptions`.
+    fn supports_translation(&self, _model: &TranscriptionModel) -> bool {
+        true
+    }
+
+    /// Only servers with a configured base URL are asked, so `ostt auth` does not probe
+    /// localhost for users who never set up a custom server.
+    fn model_list_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+    ) -> Option<reqwest::RequestBuilder> {
+        let configured = config.providers.custom.base_url.as_deref().unwrap_or("");
+        let server = super::base_url(config, configured);
+        if server.is_empty() {
+            return None;
+        }
+        let mut request = client.get(format!("{server}{MODELS_PATH}"));
+        if !config.api_key.is_empty() {
+            request = request.bearer_auth(&config.api_key);
+        }
+        Some(request)
+    }
+
+    /// Servers list only the models they serve, so every listed model is kept.
+    fn parse_model_list(&self, body: &str) -> anyhow::Result<Vec<String>> {
+        discovery::parse_openai_model_list(body)
+    }
+
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let custom_config = &config.providers.custom;
+        // `[providers.custom].model` only overrides the built-in model; user-defined
+        // models carry their own API model name
+        let model_name = match custom_config.model.as_deref() {
+            Some(model) if config.model.id() == "custom" => model,
+            _ => config.model.api_model_name(),
+        };
+
+        let mut form = reqwest::multipart::Form::new()
+            .part("file", audio.into_file_part()?)
+            .text("model", model_name.to_string());
+
+        // Debug log: Log the API call details (without the audio data)
+        let mut debug_params = vec![format!("model={model_name}")];
+
+        if let Some(language) = config.language.as_ref().filter(|_| !config.translate) {
+            form = form.text("language", language.clone());
+            debug_params.push(format!("language={language}"));
+        }
+
+        // Add keywords as prompt for better transcription context
+        if !config.keywords.is_empty() {
+            let prompt = config.keywords.join(", ");
+            form = form.text("prompt", prompt.clone());
+            debug_params.push(format!("prompt={prompt}"));
+            tracing::debug!(
+                "Keywords used as prompt for custom model: {:?}",
+                config.keywords
+            );
+        }
+
+        // Add user-defined form fields (e.g. temperature, vad_filter)
+        for (name, value) in &custom_config.extra_fields {
+            let value = match value {
+                toml::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            debug_params.push(format!("{name}={value}"));
+            form = form.text(name.clone(), value);
+        }
+
+        let endpoint = if config.translate {
+            format!("{}/audio/translations", base_url(config))
+        } else {
+            format!("{}/audio/transcriptions", base_url(config))
+        };
+
+        tracing::debug!(
+            "Custom API Call:\n  URL: {}\n  Method: POST\n  Headers:\n    Authorization: {}\n    Content-Type: multipart/form-data\n  Body parameters: {}",
+            endpoint,
+            if config.api_key.is_empty() { "none" } else { "Bearer <redacted>" },
+            debug_params.join("\n    ")
+        );
+
+        let mut request = client.post(&endpoint).multipart(form);
+        if !config.api_key.is_empty() {
+            request = request.bearer_auth(&config.api_key);
+        }
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        _config: &TranscriptionConfig,
+        body: &str,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let custom_response: CustomResponse = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse custom server response: {e}"))?;
+
+        // Debug log: Log the full response for debugging
+        tracing::debug!(
+            "Custom API Response:\n  Status: Success\n  Transcription length: {} characters\n  Full response: {:#?}",
+            custom_response.text.len(),
+            custom_response
+        );
+
+        Ok(TranscriptionResponse::new(custom_response.text))
+    }
+
+    fn describe_status(&self, status: reqwest::StatusCode, body: &str) -> String {
+        match status.as_u16() {
+            401 | 403 => "Custom transcription server rejected the request. Please run 'ostt auth' to set its API key.".to_string(),
+            404 => "Custom transcription server has no /audio/transcriptions endpoint (or /audio/translations with --translate). Check [providers.custom].base_url in ostt.toml.".to_string(),
+            429 => "Too many requests to custom transcription server. Please wait and try again.".to_string(),
+            500 | 502 | 503 | 504 => "Custom transcription server is experiencing issues. Please try again later.".to_string(),
+            _ => format!("Custom server API error (status {status}): {body}"),
+        }
+    }
+
+    fn describe_send_error(&self, config: &TranscriptionConfig, error: &reqwest::Error) -> String {
+        if error.is_connect() {
+            format!(
+                "Failed to connect to custom transcription server at {}. Check that the server is running.",
+                base_url(config)
+            )
+        } else if error.is_timeout() {
+            "Request to custom transcription server timed out. The server is not responding."
+                .to_string()
+        } else if error.is_builder() {
+            format!(
+                "Failed to build custom API request: {error}. Check [providers.custom].base_url in ostt.toml."
+            )
+        } else {
+            format!("Custom server network error: {error}")
+        }
+    }
+}
@@ -1,29 +1,69 @@
 //! Deepgram API implementation.
 //!
-//! Handles transcription requests to Deepgram's API using binary audio data.
+//! Handles transcription requests to Deepgram's API using binary audio data, and live
+//! transcription over the streaming `/v1/listen` WebSocket endpoint.
 
+use async_trait::async_trait;
 use serde::Deserialize;
-use std::path::Path;
 use urlencoding;
 
-use super::super::model::TranscriptionModel;
-use super::TranscriptionConfig;
+use super::{
+    base_url, AudioUpload, KeywordSupport, ModelCapabilities, Provider, ProviderCapabilities,
+    TranscriptSegment, TranscriptWord, TranscriptionConfig, TranscriptionResponse,
+};
+use crate::transcription::streaming::{LiveRequest, LiveUpdate};
+use crate::transcription::{TranscriptionModel, TranscriptionProvider};
 
-/// Deepgram response structure (kept for potential future use)
-#[allow(dead_code)]
-#[derive(Debug, Deserialize)]
-struct DeepgramResult {
-    transcript: String,
-}
+const PROVIDER: TranscriptionProvider = TranscriptionProvider::new("deepgram", "Deepgram");
+
+const BASE_URL: &str = "https://api.deepgram.com/v1";
+
+const LIVE_ENDPOINT: &str = "wss://api.deepgram.com/v1/listen";
 
 #[derive(Debug, Deserialize)]
 struct DeepgramChannel {
     alternatives: Vec<DeepgramAlternative>,
+    /// Present when `detect_language` is enabled
+    detected_language: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct DeepgramAlternative {
     transcript: String,
+    #[serde(default)]
+    words: Vec<DeepgramWord>,
+    /// Present when `paragraphs` or `smart_format` is enabled
+    paragraphs: Option<DeepgramParagraphs>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramWord {
+    word: String,
+    /// Present when punctuation or smart formatting is enabled
+    punctuated_word: Option<String>,
+    start: f64,
+    end: f64,
+    confidence: Option<f64>,
+    /// Zero-based speaker index, present when `diarize` is enabled
+    speaker: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramParagraphs {
+    paragraphs: Vec<DeepgramParagraph>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramParagraph {
+    sentences: Vec<DeepgramSentence>,
+    speaker: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramSentence {
+    text: String,
+    start: f64,
+    end: f64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,133 +74,388 @@ struct DeepgramResponse {
 #[derive(Debug, Deserialize)]
 struct DeepgramResults {
     channels: Vec<DeepgramChannel>,
+    /// Present when `utterances` is enabled
+    utterances: Option<Vec<DeepgramUtterance>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramUtterance {
+    transcript: String,
+    start: f64,
+    end: f64,
+    confidence: Option<f64>,
+    speaker: Option<u32>,
+}
+
+/// Message received on a live (streaming) connection
+#[derive(Debug, Deserialize)]
+struct DeepgramLiveMessage {
+    #[serde(rename = "type")]
+    message_type: String,
+    #[serde(default)]
+    is_final: bool,
+    channel: Option<DeepgramChannel>,
 }
 
-/// Transcribes an audio file using Deepgram's API.
+/// Deepgram transcription provider.
 ///
 /// Sends raw binary audio data with Token authentication and model specified in query parameters.
-pub async fn transcribe(config: &TranscriptionConfig, audio_path: &Path) -> anyhow::Result<String> {
-    let audio_data =
-        std::fs::read(audio_path).map_err(|e| anyhow::anyhow!("Failed to read audio file: {e}"))?;
+pub struct Deepgram;
 
-    let client = reqwest::Client::new();
+#[async_trait]
+impl Provider for Deepgram {
+    fn info(&self) -> TranscriptionProvider {
+        PROVIDER
+    }
 
-    // Build the API URL with query parameters
-    let mut url = format!(
-        "{}?model={}",
-        config.model.endpoint(),
-        config.model.api_model_name()
-    );
+    fn models(&self) -> Vec<TranscriptionModel> {
+        vec![
+            TranscriptionModel::new(PROVIDER, "nova-3", "nova-3", "Nova 3 (latest, fastest)"),
+            TranscriptionModel::new(PROVIDER, "nova-2", "nova-2", "Nova 2 (previous generation)"),
+        ]
+    }
 
-    // Add Deepgram feature flags from provider configuration
-    let deepgram_config = &config.providers.deepgram;
-    if deepgram_config.filler_words {
-        url.push_str("&filler_words=true");
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            keywords: KeywordSupport::Keyterms,
+            streaming: true,
+            diarization: true,
+            timestamps: true,
+            ..Default::default()
+        }
     }
-    if deepgram_config.measurements {
-        url.push_str("&measurements=true");
+
+    /// Models before Nova 3 boost a keyword list instead of key terms.
+    fn model_capabilities(&self, model: &TranscriptionModel) -> ModelCapabilities {
+        let keywords = if uses_keyterms(model) {
+            KeywordSupport::Keyterms
+        } else {
+            KeywordSupport::Hotwords
+        };
+        ModelCapabilities {
+            keywords,
+            ..ModelCapabilities::from_provider(self.capabilities(), false)
+        }
     }
-    if deepgram_config.numerals {
-        url.push_str("&numerals=true");
+
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let (audio_data, _) = audio.into_bytes()?;
+
+        // Build the API URL with query parameters
+        let mut url = format!(
+            "{}/listen?model={}",
+            base_url(config, BASE_URL),
+            config.model.api_model_name()
+        );
+
+        // Add Deepgram feature flags from provider configuration
+        let deepgram_config = &config.providers.deepgram;
+        if deepgram_config.filler_words {
+            url.push_str("&filler_words=true");
+        }
+        if deepgram_config.measurements {
+            url.push_str("&measurements=true");
+        }
+        if deepgram_config.numerals {
+            url.push_str("&numerals=true");
+        }
+        if deepgram_config.paragraphs {
+            url.push_str("&paragraphs=true");
+        }
+        if deepgram_config.profanity_filter {
+            url.push_str("&profanity_filter=true");
+        }
+        if deepgram_config.punctuate {
+            url.push_str("&punctuate=true");
+        }
+        if deepgram_config.smart_format {
+            url.push_str("&smart_format=true");
+        }
+        if deepgram_config.utterances {
+            url.push_str("&utterances=true");
+        }
+        if deepgram_config.utt_split != 0.8 {
+            url.push_str(&format!("&utt_split={}", deepgram_config.utt_split));
+        }
+        if let Some(language) = &config.language {
+            url.push_str(&format!("&language={}", urlencoding::encode(language)));
+        } else if !deepgram_config.detect_language_codes.is_empty() {
+            for lang in &deepgram_config.detect_language_codes {
+                url.push_str(&format!("&detect_language={}", urlencoding::encode(lang)));
+            }
+        } else if deepgram_config.detect_language {
+            url.push_str("&detect_language=true");
+        }
+        if deepgram_config.mip_opt_out {
+            url.push_str("&mip_opt_out=true");
+        }
+        if config.diarize {
+            url.push_str("&diarize=true");
+        }
+
+        push_keyword_params(&mut url, config);
+
+        Ok(client
+            .post(&url)
+            .header("Authorization", format!("Token {}", config.api_key))
+            .header("Content-Type", "audio/mpeg")
+            .body(audio_data))
     }
-    if deepgram_config.paragraphs {
-        url.push_str("&paragraphs=true");
+
+    fn parse_response(
+        &self,
+        _config: &TranscriptionConfig,
+        body: &str,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let deepgram_response: DeepgramResponse = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse Deepgram response: {e}"))?;
+
+        // Extract transcript from the nested response structure
+        let results = deepgram_response.results;
+        let channel = results
+            .channels
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("No transcript found in Deepgram response"))?;
+        let language = channel.detected_language;
+        let alternative = channel
+            .alternatives
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("No transcript found in Deepgram response"))?;
+
+        // Prefer utterances, fall back to the sentences of smart-formatted paragraphs
+        let segments = match results.utterances {
+            Some(utterances) => utterances
+                .into_iter()
+                .map(|utterance| TranscriptSegment {
+                    text: utterance.transcript,
+                    start: utterance.start,
+                    end: utterance.end,
+                    confidence: utterance.confidence,
+                    speaker: utterance.speaker.map(speaker_number),
+                })
+                .collect(),
+            None => alternative
+                .paragraphs
+                .into_iter()
+                .flat_map(|p| p.paragraphs)
+                .flat_map(|paragraph| {
+                    let speaker = paragraph.speaker.map(speaker_number);
+                    paragraph
+                        .sentences
+                        .into_iter()
+                        .map(move |sentence| TranscriptSegment {
+                            text: sentence.text,
+                            start: sentence.start,
+                            end: sentence.end,
+                            confidence: None,
+                            speaker,
+                        })
+                })
+                .collect(),
+        };
+
+        let words = alternative
+            .words
+            .into_iter()
+            .map(|word| TranscriptWord {
+                text: word.punctuated_word.unwrap_or(word.word),
+                start: word.start,
+                end: word.end,
+                confidence: word.confidence,
+                speaker: word.speaker.map(speaker_number),
+            })
+            .collect();
+
+        Ok(TranscriptionResponse {
+            text: alternative.transcript,
+            segments,
+            words,
+            language,
+        })
     }
-    if deepgram_config.profanity_filter {
-        url.push_str("&profanity_filter=true");
+
+    fn live_request(&self, config: &TranscriptionConfig, sample_rate: u32) -> Option<LiveRequest> {
+        let mut url = format!(
+            "{}?model={}&encoding=linear16&sample_rate={}&channels=1&interim_results=true",
+            LIVE_ENDPOINT,
+            config.model.api_model_name(),
+            sample_rate
+        );
+
+        // Only the flags supported by the streaming API; paragraphs, utterances and
+        // language detection are batch-only
+        let deepgram_config = &config.providers.deepgram;
+        if let Some(language) = &config.language {
+            url.push_str(&format!("&language={}", urlencoding::encode(language)));
+        }
+        if deepgram_config.filler_words {
+            url.push_str("&filler_words=true");
+        }
+        if deepgram_config.numerals {
+            url.push_str("&numerals=true");
+        }
+        if deepgram_config.profanity_filter {
+            url.push_str("&profanity_filter=true");
+        }
+        if deepgram_config.punctuate {
+            url.push_str("&punctuate=true");
+        }
+        if deepgram_config.smart_format {
+            url.push_str("&smart_format=true");
+        }
+        if deepgram_config.mip_opt_out {
+            url.push_str("&mip_opt_out=true");
+        }
+
+        push_keyword_params(&mut url, config);
+
+        Some(LiveRequest {
+            url,
+            headers: vec![("Authorization", format!("Token {}", config.api_key))],
+        })
+    }
+
+    fn parse_live_message(&self, message: &str) -> anyhow::Result<Option<LiveUpdate>> {
+        let live_message: DeepgramLiveMessage = serde_json::from_str(message)
+            .map_err(|e| anyhow::anyhow!("Failed to parse Deepgram live message: {e}"))?;
+
+        if live_message.message_type != "Results" {
+            tracing::debug!("Deepgram live event: {}", live_message.message_type);
+            return Ok(None);
+        }
+
+        let transcript = live_message
+            .channel
+            .and_then(|channel| channel.alternatives.into_iter().next())
+            .map(|alt| alt.transcript)
+            .unwrap_or_default();
+
+        Ok(Some(if live_message.is_final {
+            LiveUpdate::Final(transcript)
+        } else {
+            LiveUpdate::Interim(transcript)
+        }))
     }
-    if deepgram_config.punctuate {
-        url.push_str("&punctuate=true");
+
+    fn live_close_message(&self) -> Option<&'static str> {
+        Some(r#"{"type":"CloseStream"}"#)
     }
-    if deepgram_config.smart_format {
-        url.push_str("&smart_format=true");
+
+    fn live_keepalive_message(&self) -> Option<&'static str> {
+        Some(r#"{"type":"KeepAlive"}"#)
     }
-    if deepgram_config.utterances {
-        url.push_str("&utterances=true");
+}
+
+/// Converts Deepgram's zero-based speaker index to a speaker number
+fn speaker_number(index: u32) -> u32 {
+    index + 1
+}
+
+/// Returns true if the model takes `keyterm` parameters (Nova 3) rather than `keywords`
+fn uses_keyterms(model: &TranscriptionModel) -> bool {
+    model.api_model_name().starts_with("nova-3")
+}
+
+/// Adds keywords/keyterms if any (nova-3 uses keyterms, older models use keywords)
+fn push_keyword_params(url: &mut String, config: &TranscriptionConfig) {
+    if config.keywords.is_empty() {
+        return;
     }
-    if deepgram_config.utt_split != 0.8 {
-        url.push_str(&format!("&utt_split={}", deepgram_config.utt_split));
+    let param_name = if uses_keyterms(&config.model) {
+        "keyterm"
+    } else {
+        "keywords"
+    };
+    for keyword in &config.keywords {
+        url.push_str(&format!("&{}={}", param_name, urlencoding::encode(keyword)));
     }
-    if !deepgram_config.detect_language_codes.is_empty() {
-        for lang in &deepgram_config.detect_language_codes {
-            url.push_str(&format!("&detect_language={}", urlencoding::encode(lang)));
-        }
-    } else if deepgram_config.detect_language {
-        url.push_str("&detect_language=true");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProvidersConfig;
+
+    fn config() -> TranscriptionConfig {
+        TranscriptionConfig::new(
+            Deepgram.models().remove(0),
+            String::new(),
+            Vec::new(),
+            ProvidersConfig::default(),
+        )
     }
-    if deepgram_config.mip_opt_out {
-        url.push_str("&mip_opt_out=true");
+
+    #[test]
+    fn batch_response_includes_words_and_sentences() {
+        let body = r#"{"metadata":{},"results":{"channels":[{"alternatives":[{
+            "transcript":"hello world","confidence":0.99,
+            "words":[{"word":"hello","start":0.08,"end":0.4,"confidence":0.99,"punctuated_word":"Hello"},
+                     {"word":"world","start":0.48,"end":0.9,"confidence":0.95,"punctuated_word":"world."}],
+            "paragraphs":{"transcript":"Hello world.","paragraphs":[{"sentences":[
+                {"text":"Hello world.","start":0.08,"end":0.9}],"start":0.08,"end":0.9}]}
+        }]}]}}"#;
+
+        let response = Deepgram.parse_response(&config(), body).unwrap();
+        assert_eq!(response.text, "hello world");
+        assert_eq!(response.words.len(), 2);
+        assert_eq!(response.words[1].text, "world.");
+        assert_eq!(response.words[1].confidence, Some(0.95));
+        assert_eq!(response.segments.len(), 1);
+        assert_eq!(response.segments[0].text, "Hello world.");
+        assert_eq!(response.segments[0].end, 0.9);
     }
 
-    // Add keywords/keyterms if any (nova-3 uses keyterms, nova-2 uses keywords)
-    if !config.keywords.is_empty() {
-        let param_name = match config.model {
-            TranscriptionModel::DeepgramNova3 => "keyterm",
-            TranscriptionModel::DeepgramNova2 => "keywords",
-            _ => "keywords", // fallback
-        };
-        for keyword in &config.keywords {
-            url.push_str(&format!("&{}={}", param_name, urlencoding::encode(keyword)));
-        }
-    }
-
-    let response = match client
-        .post(&url)
-        .header("Authorization", format!("Token {}", config.api_key))
-        .header("Content-Type", "audio/mpeg")
-        .body(audio_data)
-        .send()
-        .await
-    {
-        Ok(resp) => resp,
-        Err(e) => {
-            let error_msg = if e.is_connect() {
-                "Failed to connect to Deepgram API server. Check your internet connection."
-                    .to_string()
-            } else if e.is_timeout() {
-                "Request to Deepgram timed out. The API server is not responding.".to_string()
-            } else if e.to_string().contains("builder") {
-                format!(
-                    "Failed to build Deepgram API request: {e}. This may be a configuration error."
-                )
-            } else {
-                format!("Deepgram network error: {e}")
-            };
-            return Err(anyhow::anyhow!(error_msg));
-        }
-    };
+    #[test]
+    fn diarized_words_are_numbered_from_one() {
+        let body = r#"{"results":{"channels":[{"alternatives":[{"transcript":"hi hello",
+            "words":[{"word":"hi","start":0.0,"end":0.3,"confidence":0.9,"speaker":0},
+                     {"word":"hello","start":0.5,"end":0.9,"confidence":0.9,"speaker":1}]}]}]}}"#;
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-
-        let human_readable = match status.as_u16() {
-            401 => "Deepgram API key is invalid or expired. Please run 'ostt auth' to update your API key.".to_string(),
-            403 => "You don't have permission to use Deepgram's API. Check your API key and account status.".to_string(),
-            429 => "Too many requests to Deepgram. You've hit the API rate limit. Please wait and try again.".to_string(),
-            500 | 502 | 503 | 504 => "Deepgram API server is experiencing issues. Please try again later.".to_string(),
-            _ => format!("Deepgram API error (status {status}): {error_body}"),
-        };
+        let response = Deepgram.parse_response(&config(), body).unwrap();
+        let speakers: Vec<_> = response.words.iter().map(|w| w.speaker).collect();
+        assert_eq!(speakers, [Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn batch_response_prefers_utterances() {
+        let body = r#"{"results":{"channels":[{"alternatives":[{"transcript":"hi there"}]}],
+            "utterances":[{"start":0.0,"end":0.5,"confidence":0.9,"transcript":"hi"},
+                          {"start":1.0,"end":1.5,"confidence":0.8,"transcript":"there"}]}}"#;
 
-        return Err(anyhow::anyhow!(human_readable));
+        let response = Deepgram.parse_response(&config(), body).unwrap();
+        assert!(response.words.is_empty());
+        assert_eq!(response.segments.len(), 2);
+        assert_eq!(response.segments[1].start, 1.0);
+        assert_eq!(response.segments[1].confidence, Some(0.8));
     }
 
-    let deepgram_response: DeepgramResponse = response
-        .json()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to parse Deepgram response: {e}"))?;
+    #[test]
+    fn live_results_map_to_interim_and_final_updates() {
+        let interim = r#"{"type":"Results","is_final":false,"speech_final":false,
+            "channel":{"alternatives":[{"transcript":"hello wor","confidence":0.9}]}}"#;
+        let last = r#"{"type":"Results","is_final":true,"speech_final":true,
+            "channel":{"alternatives":[{"transcript":"hello world","confidence":0.98}]}}"#;
 
-    // Extract transcript from the nested response structure
-    let transcript = deepgram_response
-        .results
-        .channels
-        .first()
-        .and_then(|channel| channel.alternatives.first())
-        .map(|alt| alt.transcript.clone())
-        .ok_or_else(|| anyhow::anyhow!("No transcript found in Deepgram response"))?;
+        assert_eq!(
+            Deepgram.parse_live_message(interim).unwrap(),
+            Some(LiveUpdate::Interim("hello wor".to_string()))
+        );
+        assert_eq!(
+            Deepgram.parse_live_message(last).unwrap(),
+            Some(LiveUpdate::Final("hello world".to_string()))
+        );
+    }
 
-    Ok(transcript.trim().to_string())
+    #[test]
+    fn live_metadata_is_ignored() {
+        let metadata = r#"{"type":"Metadata","request_id":"abc","duration":1.5}"#;
+        assert_eq!(Deepgram.parse_live_message(metadata).unwrap(), None);
+    }
 }
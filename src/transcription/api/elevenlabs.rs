@@ -3,112 +3,210 @@
 //! Handles transcription requests to ElevenLabs' speech-to-text API using
 //! multipart form data. Supports the Scribe v2 and Scribe v1 models.
 
+use async_trait::async_trait;
 use serde::Deserialize;
-use std::path::Path;
 
-use super::TranscriptionConfig;
+use super::{
+    base_url, AudioUpload, KeywordSupport, Provider, ProviderCapabilities, TranscriptWord,
+    TranscriptionConfig, TranscriptionResponse,
+};
+use crate::transcription::diarization::SpeakerNumbers;
+use crate::transcription::{TranscriptionModel, TranscriptionProvider};
+
+const PROVIDER: TranscriptionProvider = TranscriptionProvider::new("elevenlabs", "ElevenLabs");
+
+const BASE_URL: &str = "https://api.elevenlabs.io/v1";
 
 /// ElevenLabs speech-to-text response structure
 #[derive(Debug, Deserialize)]
 struct ElevenLabsResponse {
     /// The transcribed text
     text: String,
+    /// Words, spacing and audio events with their timings
+    #[serde(default)]
+    words: Vec<ElevenLabsWord>,
+    /// Detected or requested language (ISO-639-3)
+    language_code: Option<String>,
+}
+
+/// A timed token of the ElevenLabs transcript
+#[derive(Debug, Deserialize)]
+struct ElevenLabsWord {
+    text: String,
+    /// `word`, `spacing` or `audio_event`
+    #[serde(rename = "type")]
+    word_type: String,
+    start: Option<f64>,
+    end: Option<f64>,
+    /// Log probability of the word
+    logprob: Option<f64>,
+    /// Speaker label (`speaker_0`, ...) when diarization is enabled
+    speaker_id: Option<String>,
 }
 
-/// Transcribes an audio file using ElevenLabs' Scribe API.
+/// ElevenLabs Scribe transcription provider.
 ///
 /// Sends multipart form data with `xi-api-key` header authentication.
 /// Keywords are passed as `keyterms` to improve transcription accuracy for
 /// domain-specific terms.
-pub async fn transcribe(config: &TranscriptionConfig, audio_path: &Path) -> anyhow::Result<String> {
-    let audio_data =
-        std::fs::read(audio_path).map_err(|e| anyhow::anyhow!("Failed to read audio file: {e}"))?;
-
-    let file_name = audio_path
-        .file_name()
-        .unwrap_or_default()
-        .to_string_lossy()
-        .to_string();
-
-    let file_part = reqwest::multipart::Part::bytes(audio_data)
-        .file_name(file_name)
-        .mime_str("audio/mpeg")
-        .map_err(|e| anyhow::anyhow!("Failed to create file part for upload: {e}"))?;
-
-    let mut form = reqwest::multipart::Form::new()
-        .text("model_id", config.model.api_model_name().to_string())
-        .part("file", file_part);
-
-    // Add optional language code from provider config
-    let elevenlabs_config = &config.providers.elevenlabs;
-    if let Some(ref lang) = elevenlabs_config.language_code {
-        if !lang.is_empty() {
-            form = form.text("language_code", lang.clone());
-        }
+pub struct ElevenLabs;
+
+#[async_trait]
+impl Provider for ElevenLabs {
+    fn info(&self) -> TranscriptionProvider {
+        PROVIDER
     }
 
-    // Add keyterms (ElevenLabs supports up to 1000 keyterms for boosting accuracy)
-    // Each keyterm is passed as a separate form field
-    for keyword in &config.keywords {
-        form = form.text("keyterms", keyword.clone());
+    fn models(&self) -> Vec<TranscriptionModel> {
+        vec![
+            TranscriptionModel::new(
+                PROVIDER,
+                "elevenlabs-scribe-v2",
+                "scribe_v2",
+                "Scribe v2 (highest accuracy, 99 languages)",
+            ),
+            TranscriptionModel::new(
+                PROVIDER,
+                "elevenlabs-scribe-v1",
+                "scribe_v1",
+                "Scribe v1 (previous generation)",
+            ),
+        ]
     }
 
-    let client = reqwest::Client::new();
-    let url = config.model.endpoint();
-
-    tracing::debug!(
-        "ElevenLabs API Call:\n  URL: {}\n  Method: POST\n  Model: {}\n  Keyterms: {:?}",
-        url,
-        config.model.api_model_name(),
-        config.keywords,
-    );
-
-    let response = match client
-        .post(url)
-        .header("xi-api-key", &config.api_key)
-        .multipart(form)
-        .send()
-        .await
-    {
-        Ok(resp) => resp,
-        Err(e) => {
-            let error_msg = if e.is_connect() {
-                "Failed to connect to ElevenLabs API server. Check your internet connection."
-                    .to_string()
-            } else if e.is_timeout() {
-                "Request to ElevenLabs timed out. The API server is not responding.".to_string()
-            } else if e.to_string().contains("builder") {
-                format!("Failed to build ElevenLabs API request: {e}. This may be a configuration error.")
-            } else {
-                format!("ElevenLabs network error: {e}")
-            };
-            return Err(anyhow::anyhow!(error_msg));
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            keywords: KeywordSupport::Keyterms,
+            diarization: true,
+            timestamps: true,
+            ..Default::default()
         }
-    };
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-
-        let human_readable = match status.as_u16() {
-            401 => "ElevenLabs API key is invalid or expired. Please run 'ostt auth' to update your API key.".to_string(),
-            403 => "You don't have permission to use ElevenLabs' API. Check your API key and account status.".to_string(),
-            422 => format!("ElevenLabs API validation error: {error_body}"),
-            429 => "Too many requests to ElevenLabs. You've hit the API rate limit. Please wait and try again.".to_string(),
-            500 | 502 | 503 | 504 => "ElevenLabs API server is experiencing issues. Please try again later.".to_string(),
-            _ => format!("ElevenLabs API error (status {status}): {error_body}"),
-        };
-
-        return Err(anyhow::anyhow!(human_readable));
     }
 
-    let elevenlabs_response: ElevenLabsResponse = response
-        .json()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to parse ElevenLabs response: {e}"))?;
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let mut form = reqwest::multipart::Form::new()
+            .text("model_id", config.model.api_model_name().to_string())
+            .part("file", audio.into_file_part()?);
+
+        // Add optional language code, preferring the requested language over the
+        // provider config
+        let elevenlabs_config = &config.providers.elevenlabs;
+        let language = config
+            .language
+            .as_ref()
+            .or(elevenlabs_config.language_code.as_ref());
+        if let Some(lang) = language {
+            if !lang.is_empty() {
+                form = form.text("language_code", lang.clone());
+            }
+        }
+
+        if config.diarize {
+            form = form.text("diarize", "true");
+        }
 
-    Ok(elevenlabs_response.text.trim().to_string())
+        // Add keyterms (ElevenLabs supports up to 1000 keyterms for boosting accuracy)
+        // Each keyterm is passed as a separate form field
+        for keyword in &config.keywords {
+            form = form.text("keyterms", keyword.clone());
+        }
+
+        let endpoint = format!("{}/speech-to-text", base_url(config, BASE_URL));
+        tracing::debug!(
+            "ElevenLabs API Call:\n  URL: {}\n  Method: POST\n  Model: {}\n  Keyterms: {:?}",
+            endpoint,
+            config.model.api_model_name(),
+            config.keywords,
+        );
+
+        Ok(client
+            .post(&endpoint)
+            .header("xi-api-key", &config.api_key)
+            .multipart(form))
+    }
+
+    fn parse_response(
+        &self,
+        _config: &TranscriptionConfig,
+        body: &str,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let elevenlabs_response: ElevenLabsResponse = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse ElevenLabs response: {e}"))?;
+
+        // Keep only spoken words; spacing and audio events (e.g. laughter) carry no text
+        let mut speakers = SpeakerNumbers::default();
+        let words = elevenlabs_response
+            .words
+            .into_iter()
+            .filter(|word| word.word_type == "word")
+            .filter_map(|word| {
+                Some(TranscriptWord {
+                    text: word.text,
+                    start: word.start?,
+                    end: word.end?,
+                    confidence: word.logprob.map(f64::exp),
+                    speaker: word.speaker_id.map(|label| speakers.number(&label)),
+                })
+            })
+            .collect();
+
+        Ok(TranscriptionResponse {
+            text: elevenlabs_response.text,
+            segments: Vec::new(),
+            words,
+            language: elevenlabs_response.language_code,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProvidersConfig;
+
+    #[test]
+    fn response_keeps_timed_words_only() {
+        let config = TranscriptionConfig::new(
+            ElevenLabs.models().remove(0),
+            String::new(),
+            Vec::new(),
+            ProvidersConfig::default(),
+        );
+        let body = r#"{"language_code":"en","language_probability":0.98,"text":"Hello world!",
+            "words":[{"text":"Hello","type":"word","start":0.1,"end":0.5,"logprob":0.0},
+                     {"text":" ","type":"spacing","start":0.5,"end":0.6},
+                     {"text":"world!","type":"word","start":0.6,"end":1.0,"logprob":0.0},
+                     {"text":"(laughs)","type":"audio_event","start":1.0,"end":1.8}]}"#;
+
+        let response = ElevenLabs.parse_response(&config, body).unwrap();
+        assert_eq!(response.text, "Hello world!");
+        assert_eq!(response.language.as_deref(), Some("en"));
+        let words: Vec<&str> = response.words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(words, ["Hello", "world!"]);
+        assert_eq!(response.words[1].start, 0.6);
+        assert_eq!(response.words[1].confidence, Some(1.0));
+        assert_eq!(response.words[1].speaker, None);
+    }
+
+    #[test]
+    fn diarized_words_carry_speaker_numbers() {
+        let config = TranscriptionConfig::new(
+            ElevenLabs.models().remove(0),
+            String::new(),
+            Vec::new(),
+            ProvidersConfig::default(),
+        );
+        let body = r#"{"text":"Hi hello",
+            "words":[{"text":"Hi","type":"word","start":0.0,"end":0.3,"speaker_id":"speaker_1"},
+                     {"text":"hello","type":"word","start":0.5,"end":0.9,"speaker_id":"speaker_0"}]}"#;
+
+        let response = ElevenLabs.parse_response(&config, body).unwrap();
+        let speakers: Vec<_> = response.words.iter().map(|w| w.speaker).collect();
+        assert_eq!(speakers, [Some(1), Some(2)]);
+    }
 }
@@ -0,0 +1,430 @@
+//! Gladia API implementation.
+//!
+//! Handles transcription requests to Gladia's pre-recorded API using an
+//! upload→submit→poll pattern:
+//! 1. Upload the audio file to get an audio URL
+//! 2. Submit a transcription request with the audio URL and options
+//! 3. Poll the result endpoint until the transcription is done
+//!
+//! Gladia can follow speakers who switch languages mid-sentence (code-switching), which is
+//! enabled with `[providers.gladia].code_switching`.
+
+use std::path::Path;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    base_url, AudioUpload, KeywordSupport, Provider, ProviderCapabilities, TranscriptSegment,
+    TranscriptWord, TranscriptionConfig, TranscriptionResponse,
+};
+use crate::transcription::diarization::SpeakerNumbers;
+use crate::transcription::error::TranscriptionError;
+use crate::transcription::{TranscriptionModel, TranscriptionProvider};
+
+const PROVIDER: TranscriptionProvider = TranscriptionProvider::new("gladia", "Gladia");
+
+const BASE_URL: &str = "https://api.gladia.io/v2";
+
+/// Maximum number of poll attempts before timing out (5 minutes at 2-second intervals)
+const MAX_POLL_ATTEMPTS: u32 = 150;
+
+/// Polling interval in seconds
+const POLL_INTERVAL_SECS: u64 = 2;
+
+/// Response from the upload endpoint
+#[derive(Debug, Deserialize)]
+struct UploadResponse {
+    audio_url: String,
+}
+
+/// Request body for the pre-recorded endpoint
+#[derive(Debug, Serialize)]
+struct TranscriptRequest {
+    audio_url: String,
+    model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language_config: Option<LanguageConfig>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    custom_vocabulary: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    custom_vocabulary_config: Option<CustomVocabularyConfig>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    diarization: bool,
+}
+
+/// Expected languages and whether the language may change within the audio
+#[derive(Debug, Serialize)]
+struct LanguageConfig {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    languages: Vec<String>,
+    code_switching: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct CustomVocabularyConfig {
+    vocabulary: Vec<String>,
+}
+
+/// Response from the pre-recorded endpoint (both submit and poll)
+#[derive(Debug, Deserialize)]
+struct TranscriptResponse {
+    id: String,
+    /// `queued`, `processing`, `done` or `error` (absent in the submit response)
+    #[serde(default)]
+    status: Option<String>,
+    error_code: Option<u16>,
+    result: Option<TranscriptResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptResult {
+    transcription: Option<Transcription>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Transcription {
+    full_transcript: String,
+    /// Languages spoken in the audio, detected or requested
+    #[serde(default)]
+    languages: Vec<String>,
+    #[serde(default)]
+    utterances: Vec<Utterance>,
+}
+
+/// A stretch of speech by one speaker in one language
+#[derive(Debug, Deserialize)]
+struct Utterance {
+    text: String,
+    start: f64,
+    end: f64,
+    confidence: Option<f64>,
+    /// Speaker number starting at 0 when diarization is enabled
+    speaker: Option<u32>,
+    #[serde(default)]
+    words: Vec<Word>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Word {
+    word: String,
+    start: f64,
+    end: f64,
+    confidence: Option<f64>,
+}
+
+/// Gladia transcription provider.
+///
+/// Uploads audio, submits a pre-recorded transcription and polls its result at 2-second
+/// intervals for up to 5 minutes. Authenticates with the `x-gladia-key` header.
+///
+/// The API model name is sent as `model`, so user-defined models select other Gladia
+/// models. Keywords are passed as the custom vocabulary. Expected languages and code-switching
+/// come from `[providers.gladia]`; a requested language replaces both.
+pub struct Gladia;
+
+#[async_trait]
+impl Provider for Gladia {
+    fn info(&self) -> TranscriptionProvider {
+        PROVIDER
+    }
+
+    fn models(&self) -> Vec<TranscriptionModel> {
+        vec![TranscriptionModel::new(
+            PROVIDER,
+            "gladia-solaria",
+            "solaria-1",
+            "Solaria (100+ languages, code-switching)",
+        )]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            keywords: KeywordSupport::Keyterms,
+            diarization: true,
+            timestamps: true,
+            ..Default::default()
+        }
+    }
+
+    /// Uploads the audio to Gladia and returns its audio URL.
+    async fn upload(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio_path: &Path,
+    ) -> anyhow::Result<AudioUpload> {
+        let form = reqwest::multipart::Form::new().part(
+            "audio",
+            AudioUpload::from_path(audio_path)?.into_file_part()?,
+        );
+        let request = client
+            .post(format!("{}/upload", base_url(config, BASE_URL)))
+            .header("x-gladia-key", &config.api_key)
+            .multipart(form);
+        let body = self.send(config, request).await?;
+
+        let upload: UploadResponse = serde_json::from_str(&body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse Gladia upload response: {e}"))?;
+        tracing::debug!("Audio uploaded successfully");
+        Ok(AudioUpload::Url(upload.audio_url))
+    }
+
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let AudioUpload::Url(audio_url) = audio else {
+            return Err(anyhow::anyhow!(
+                "Gladia requires the audio to be uploaded before transcription"
+            ));
+        };
+
+        let request = transcript_request(config, audio_url);
+        tracing::debug!(
+            "Gladia API Call:\n  URL: {}/pre-recorded\n  Method: POST\n  Body: {}",
+            base_url(config, BASE_URL),
+            serde_json::to_string(&request).unwrap_or_default()
+        );
+
+        Ok(client
+            .post(format!("{}/pre-recorded", base_url(config, BASE_URL)))
+            .header("x-gladia-key", &config.api_key)
+            .json(&request))
+    }
+
+    fn parse_response(
+        &self,
+        _config: &TranscriptionConfig,
+        body: &str,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let result: TranscriptResponse = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse Gladia result: {e}"))?;
+
+        match result.status.as_deref() {
+            Some("done") => {
+                let transcription = result
+                    .result
+                    .and_then(|result| result.transcription)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("Gladia returned done status but no transcription")
+                    })?;
+                tracing::debug!(
+                    "Transcription completed: {} chars",
+                    transcription.full_transcript.trim().len()
+                );
+
+                let mut speakers = SpeakerNumbers::default();
+                let mut segments = Vec::new();
+                let mut words = Vec::new();
+                for utterance in transcription.utterances {
+                    let speaker = utterance
+                        .speaker
+                        .map(|label| speakers.number(&label.to_string()));
+                    words.extend(utterance.words.into_iter().map(|word| TranscriptWord {
+                        text: word.word.trim().to_string(),
+                        start: word.start,
+                        end: word.end,
+                        confidence: word.confidence,
+                        speaker,
+                    }));
+                    segments.push(TranscriptSegment {
+                        text: utterance.text.trim().to_string(),
+                        start: utterance.start,
+                        end: utterance.end,
+                        confidence: utterance.confidence,
+                        speaker,
+                    });
+                }
+
+                Ok(TranscriptionResponse {
+                    text: transcription.full_transcript,
+                    segments,
+                    words,
+                    language: transcription.languages.into_iter().next(),
+                })
+            }
+            Some("error") => Err(TranscriptionError::JobFailed {
+                message: format!(
+                    "Gladia transcription failed{}",
+                    result
+                        .error_code
+                        .map(|code| format!(" (error code {code})"))
+                        .unwrap_or_default()
+                ),
+            }
+            .into()),
+            status => Err(anyhow::anyhow!(
+                "Gladia transcription {} is not finished (status: {})",
+                result.id,
+                status.unwrap_or("unknown")
+            )),
+        }
+    }
+
+    async fn transcribe(
+        &self,
+        config: &TranscriptionConfig,
+        audio_path: &Path,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let client = self.http_client(config)?;
+
+        // Step 1: Upload audio
+        let audio = self.upload(&client, config, audio_path).await?;
+
+        // Step 2: Submit transcription request
+        tracing::debug!("Submitting transcription request...");
+        let request = self.build_request(&client, config, audio)?;
+        let body = self.send(config, request).await?;
+        let submitted: TranscriptResponse = serde_json::from_str(&body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse Gladia submit response: {e}"))?;
+        tracing::debug!("Transcription submitted, id: {}", submitted.id);
+
+        // Step 3: Poll the result endpoint
+        let result_url = format!(
+            "{}/pre-recorded/{}",
+            base_url(config, BASE_URL),
+            submitted.id
+        );
+        let mut attempts: u32 = 0;
+        loop {
+            attempts += 1;
+            if attempts > MAX_POLL_ATTEMPTS {
+                return Err(TranscriptionError::poll_timeout(
+                    PROVIDER.name(),
+                    Duration::from_secs(MAX_POLL_ATTEMPTS as u64 * POLL_INTERVAL_SECS),
+                )
+                .into());
+            }
+
+            let poll_request = client
+                .get(&result_url)
+                .header("x-gladia-key", &config.api_key);
+            let body = self.send(config, poll_request).await?;
+            let result: TranscriptResponse = serde_json::from_str(&body)
+                .map_err(|e| anyhow::anyhow!("Failed to parse Gladia result: {e}"))?;
+
+            tracing::debug!(
+                "Poll attempt {}/{}: status={:?}, id={}",
+                attempts,
+                MAX_POLL_ATTEMPTS,
+                result.status,
+                result.id
+            );
+
+            match result.status.as_deref() {
+                Some("done" | "error") => return self.parse_response(config, &body),
+                // Still queued or processing
+                _ => tokio::time::sleep(Duration::from_secs(POLL_INTERVAL_SECS)).await,
+            }
+        }
+    }
+}
+
+/// Returns the request body that transcribes `audio_url` with `config`.
+///
+/// A requested language replaces the expected languages and turns off code-switching, so
+/// the whole recording is transcribed in that language.
+fn transcript_request(config: &TranscriptionConfig, audio_url: String) -> TranscriptRequest {
+    let gladia_config = &config.providers.gladia;
+    let language_config = match &config.language {
+        Some(language) => Some(LanguageConfig {
+            languages: vec![language.clone()],
+            code_switching: false,
+        }),
+        None if gladia_config.code_switching || !gladia_config.languages.is_empty() => {
+            Some(LanguageConfig {
+                languages: gladia_config.languages.clone(),
+                code_switching: gladia_config.code_switching,
+            })
+        }
+        None => None,
+    };
+
+    TranscriptRequest {
+        audio_url,
+        model: config.model.api_model_name().to_string(),
+        language_config,
+        custom_vocabulary: !config.keywords.is_empty(),
+        custom_vocabulary_config: (!config.keywords.is_empty()).then(|| CustomVocabularyConfig {
+            vocabulary: config.keywords.clone(),
+        }),
+        diarization: config.diarize,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProvidersConfig;
+
+    fn config(keywords: &[&str]) -> TranscriptionConfig {
+        TranscriptionConfig::new(
+            Gladia.models().remove(0),
+            String::new(),
+            keywords.iter().map(|k| k.to_string()).collect(),
+            ProvidersConfig::default(),
+        )
+    }
+
+    #[test]
+    fn request_carries_vocabulary_and_code_switching() {
+        let mut config = config(&["ostt", "Gladia"]);
+        let request = serde_json::to_value(transcript_request(&config, "u".to_string())).unwrap();
+        assert_eq!(
+            request,
+            serde_json::json!({
+                "audio_url": "u",
+                "model": "solaria-1",
+                "custom_vocabulary": true,
+                "custom_vocabulary_config": {"vocabulary": ["ostt", "Gladia"]},
+            })
+        );
+
+        config.providers.gladia.code_switching = true;
+        config.providers.gladia.languages = vec!["en".to_string(), "sv".to_string()];
+        let request = serde_json::to_value(transcript_request(&config, "u".to_string())).unwrap();
+        assert_eq!(
+            request["language_config"],
+            serde_json::json!({"languages": ["en", "sv"], "code_switching": true})
+        );
+
+        // A requested language fixes the language for the whole recording
+        config.language = Some("de".to_string());
+        let request = serde_json::to_value(transcript_request(&config, "u".to_string())).unwrap();
+        assert_eq!(
+            request["language_config"],
+            serde_json::json!({"languages": ["de"], "code_switching": false})
+        );
+    }
+
+    #[test]
+    fn finished_result_includes_utterances_and_words() {
+        let body = r#"{"id":"g-1","status":"done","result":{"transcription":{
+            "full_transcript":"Hello. Hej hej.","languages":["en","sv"],
+            "utterances":[
+              {"text":" Hello.","start":0.1,"end":0.6,"confidence":0.95,"speaker":0,"language":"en",
+               "words":[{"word":" Hello.","start":0.1,"end":0.6,"confidence":0.95}]},
+              {"text":" Hej hej.","start":0.8,"end":1.5,"confidence":0.9,"speaker":1,"language":"sv",
+               "words":[{"word":" Hej","start":0.8,"end":1.1,"confidence":0.9},
+                        {"word":" hej.","start":1.1,"end":1.5,"confidence":0.9}]}]}}}"#;
+
+        let response = Gladia.parse_response(&config(&[]), body).unwrap();
+        assert_eq!(response.text, "Hello. Hej hej.");
+        assert_eq!(response.language.as_deref(), Some("en"));
+        assert_eq!(response.segments[1].text, "Hej hej.");
+        assert_eq!(response.segments[1].speaker, Some(2));
+        let words: Vec<&str> = response.words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(words, ["Hello.", "Hej", "hej."]);
+
+        let failed = r#"{"id":"g-1","status":"error","error_code":422,"result":null}"#;
+        let e = Gladia.parse_response(&config(&[]), failed).unwrap_err();
+        assert!(matches!(
+            e.downcast_ref::<TranscriptionError>(),
+            Some(TranscriptionError::JobFailed { .. })
+        ));
+    }
+}
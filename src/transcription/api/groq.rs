@@ -2,117 +2,161 @@
 //!
 //! Handles transcription requests to Groq's OpenAI-compatible Whisper API using multipart form data.
 
-use serde::Deserialize;
-use std::path::Path;
+use async_trait::async_trait;
 
-use super::TranscriptionConfig;
+use super::verbose_json::{self, VerboseJsonResponse};
+use super::{
+    base_url, AudioUpload, Provider, ProviderCapabilities, TranscriptionConfig,
+    TranscriptionResponse,
+};
+use crate::transcription::{TranscriptionModel, TranscriptionProvider};
 
-/// Groq API response wrapper
-#[derive(Debug, Deserialize)]
-struct GroqResponse {
-    text: String,
-}
+const PROVIDER: TranscriptionProvider = TranscriptionProvider::new("groq", "Groq");
+
+const BASE_URL: &str = "https://api.groq.com/openai/v1";
+
+const TRANSCRIPTION_PATH: &str = "/audio/transcriptions";
+
+/// Path of the endpoint that lists the models available to the account
+const MODELS_PATH: &str = "/models";
+
+/// Path of the endpoint that translates speech to English
+const TRANSLATION_PATH: &str = "/audio/translations";
+
+/// The only Groq model that supports translation
+const TRANSLATION_MODEL: &str = "whisper-large-v3";
+
+/// Maximum upload size on the free tier
+const MAX_UPLOAD_BYTES: u64 = 25 * 1024 * 1024;
 
-/// Transcribes an audio file using Groq's Whisper API.
+/// Audio containers accepted by the transcriptions endpoint
+const CONTAINERS: &[&str] = &[
+    "flac", "m4a", "mp3", "mp4", "mpeg", "mpga", "ogg", "opus", "wav", "webm",
+];
+
+/// Groq transcription provider.
 ///
 /// Uses multipart form data with bearer token authentication.
 /// Groq provides an OpenAI-compatible API endpoint.
 ///
 /// Keywords are passed as the `prompt` parameter to guide transcription context.
-pub async fn transcribe(config: &TranscriptionConfig, audio_path: &Path) -> anyhow::Result<String> {
-    let audio_data =
-        std::fs::read(audio_path).map_err(|e| anyhow::anyhow!("Failed to read audio file: {e}"))?;
-
-    let client = reqwest::Client::new();
-
-    let file_name = audio_path
-        .file_name()
-        .unwrap_or_default()
-        .to_string_lossy()
-        .to_string();
-
-    let file_part = reqwest::multipart::Part::bytes(audio_data)
-        .file_name(file_name.clone())
-        .mime_str("audio/mpeg")
-        .map_err(|e| anyhow::anyhow!("Failed to create file part for upload: {e}"))?;
-
-    let mut form = reqwest::multipart::Form::new()
-        .part("file", file_part)
-        .text("model", config.model.api_model_name().to_string());
-
-    // Debug log: Log the API call details (without the audio data)
-    let mut debug_params = vec![format!("model={}", config.model.api_model_name())];
-
-    // Add keywords as prompt for better transcription context
-    if !config.keywords.is_empty() {
-        let prompt = config.keywords.join(", ");
-        form = form.text("prompt", prompt.clone());
-        debug_params.push(format!("prompt={prompt}"));
-        tracing::debug!(
-            "Keywords used as prompt for Groq model: {:?}",
-            config.keywords
-        );
+/// Responses are requested as `verbose_json` to get segment and word timestamps.
+pub struct Groq;
+
+#[async_trait]
+impl Provider for Groq {
+    fn info(&self) -> TranscriptionProvider {
+        PROVIDER
     }
 
-    let endpoint = config.model.endpoint();
-
-    tracing::debug!(
-        "Groq API Call:\n  URL: {}\n  Method: POST\n  Headers:\n    Authorization: Bearer <redacted>\n    Content-Type: multipart/form-data\n  Body parameters: {}",
-        endpoint,
-        debug_params.join("\n    ")
-    );
-
-    let response = match client
-        .post(endpoint)
-        .bearer_auth(&config.api_key)
-        .multipart(form)
-        .send()
-        .await
-    {
-        Ok(resp) => resp,
-        Err(e) => {
-            let error_msg = if e.is_connect() {
-                "Failed to connect to Groq API server. Check your internet connection.".to_string()
-            } else if e.is_timeout() {
-                "Request to Groq timed out. The API server is not responding.".to_string()
-            } else if e.to_string().contains("builder") {
-                format!("Failed to build Groq API request: {e}. This may be a configuration error.")
-            } else {
-                format!("Groq network error: {e}")
-            };
-            return Err(anyhow::anyhow!(error_msg));
+    fn models(&self) -> Vec<TranscriptionModel> {
+        vec![
+            TranscriptionModel::new(
+                PROVIDER,
+                "groq-whisper-large-v3",
+                "whisper-large-v3",
+                "Whisper Large V3 (high accuracy)",
+            ),
+            TranscriptionModel::new(
+                PROVIDER,
+                "groq-whisper-large-v3-turbo",
+                "whisper-large-v3-turbo",
+                "Whisper Large V3 Turbo (fastest)",
+            ),
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            max_upload_bytes: Some(MAX_UPLOAD_BYTES),
+            containers: CONTAINERS,
+            timestamps: true,
+            ..Default::default()
         }
-    };
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-
-        let human_readable = match status.as_u16() {
-            401 => "Groq API key is invalid or expired. Please run 'ostt auth' to update your API key.".to_string(),
-            403 => "You don't have permission to use Groq's API. Check your API key and account status.".to_string(),
-            429 => "Too many requests to Groq. You've hit the API rate limit. Please wait and try again.".to_string(),
-            500 | 502 | 503 | 504 => "Groq API server is experiencing issues. Please try again later.".to_string(),
-            _ => format!("Groq API error (status {status}): {error_body}"),
+    }
+
+    fn supports_translation(&self, model: &TranscriptionModel) -> bool {
+        model.api_model_name() == TRANSLATION_MODEL
+    }
+
+    fn model_list_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+    ) -> Option<reqwest::RequestBuilder> {
+        let endpoint = format!("{}{MODELS_PATH}", base_url(config, BASE_URL));
+        Some(client.get(endpoint).bearer_auth(&config.api_key))
+    }
+
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let mut form = reqwest::multipart::Form::new()
+            .part("file", audio.into_file_part()?)
+            .text("model", config.model.api_model_name().to_string());
+        let path = if config.translate {
+            // Translations return segments but not word timings
+            form = form.text("response_format", "verbose_json");
+            TRANSLATION_PATH
+        } else {
+            form = verbose_json::add_form_fields(form);
+            TRANSCRIPTION_PATH
         };
+        let endpoint = format!("{}{path}", base_url(config, BASE_URL));
+
+        // Debug log: Log the API call details (without the audio data)
+        let mut debug_params = vec![
+            format!("model={}", config.model.api_model_name()),
+            "response_format=verbose_json".to_string(),
+        ];
+
+        // Translations are always English, so the endpoint takes no language
+        if let Some(language) = config.language.as_ref().filter(|_| !config.translate) {
+            form = form.text("language", language.clone());
+            debug_params.push(format!("language={language}"));
+        }
+
+        // Add keywords as prompt for better transcription context
+        if !config.keywords.is_empty() {
+            let prompt = config.keywords.join(", ");
+            form = form.text("prompt", prompt.clone());
+            debug_params.push(format!("prompt={prompt}"));
+            tracing::debug!(
+                "Keywords used as prompt for Groq model: {:?}",
+                config.keywords
+            );
+        }
+
+        tracing::debug!(
+            "Groq API Call:\n  URL: {}\n  Method: POST\n  Headers:\n    Authorization: Bearer <redacted>\n    Content-Type: multipart/form-data\n  Body parameters: {}",
+            endpoint,
+            debug_params.join("\n    ")
+        );
 
-        return Err(anyhow::anyhow!(human_readable));
+        Ok(client
+            .post(&endpoint)
+            .bearer_auth(&config.api_key)
+            .multipart(form))
     }
 
-    let groq_response: GroqResponse = response
-        .json()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to parse Groq response: {e}"))?;
+    fn parse_response(
+        &self,
+        _config: &TranscriptionConfig,
+        body: &str,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let groq_response: VerboseJsonResponse = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse Groq response: {e}"))?;
 
-    // Debug log: Log the full response for debugging
-    tracing::debug!(
-        "Groq API Response:\n  Status: Success\n  Transcription length: {} characters\n  Full response: {:#?}",
-        groq_response.text.len(),
-        groq_response
-    );
+        // Debug log: Log the full response for debugging
+        tracing::debug!(
+            "Groq API Response:\n  Status: Success\n  Transcription length: {} characters\n  Full response: {:#?}",
+            groq_response.text_len(),
+            groq_response
+        );
 
-    Ok(groq_response.text.trim().to_string())
+        Ok(groq_response.into())
+    }
 }
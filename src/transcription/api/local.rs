@@ -0,0 +1,247 @@
+//! Offline transcription with whisper.cpp.
+//!
+//! Runs Whisper GGML models on the CPU through whisper-rs, so no API key or network
+//! connection is needed. The audio is decoded to 16 kHz mono PCM with ffmpeg and the model
+//! file is loaded for every transcription. Only built with the `local-whisper` cargo feature.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+use super::{
+    AudioUpload, KeywordSupport, Provider, ProviderCapabilities, TranscriptSegment,
+    TranscriptionConfig, TranscriptionResponse,
+};
+use crate::recording::ffmpeg;
+use crate::transcription::local_model;
+use crate::transcription::{TranscriptionModel, TranscriptionProvider};
+
+const PROVIDER: TranscriptionProvider = TranscriptionProvider::new("local", "Local (whisper.cpp)");
+
+/// Sample rate whisper.cpp expects its input in
+const SAMPLE_RATE: u32 = 16000;
+
+/// Upper bound for the default thread count; more threads stop helping on most CPUs
+const MAX_DEFAULT_THREADS: usize = 8;
+
+/// Offline whisper.cpp provider.
+///
+/// Keywords are passed as the initial prompt, which Whisper uses as preceding context.
+/// The model file comes from `[providers.local].model_path` or the models downloaded by
+/// `ostt auth`.
+pub struct Local;
+
+#[async_trait]
+impl Provider for Local {
+    fn info(&self) -> TranscriptionProvider {
+        PROVIDER
+    }
+
+    fn models(&self) -> Vec<TranscriptionModel> {
+        vec![
+            TranscriptionModel::new(
+                PROVIDER,
+                "local-whisper-tiny",
+                "tiny",
+                "Whisper Tiny (offline, 75 MB download)",
+            ),
+            TranscriptionModel::new(
+                PROVIDER,
+                "local-whisper-base",
+                "base",
+                "Whisper Base (offline, 142 MB download)",
+            ),
+            TranscriptionModel::new(
+                PROVIDER,
+                "local-whisper-small",
+                "small",
+                "Whisper Small (offline, 466 MB download)",
+            ),
+            TranscriptionModel::new(
+                PROVIDER,
+                "local-whisper-large-v3-turbo",
+                "large-v3-turbo",
+                "Whisper Large V3 Turbo (offline, 1.6 GB download)",
+            ),
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            requires_api_key: false,
+            keywords: KeywordSupport::Prompt,
+            timestamps: true,
+            ..Default::default()
+        }
+    }
+
+    /// English-only (`.en`) and turbo models were not trained to translate.
+    fn supports_translation(&self, model: &TranscriptionModel) -> bool {
+        let name = model.api_model_name();
+        !name.ends_with(".en") && !name.contains("turbo")
+    }
+
+    fn build_request(
+        &self,
+        _client: &reqwest::Client,
+        _config: &TranscriptionConfig,
+        _audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        Err(anyhow::anyhow!(
+            "{} transcribes on this machine and does not send requests",
+            PROVIDER.name()
+        ))
+    }
+
+    fn parse_response(
+        &self,
+        _config: &TranscriptionConfig,
+        _body: &str,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        Err(anyhow::anyhow!(
+            "{} transcribes on this machine and does not receive responses",
+            PROVIDER.name()
+        ))
+    }
+
+    async fn transcribe(
+        &self,
+        config: &TranscriptionConfig,
+        audio_path: &Path,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let model_path = local_model::model_path(&config.providers, &config.model)?;
+        if !model_path.exists() {
+            return Err(anyhow::anyhow!(
+                "Model file {} not found. Run 'ostt auth' and select {} to download it, or set [providers.local].model_path in ostt.toml.",
+                model_path.display(),
+                config.model.id()
+            ));
+        }
+
+        let job = WhisperJob {
+            model_path,
+            audio_path: audio_path.to_path_buf(),
+            language: config.language.clone(),
+            translate: config.translate,
+            prompt: config.keywords.join(", ").replace('\0', ""),
+            threads: config
+                .providers
+                .local
+                .threads
+                .map(|threads| threads as usize)
+                .unwrap_or_else(default_threads),
+        };
+        tracing::debug!(
+            "whisper.cpp transcription:\n  Model: {}\n  Threads: {}\n  Language: {}\n  Translate: {}\n  Prompt: {}",
+            job.model_path.display(),
+            job.threads,
+            job.language.as_deref().unwrap_or("auto"),
+            job.translate,
+            job.prompt
+        );
+
+        tokio::task::spawn_blocking(move || job.run())
+            .await
+            .map_err(|e| anyhow::anyhow!("whisper.cpp transcription task failed: {e}"))?
+    }
+}
+
+/// Everything needed to run one transcription on a blocking thread.
+struct WhisperJob {
+    model_path: std::path::PathBuf,
+    audio_path: std::path::PathBuf,
+    language: Option<String>,
+    translate: bool,
+    prompt: String,
+    threads: usize,
+}
+
+impl WhisperJob {
+    /// Decodes the audio, runs the model and collects the timed segments.
+    fn run(self) -> anyhow::Result<TranscriptionResponse> {
+        // Route whisper.cpp's logging into tracing instead of the terminal
+        whisper_rs::install_logging_hooks();
+
+        let samples = ffmpeg::decode_pcm(&self.audio_path, SAMPLE_RATE)?;
+        let context =
+            WhisperContext::new_with_params(&self.model_path, WhisperContextParameters::default())
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "Failed to load whisper model {}: {e}",
+                        self.model_path.display()
+                    )
+                })?;
+        let mut state = context
+            .create_state()
+            .map_err(|e| anyhow::anyhow!("Failed to initialize whisper.cpp: {e}"))?;
+
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        params.set_n_threads(self.threads as i32);
+        params.set_language(Some(self.language.as_deref().unwrap_or("auto")));
+        params.set_translate(self.translate);
+        if !self.prompt.is_empty() {
+            params.set_initial_prompt(&self.prompt);
+        }
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+
+        state
+            .full(params, &samples)
+            .map_err(|e| anyhow::anyhow!("whisper.cpp transcription failed: {e}"))?;
+
+        let mut segments = Vec::new();
+        for segment in state.as_iter() {
+            let text = segment
+                .to_str_lossy()
+                .map_err(|e| anyhow::anyhow!("Failed to read whisper.cpp segment: {e}"))?;
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            // whisper.cpp timestamps are in centiseconds
+            segments.push(TranscriptSegment {
+                text: text.to_string(),
+                start: segment.start_timestamp() as f64 / 100.0,
+                end: segment.end_timestamp() as f64 / 100.0,
+                confidence: None,
+                speaker: None,
+            });
+        }
+
+        let text = segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let language = if self.translate {
+            Some("en".to_string())
+        } else {
+            whisper_rs::get_lang_str(state.full_lang_id_from_state())
+                .map(str::to_string)
+                .or(self.language)
+        };
+        tracing::debug!(
+            "whisper.cpp transcription finished: {} segments, {} characters",
+            segments.len(),
+            text.len()
+        );
+
+        Ok(TranscriptionResponse {
+            text,
+            segments,
+            words: Vec::new(),
+            language,
+        })
+    }
+}
+
+/// Returns the number of CPU cores, up to [`MAX_DEFAULT_THREADS`].
+fn default_threads() -> usize {
+    std::thread::available_parallelism()
+        .map(|cores| cores.get())
+        .unwrap_or(4)
+        .min(MAX_DEFAULT_THREADS)
+}
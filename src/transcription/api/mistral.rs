@@ -0,0 +1,198 @@
+//! Mistral Voxtral API implementation.
+//!
+//! Handles transcription requests to Mistral's audio transcription API using multipart form
+//! data. Mistral hosts its API in the EU.
+
+use async_trait::async_trait;
+
+use super::verbose_json::VerboseJsonResponse;
+use super::{
+    base_url, AudioUpload, KeywordSupport, Provider, ProviderCapabilities, TranscriptionConfig,
+    TranscriptionResponse,
+};
+use crate::transcription::{TranscriptionModel, TranscriptionProvider};
+
+const PROVIDER: TranscriptionProvider = TranscriptionProvider::new("mistral", "Mistral");
+
+const BASE_URL: &str = "https://api.mistral.ai/v1";
+
+/// Most terms accepted in `context_bias`
+const MAX_CONTEXT_BIAS_TERMS: usize = 100;
+
+/// Mistral Voxtral transcription provider.
+///
+/// Uses multipart form data with bearer token authentication.
+///
+/// Keywords, followed by `[providers.mistral].context_bias`, are sent as `context_bias`
+/// terms. Segment timestamps are requested unless a language is set, since the API does
+/// not accept both in one request.
+pub struct Mistral;
+
+#[async_trait]
+impl Provider for Mistral {
+    fn info(&self) -> TranscriptionProvider {
+        PROVIDER
+    }
+
+    fn models(&self) -> Vec<TranscriptionModel> {
+        vec![
+            TranscriptionModel::new(
+                PROVIDER,
+                "mistral-voxtral-mini-latest",
+                "voxtral-mini-latest",
+                "Voxtral Mini Transcribe (latest, EU-hosted)",
+            ),
+            TranscriptionModel::new(
+                PROVIDER,
+                "mistral-voxtral-mini-2507",
+                "voxtral-mini-2507",
+                "Voxtral Mini Transcribe (July 2025 release)",
+            ),
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            keywords: KeywordSupport::Keyterms,
+            timestamps: true,
+            ..Default::default()
+        }
+    }
+
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let mistral_config = &config.providers.mistral;
+        let mut form = reqwest::multipart::Form::new()
+            .part("file", audio.into_file_part()?)
+            .text("model", config.model.api_model_name().to_string());
+
+        // Debug log: Log the API call details (without the audio data)
+        let mut debug_params = vec![format!("model={}", config.model.api_model_name())];
+
+        // The requested language takes precedence over the provider config
+        let language = config
+            .language
+            .as_ref()
+            .or(mistral_config.language.as_ref())
+            .filter(|language| !language.is_empty());
+        match language {
+            Some(language) => {
+                form = form.text("language", language.clone());
+                debug_params.push(format!("language={language}"));
+            }
+            None => {
+                form = form.text("timestamp_granularities", "segment");
+                debug_params.push("timestamp_granularities=segment".to_string());
+            }
+        }
+
+        let terms = context_bias(config);
+        for term in &terms {
+            form = form.text("context_bias", term.clone());
+        }
+        if !terms.is_empty() {
+            debug_params.push(format!("context_bias={terms:?}"));
+        }
+
+        let endpoint = format!("{}/audio/transcriptions", base_url(config, BASE_URL));
+        tracing::debug!(
+            "Mistral API Call:\n  URL: {}\n  Method: POST\n  Headers:\n    Authorization: Bearer <redacted>\n    Content-Type: multipart/form-data\n  Body parameters: {}",
+            endpoint,
+            debug_params.join("\n    ")
+        );
+
+        Ok(client
+            .post(&endpoint)
+            .bearer_auth(&config.api_key)
+            .multipart(form))
+    }
+
+    fn parse_response(
+        &self,
+        _config: &TranscriptionConfig,
+        body: &str,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let mistral_response: VerboseJsonResponse = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse Mistral response: {e}"))?;
+
+        tracing::debug!(
+            "Mistral API Response:\n  Status: Success\n  Transcription length: {} characters",
+            mistral_response.text_len()
+        );
+
+        Ok(mistral_response.into())
+    }
+}
+
+/// Returns the keywords followed by the configured context terms, without duplicates and
+/// limited to what the API accepts.
+fn context_bias(config: &TranscriptionConfig) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for term in config
+        .keywords
+        .iter()
+        .chain(&config.providers.mistral.context_bias)
+    {
+        let term = term.trim();
+        if !term.is_empty() && !terms.iter().any(|t| t == term) {
+            terms.push(term.to_string());
+        }
+    }
+    if terms.len() > MAX_CONTEXT_BIAS_TERMS {
+        tracing::warn!(
+            "Mistral accepts at most {MAX_CONTEXT_BIAS_TERMS} context terms, ignoring the other {}",
+            terms.len() - MAX_CONTEXT_BIAS_TERMS
+        );
+        terms.truncate(MAX_CONTEXT_BIAS_TERMS);
+    }
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProvidersConfig;
+
+    fn config(keywords: &[&str], context_bias: &[&str]) -> TranscriptionConfig {
+        let mut providers = ProvidersConfig::default();
+        providers.mistral.context_bias = context_bias.iter().map(|t| t.to_string()).collect();
+        TranscriptionConfig::new(
+            Mistral.models().remove(0),
+            String::new(),
+            keywords.iter().map(|k| k.to_string()).collect(),
+            providers,
+        )
+    }
+
+    #[test]
+    fn keywords_and_configured_terms_are_sent_as_context_bias() {
+        let terms = context_bias(&config(&["ostt", "Voxtral"], &["Berget", "ostt", " "]));
+        assert_eq!(terms, ["ostt", "Voxtral", "Berget"]);
+
+        let many: Vec<String> = (0..150).map(|i| format!("term{i}")).collect();
+        let many: Vec<&str> = many.iter().map(String::as_str).collect();
+        assert_eq!(
+            context_bias(&config(&many, &[])).len(),
+            MAX_CONTEXT_BIAS_TERMS
+        );
+    }
+
+    #[test]
+    fn response_keeps_segments_and_language() {
+        let body = r#"{"model":"voxtral-mini-2507","text":"Hej världen. Hello world.","language":"sv",
+            "segments":[{"text":"Hej världen.","start":0.0,"end":1.2},
+                        {"text":" Hello world.","start":1.2,"end":2.4}],
+            "usage":{"prompt_audio_seconds":3}}"#;
+
+        let response = Mistral.parse_response(&config(&[], &[]), body).unwrap();
+        assert_eq!(response.text, "Hej världen. Hello world.");
+        assert_eq!(response.language.as_deref(), Some("sv"));
+        assert_eq!(response.segments.len(), 2);
+        assert_eq!(response.segments[1].text, "Hello world.");
+        assert_eq!(response.segments[1].end, 2.4);
+    }
+}
@@ -1,23 +1,41 @@
 //! Transcription API client with provider-specific implementations.
 //!
 //! This module provides a trait-based system for handling multiple transcription providers
-//! (OpenAI, Deepgram, etc.) with their respective APIs. Each provider implements the
-//! `TranscriptionProvider` trait to handle authentication and API communication.
+//! (OpenAI, Deepgram, etc.) with their respective APIs. Each provider module implements the
+//! [`Provider`] trait to describe its models and capabilities, build its HTTP requests and
+//! parse its responses. Providers are looked up through the
+//! [`ProviderRegistry`](super::registry::ProviderRegistry), so adding a provider means adding
+//! a module here and listing it in [`builtin_providers`].
 
 mod assemblyai;
+mod azure;
 mod berget;
+mod custom;
 mod deepgram;
 mod deepinfra;
 mod elevenlabs;
+mod gladia;
 mod groq;
+#[cfg(feature = "local-whisper")]
+mod local;
+mod mistral;
 mod openai;
+mod speechmatics;
+mod verbose_json;
 
-use serde::Deserialize;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::sync::Arc;
 
+use super::chunking;
+use super::discovery;
+use super::error::{self, TranscriptionError};
 use super::model::TranscriptionModel;
 use super::provider::TranscriptionProvider;
-use crate::config::file::ProvidersConfig;
+use super::registry;
+use super::streaming::{LiveRequest, LiveUpdate};
+use crate::config::file::{NetworkConfig, ProvidersConfig};
 
 /// Configuration for transcription requests
 #[derive(Debug, Clone)]
@@ -30,6 +48,14 @@ pub struct TranscriptionConfig {
     pub keywords: Vec<String>,
     /// Provider-specific configurations
     pub providers: ProvidersConfig,
+    /// Label speakers and render the transcript as speaker turns
+    pub diarize: bool,
+    /// Spoken language as an ISO-639-1 code (e.g. "en", "sv"), or `None` to auto-detect
+    pub language: Option<String>,
+    /// Translate the speech to English instead of transcribing it
+    pub translate: bool,
+    /// Proxy, certificate and timeout settings for provider requests
+    pub network: NetworkConfig,
 }
 
 impl TranscriptionConfig {
@@ -45,43 +71,767 @@ impl TranscriptionConfig {
             api_key,
             keywords,
             providers,
+            diarize: false,
+            language: None,
+            translate: false,
+            network: NetworkConfig::default(),
         }
     }
 }
 
 /// Response from transcription APIs (unified across providers).
-#[derive(Debug, Clone, Deserialize)]
+///
+/// Providers that report timing information fill in `segments` and `words`; both are empty
+/// for providers that only return plain text.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct TranscriptionResponse {
     /// The transcribed text from the audio file
     pub text: String,
+    /// Sentence- or utterance-level segments in chronological order
+    #[serde(default)]
+    pub segments: Vec<TranscriptSegment>,
+    /// Individual words in chronological order
+    #[serde(default)]
+    pub words: Vec<TranscriptWord>,
+    /// Language of the audio as reported by the provider, or as requested if the
+    /// provider does not report one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+impl TranscriptionResponse {
+    /// Creates a response that carries only text, without timing information.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the end of the last timed segment or word in seconds, if there are timings.
+    pub fn timed_duration(&self) -> Option<f64> {
+        let segment_end = self.segments.last().map(|segment| segment.end);
+        let word_end = self.words.last().map(|word| word.end);
+        match (segment_end, word_end) {
+            (Some(segment_end), Some(word_end)) => Some(segment_end.max(word_end)),
+            (segment_end, word_end) => segment_end.or(word_end),
+        }
+    }
+}
+
+/// A timed segment of the transcript (a sentence or utterance).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    /// The text spoken in this segment
+    pub text: String,
+    /// Start time in seconds from the beginning of the audio
+    pub start: f64,
+    /// End time in seconds from the beginning of the audio
+    pub end: f64,
+    /// Provider confidence between 0.0 and 1.0, if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    /// Speaker number starting at 1, if speakers were labeled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<u32>,
+}
+
+/// A single timed word of the transcript.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptWord {
+    /// The word as it appears in the transcript (including punctuation where available)
+    pub text: String,
+    /// Start time in seconds from the beginning of the audio
+    pub start: f64,
+    /// End time in seconds from the beginning of the audio
+    pub end: f64,
+    /// Provider confidence between 0.0 and 1.0, if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    /// Speaker number starting at 1, if speakers were labeled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<u32>,
+}
+
+/// How a provider accepts keywords for biasing recognition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordSupport {
+    /// Keywords are ignored
+    None,
+    /// Keywords are joined into a free-text context prompt (Whisper-style)
+    Prompt,
+    /// Keywords are sent as individual key terms to boost
+    Keyterms,
+    /// Keywords are sent as a dedicated hotword list
+    Hotwords,
+}
+
+impl KeywordSupport {
+    /// Returns the lowercase name shown by `ostt models`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeywordSupport::None => "none",
+            KeywordSupport::Prompt => "prompt",
+            KeywordSupport::Keyterms => "keyterms",
+            KeywordSupport::Hotwords => "hotwords",
+        }
+    }
+}
+
+/// Static capabilities of a transcription provider.
+#[derive(Debug, Clone, Copy)]
+pub struct ProviderCapabilities {
+    /// Whether an API key must be saved before the provider can be used
+    pub requires_api_key: bool,
+    /// How keywords from `ostt keywords` are passed to the provider
+    pub keywords: KeywordSupport,
+    /// Whether the provider can transcribe audio live while recording
+    pub streaming: bool,
+    /// Whether the provider can label speakers
+    pub diarization: bool,
+    /// Largest audio file accepted in a single request, in bytes
+    pub max_upload_bytes: Option<u64>,
+    /// Longest audio accepted in a single request, in seconds
+    pub max_duration_secs: Option<u64>,
+    /// Audio file extensions accepted for upload; empty if any common format is decoded
+    pub containers: &'static [&'static str],
+    /// Whether transcripts come with segment or word timings
+    pub timestamps: bool,
+    /// Spoken languages as ISO-639-1 codes; empty if any language is transcribed
+    pub languages: &'static [&'static str],
+}
+
+impl Default for ProviderCapabilities {
+    fn default() -> Self {
+        Self {
+            requires_api_key: true,
+            keywords: KeywordSupport::Prompt,
+            streaming: false,
+            diarization: false,
+            max_upload_bytes: None,
+            max_duration_secs: None,
+            containers: &[],
+            timestamps: false,
+            languages: &[],
+        }
+    }
+}
+
+/// Audio containers accepted by the OpenAI-compatible `/audio/transcriptions` endpoints
+pub const OPENAI_CONTAINERS: &[&str] = &[
+    "flac", "m4a", "mp3", "mp4", "mpeg", "mpga", "oga", "ogg", "wav", "webm",
+];
+
+/// What a single model supports, as listed by `ostt models`.
+///
+/// Requests are checked against these before any audio is uploaded, so unsupported
+/// settings fail with a clear message instead of an HTTP error from the provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelCapabilities {
+    /// Largest audio file accepted in a single request, in bytes
+    pub max_upload_bytes: Option<u64>,
+    /// Longest audio accepted in a single request, in seconds
+    pub max_duration_secs: Option<u64>,
+    /// Audio file extensions accepted for upload; empty if any common format is decoded
+    pub containers: &'static [&'static str],
+    /// How keywords from `ostt keywords` are passed to the model
+    pub keywords: KeywordSupport,
+    /// Whether transcripts come with segment or word timings
+    pub timestamps: bool,
+    /// Whether the model can label speakers
+    pub diarization: bool,
+    /// Whether the model can translate speech to English
+    pub translation: bool,
+    /// Whether the model can transcribe live while recording
+    pub streaming: bool,
+    /// Spoken languages as ISO-639-1 codes; empty if any language is transcribed
+    pub languages: &'static [&'static str],
+}
+
+impl ModelCapabilities {
+    /// Returns the capabilities of a model that supports everything its provider does.
+    pub fn from_provider(capabilities: ProviderCapabilities, translation: bool) -> Self {
+        Self {
+            max_upload_bytes: capabilities.max_upload_bytes,
+            max_duration_secs: capabilities.max_duration_secs,
+            containers: capabilities.containers,
+            keywords: capabilities.keywords,
+            timestamps: capabilities.timestamps,
+            diarization: capabilities.diarization,
+            translation,
+            streaming: capabilities.streaming,
+            languages: capabilities.languages,
+        }
+    }
+
+    /// Returns true if the model accepts files with this extension.
+    pub fn accepts_container(&self, extension: &str) -> bool {
+        self.containers.is_empty()
+            || self
+                .containers
+                .iter()
+                .any(|container| container.eq_ignore_ascii_case(extension))
+    }
+
+    /// Returns true if the model transcribes this ISO-639-1 language.
+    pub fn supports_language(&self, language: &str) -> bool {
+        self.languages.is_empty()
+            || self
+                .languages
+                .iter()
+                .any(|code| code.eq_ignore_ascii_case(language))
+    }
+
+    /// Checks a request against the model's capabilities before the audio is uploaded.
+    ///
+    /// Upload size and duration limits are not checked here; files over them are split
+    /// by [`chunking`].
+    ///
+    /// # Errors
+    /// - If the audio container is not accepted
+    /// - If translation is requested but not supported
+    /// - If the requested language is not supported
+    pub fn check(&self, config: &TranscriptionConfig, audio_path: &Path) -> anyhow::Result<()> {
+        let model_id = config.model.id();
+        if let Some(extension) = audio_path.extension().and_then(|e| e.to_str()) {
+            if !self.accepts_container(extension) {
+                return Err(TranscriptionError::UnsupportedFormat {
+                    message: format!(
+                        "{model_id} does not accept .{extension} files. Convert the audio to one of: {}",
+                        self.containers.join(", ")
+                    ),
+                }
+                .into());
+            }
+        }
+        if config.translate && !self.translation {
+            return Err(anyhow::anyhow!(
+                "{model_id} cannot translate speech. Choose a model with translation, such as OpenAI Whisper or Groq Whisper Large V3"
+            ));
+        }
+        if let Some(language) = config.language.as_deref().filter(|_| !config.translate) {
+            if !self.supports_language(language) {
+                return Err(anyhow::anyhow!(
+                    "{model_id} does not transcribe '{language}'. Supported languages: {}",
+                    self.languages.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for ModelCapabilities {
+    fn default() -> Self {
+        Self::from_provider(ProviderCapabilities::default(), false)
+    }
+}
+
+/// Audio that has been prepared for a provider request.
+#[derive(Debug)]
+pub enum AudioUpload {
+    /// Raw audio bytes to embed in the request
+    Bytes {
+        /// The audio file content
+        data: Vec<u8>,
+        /// The original file name, used for multipart uploads
+        file_name: String,
+    },
+    /// A URL where the provider can fetch previously uploaded audio
+    Url(String),
+}
+
+impl AudioUpload {
+    /// Reads an audio file from disk into memory.
+    ///
+    /// # Errors
+    /// - If the audio file cannot be read
+    pub fn from_path(audio_path: &Path) -> anyhow::Result<Self> {
+        let data = std::fs::read(audio_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read audio file: {e}"))?;
+        let file_name = audio_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        Ok(AudioUpload::Bytes { data, file_name })
+    }
+
+    /// Returns the audio as a multipart file part.
+    ///
+    /// # Errors
+    /// - If the audio has already been uploaded and is only available by URL
+    /// - If the part cannot be created
+    pub fn into_file_part(self) -> anyhow::Result<reqwest::multipart::Part> {
+        let (data, file_name) = self.into_bytes()?;
+        reqwest::multipart::Part::bytes(data)
+            .file_name(file_name)
+            .mime_str("audio/mpeg")
+            .map_err(|e| anyhow::anyhow!("Failed to create file part for upload: {e}"))
+    }
+
+    /// Returns the raw audio bytes together with the original file name.
+    ///
+    /// # Errors
+    /// - If the audio has already been uploaded and is only available by URL
+    pub fn into_bytes(self) -> anyhow::Result<(Vec<u8>, String)> {
+        match self {
+            AudioUpload::Bytes { data, file_name } => Ok((data, file_name)),
+            AudioUpload::Url(_) => Err(anyhow::anyhow!(
+                "Audio was uploaded by URL but the provider expects raw audio data"
+            )),
+        }
+    }
+}
+
+/// A transcription provider implementation.
+///
+/// The default [`Provider::transcribe`] runs the common single-request flow:
+/// [`upload`](Provider::upload) the audio, [`build_request`](Provider::build_request),
+/// send it, map HTTP errors to human-readable messages, then
+/// [`parse_response`](Provider::parse_response). Providers with a different flow
+/// (e.g. submit-and-poll) override `transcribe` and reuse the individual steps.
+#[async_trait]
+pub trait Provider: Send + Sync {
+    /// Returns the provider identity (id and display name).
+    fn info(&self) -> TranscriptionProvider;
+
+    /// Returns the built-in models offered by this provider.
+    fn models(&self) -> Vec<TranscriptionModel>;
+
+    /// Returns what this provider supports.
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::default()
+    }
+
+    /// Returns true if `model` can translate speech to English in the same request.
+    fn supports_translation(&self, _model: &TranscriptionModel) -> bool {
+        false
+    }
+
+    /// Returns what `model` supports.
+    ///
+    /// Defaults to the provider's [`capabilities`](Provider::capabilities) together with
+    /// [`supports_translation`](Provider::supports_translation); providers whose models
+    /// differ in other ways override this.
+    fn model_capabilities(&self, model: &TranscriptionModel) -> ModelCapabilities {
+        ModelCapabilities::from_provider(self.capabilities(), self.supports_translation(model))
+    }
+
+    /// Creates the HTTP client used for requests to this provider.
+    ///
+    /// Uses the `[network]` settings; providers that need other defaults (e.g. timeouts)
+    /// can start from [`http_client_builder`].
+    ///
+    /// # Errors
+    /// - If the client cannot be built, e.g. because of an invalid proxy or CA bundle
+    fn http_client(&self, config: &TranscriptionConfig) -> anyhow::Result<reqwest::Client> {
+        http_client_builder(&config.network)?
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {e}"))
+    }
+
+    /// Prepares the audio for the transcription request.
+    ///
+    /// Reads the file into memory by default. Providers that need the audio hosted
+    /// first can upload it here and return [`AudioUpload::Url`].
+    ///
+    /// # Errors
+    /// - If the audio file cannot be read or uploaded
+    async fn upload(
+        &self,
+        _client: &reqwest::Client,
+        _config: &TranscriptionConfig,
+        audio_path: &Path,
+    ) -> anyhow::Result<AudioUpload> {
+        AudioUpload::from_path(audio_path)
+    }
+
+    /// Builds the request that submits the audio for transcription.
+    ///
+    /// # Errors
+    /// - If the request cannot be assembled from the configuration
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder>;
+
+    /// Parses a successful response body into a transcription.
+    ///
+    /// # Errors
+    /// - If the body cannot be parsed or contains no transcript
+    fn parse_response(
+        &self,
+        config: &TranscriptionConfig,
+        body: &str,
+    ) -> anyhow::Result<TranscriptionResponse>;
+
+    /// Returns a human-readable message for a failed HTTP status.
+    fn describe_status(&self, status: reqwest::StatusCode, body: &str) -> String {
+        default_status_message(self.info().name(), status, body)
+    }
+
+    /// Returns a human-readable message for a request that could not be sent.
+    fn describe_send_error(&self, _config: &TranscriptionConfig, error: &reqwest::Error) -> String {
+        default_send_error_message(self.info().name(), error)
+    }
+
+    /// Sends a request and returns the response body of a successful response.
+    ///
+    /// Failed requests are returned as a [`TranscriptionError`] so callers can tell the
+    /// kind of failure apart, e.g. to fall back to another model on a rate limit.
+    ///
+    /// # Errors
+    /// - If the request fails due to network issues (connection, timeout)
+    /// - If the API returns an HTTP error status
+    async fn send(
+        &self,
+        config: &TranscriptionConfig,
+        request: reqwest::RequestBuilder,
+    ) -> anyhow::Result<String> {
+        let response = request.send().await.map_err(|e| {
+            let message = self.describe_send_error(config, &e);
+            if e.is_builder() {
+                anyhow::anyhow!(message)
+            } else {
+                TranscriptionError::Network { message }.into()
+            }
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = error::parse_retry_after(response.headers());
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            let message = self.describe_status(status, &error_body);
+            return Err(
+                TranscriptionError::from_status(status, &error_body, retry_after, message).into(),
+            );
+        }
+
+        response
+            .text()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read {} response: {e}", self.info().name()))
+    }
+
+    /// Returns the request that lists the models available to the account.
+    ///
+    /// Returns `None` for providers without a models endpoint; their models are only the
+    /// built-in and user-defined ones.
+    fn model_list_request(
+        &self,
+        _client: &reqwest::Client,
+        _config: &TranscriptionConfig,
+    ) -> Option<reqwest::RequestBuilder> {
+        None
+    }
+
+    /// Parses a model list response into the API names of the speech-to-text models.
+    ///
+    /// Defaults to an OpenAI-style list (`{"data": [{"id": ...}]}`) filtered with
+    /// [`discovery::is_speech_model`].
+    ///
+    /// # Errors
+    /// - If the body is not a model list
+    fn parse_model_list(&self, body: &str) -> anyhow::Result<Vec<String>> {
+        Ok(discovery::parse_openai_model_list(body)?
+            .into_iter()
+            .filter(|name| discovery::is_speech_model(name))
+            .collect())
+    }
+
+    /// Returns the WebSocket request for a live transcription session.
+    ///
+    /// Audio is sent as mono 16-bit little-endian PCM at `sample_rate`. Returns `None` for
+    /// providers without live recognition.
+    fn live_request(
+        &self,
+        _config: &TranscriptionConfig,
+        _sample_rate: u32,
+    ) -> Option<LiveRequest> {
+        None
+    }
+
+    /// Parses a text message received during a live session.
+    ///
+    /// Returns `Ok(None)` for messages that carry no transcript (metadata, session events).
+    ///
+    /// # Errors
+    /// - If the message cannot be parsed or reports an error
+    fn parse_live_message(&self, _message: &str) -> anyhow::Result<Option<LiveUpdate>> {
+        Ok(None)
+    }
+
+    /// Returns the message that asks the provider to flush its results and close the session.
+    fn live_close_message(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Returns the message that keeps an idle live session open (e.g. while paused).
+    fn live_keepalive_message(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Transcribes an audio file.
+    ///
+    /// # Errors
+    /// - If the audio file cannot be read
+    /// - If the API request fails or returns an HTTP error
+    /// - If the API response cannot be parsed
+    async fn transcribe(
+        &self,
+        config: &TranscriptionConfig,
+        audio_path: &Path,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let client = self.http_client(config)?;
+        let audio = self.upload(&client, config, audio_path).await?;
+        let request = self.build_request(&client, config, audio)?;
+        let body = self.send(config, request).await?;
+        self.parse_response(config, &body)
+    }
+}
+
+/// Returns every provider that ships with ostt, in display order.
+pub(crate) fn builtin_providers() -> Vec<Arc<dyn Provider>> {
+    vec![
+        Arc::new(openai::OpenAi),
+        Arc::new(deepgram::Deepgram),
+        Arc::new(deepinfra::DeepInfra),
+        Arc::new(groq::Groq),
+        Arc::new(assemblyai::AssemblyAi),
+        Arc::new(berget::Berget),
+        Arc::new(elevenlabs::ElevenLabs),
+        Arc::new(mistral::Mistral),
+        Arc::new(speechmatics::Speechmatics),
+        Arc::new(gladia::Gladia),
+        Arc::new(azure::Azure),
+        Arc::new(custom::Custom),
+        #[cfg(feature = "local-whisper")]
+        Arc::new(local::Local),
+    ]
+}
+
+/// Returns an HTTP client builder with the `[network]` settings applied.
+///
+/// # Errors
+/// - If the proxy URL is invalid
+/// - If the CA bundle cannot be read or contains no valid certificate
+pub fn http_client_builder(network: &NetworkConfig) -> anyhow::Result<reqwest::ClientBuilder> {
+    let user_agent = network
+        .user_agent
+        .clone()
+        .unwrap_or_else(|| format!("ostt/{}", env!("CARGO_PKG_VERSION")));
+    let mut builder = reqwest::Client::builder().user_agent(user_agent);
+
+    if let Some(proxy_url) = &network.proxy {
+        let proxy = reqwest::Proxy::all(proxy_url)
+            .map_err(|e| anyhow::anyhow!("Invalid [network] proxy '{proxy_url}': {e}"))?
+            .no_proxy(reqwest::NoProxy::from_string(&network.no_proxy.join(",")));
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(path) = &network.ca_bundle {
+        let pem = std::fs::read(path).map_err(|e| {
+            anyhow::anyhow!("Failed to read [network] ca_bundle {}: {e}", path.display())
+        })?;
+        let certificates = reqwest::Certificate::from_pem_bundle(&pem)
+            .map_err(|e| anyhow::anyhow!("Invalid [network] ca_bundle {}: {e}", path.display()))?;
+        if certificates.is_empty() {
+            return Err(anyhow::anyhow!(
+                "[network] ca_bundle {} contains no certificates",
+                path.display()
+            ));
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    if let Some(seconds) = network.connect_timeout_secs {
+        builder = builder.connect_timeout(std::time::Duration::from_secs(seconds));
+    }
+    if let Some(seconds) = network.request_timeout_secs {
+        builder = builder.timeout(std::time::Duration::from_secs(seconds));
+    }
+    Ok(builder)
+}
+
+/// Returns the API base URL of the configured model's provider.
+///
+/// The `OSTT_<PROVIDER>_BASE_URL` environment variable (e.g. `OSTT_OPENAI_BASE_URL`) takes
+/// precedence over `[providers.base_urls]` in ostt.toml; without either, `default` is used.
+/// Live transcription WebSocket endpoints are not affected.
+pub fn base_url(config: &TranscriptionConfig, default: &str) -> String {
+    let provider_id = config.model.provider().id();
+    let from_env = std::env::var(base_url_env_var(provider_id)).ok();
+    let configured = config.providers.base_urls.get(provider_id);
+    resolve_base_url(from_env.as_deref(), configured.map(String::as_str), default)
+}
+
+/// Returns the environment variable that overrides a provider's base URL.
+pub fn base_url_env_var(provider_id: &str) -> String {
+    format!("OSTT_{}_BASE_URL", provider_id.to_uppercase())
+}
+
+fn resolve_base_url(from_env: Option<&str>, configured: Option<&str>, default: &str) -> String {
+    [from_env, configured]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|url| !url.is_empty())
+        .unwrap_or(default)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Formats HTTP error codes into human-readable messages.
+pub fn default_status_message(
+    provider_name: &str,
+    status: reqwest::StatusCode,
+    body: &str,
+) -> String {
+    if error::is_quota_exhausted(status, body) {
+        return format!("Your {provider_name} account is out of credits or quota. Check your plan and billing, or run 'ostt auth' to use another provider.");
+    }
+    if error::is_unsupported_format(status, body) {
+        return format!("{provider_name} cannot decode this audio file. Convert it to MP3, WAV or FLAC and try again.");
+    }
+    match status.as_u16() {
+        401 => format!("{provider_name} API key is invalid or expired. Please run 'ostt auth' to update your API key."),
+        403 => format!("You don't have permission to use {provider_name}'s API. Check your API key and account status."),
+        413 => format!("The audio file is too large for {provider_name}. Record a shorter clip or use a provider with a higher upload limit."),
+        422 => format!("{provider_name} API validation error: {body}"),
+        429 => format!("Too many requests to {provider_name}. You've hit the API rate limit. Please wait and try again."),
+        500 | 502 | 503 | 504 => format!("{provider_name} API server is experiencing issues. Please try again later."),
+        _ => format!("{provider_name} API error (status {status}): {body}"),
+    }
+}
+
+/// Formats request failures (connection, timeout, ...) into human-readable messages.
+pub fn default_send_error_message(provider_name: &str, error: &reqwest::Error) -> String {
+    if error.is_connect() {
+        format!("Failed to connect to {provider_name} API server. Check your internet connection.")
+    } else if error.is_timeout() {
+        format!("Request to {provider_name} timed out. The API server is not responding.")
+    } else if error.is_builder() {
+        format!("Failed to build {provider_name} API request: {error}. This may be a configuration error.")
+    } else {
+        format!("{provider_name} network error: {error}")
+    }
 }
 
 /// Transcribes an audio file using the configured transcription model.
 ///
-/// This function routes the request to the appropriate provider-specific implementation
-/// based on the configured model. The caller doesn't need to know which provider is being used.
+/// This function routes the request to the provider registered for the configured model.
+/// The caller doesn't need to know which provider is being used. Files over the provider's
+/// upload limits are split and transcribed in chunks.
 ///
 /// # Errors
 /// - If the audio file cannot be read from disk
 /// - If the API request fails due to network issues (connection, timeout)
 /// - If the API returns an HTTP error (401 for invalid key, 429 for rate limit, etc.)
 /// - If the API response cannot be parsed
-pub async fn transcribe(config: &TranscriptionConfig, audio_path: &Path) -> anyhow::Result<String> {
-    tracing::info!(
-        "Transcribing with {} ({})",
-        config.model.provider().name(),
-        config.model.id()
-    );
-
-    let result = match config.model.provider() {
-        TranscriptionProvider::OpenAI => openai::transcribe(config, audio_path).await,
-        TranscriptionProvider::Deepgram => deepgram::transcribe(config, audio_path).await,
-        TranscriptionProvider::DeepInfra => deepinfra::transcribe(config, audio_path).await,
-        TranscriptionProvider::Groq => groq::transcribe(config, audio_path).await,
-        TranscriptionProvider::AssemblyAI => assemblyai::transcribe(config, audio_path).await,
-        TranscriptionProvider::Berget => berget::transcribe(config, audio_path).await,
-        TranscriptionProvider::ElevenLabs => elevenlabs::transcribe(config, audio_path).await,
-    }?;
-
-    Ok(result)
+pub async fn transcribe(
+    config: &TranscriptionConfig,
+    audio_path: &Path,
+) -> anyhow::Result<TranscriptionResponse> {
+    chunking::transcribe(registry::registry(), config, audio_path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_url_prefers_environment_then_config() {
+        let default = "https://api.example.com/v1";
+        assert_eq!(resolve_base_url(None, None, default), default);
+        assert_eq!(
+            resolve_base_url(None, Some("http://localhost:9000/v1/"), default),
+            "http://localhost:9000/v1"
+        );
+        assert_eq!(
+            resolve_base_url(
+                Some("http://proxy/v1"),
+                Some("http://localhost:9000/v1"),
+                default
+            ),
+            "http://proxy/v1"
+        );
+        assert_eq!(resolve_base_url(Some(" "), None, default), default);
+        assert_eq!(base_url_env_var("deepinfra"), "OSTT_DEEPINFRA_BASE_URL");
+    }
+
+    #[test]
+    fn requests_are_checked_against_model_capabilities() {
+        let capabilities = ModelCapabilities {
+            containers: OPENAI_CONTAINERS,
+            languages: &["sv", "en"],
+            ..Default::default()
+        };
+        let mut config = TranscriptionConfig::new(
+            TranscriptionModel::from_id("whisper").unwrap(),
+            String::new(),
+            Vec::new(),
+            ProvidersConfig::default(),
+        );
+        assert!(capabilities.check(&config, Path::new("talk.MP3")).is_ok());
+        assert!(capabilities.check(&config, Path::new("talk")).is_ok());
+
+        let err = capabilities
+            .check(&config, Path::new("talk.aiff"))
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<TranscriptionError>(),
+            Some(TranscriptionError::UnsupportedFormat { .. })
+        ));
+
+        config.language = Some("de".to_string());
+        let err = capabilities
+            .check(&config, Path::new("talk.mp3"))
+            .unwrap_err();
+        assert!(err.to_string().contains("does not transcribe 'de'"));
+        config.language = Some("SV".to_string());
+        assert!(capabilities.check(&config, Path::new("talk.mp3")).is_ok());
+
+        config.translate = true;
+        let err = capabilities
+            .check(&config, Path::new("talk.mp3"))
+            .unwrap_err();
+        assert!(err.to_string().contains("cannot translate"));
+    }
+
+    #[test]
+    fn invalid_network_settings_are_reported() {
+        let network = NetworkConfig {
+            proxy: Some("not a url".to_string()),
+            ..Default::default()
+        };
+        let err = http_client_builder(&network).unwrap_err();
+        assert!(err.to_string().contains("Invalid [network] proxy"));
+
+        let network = NetworkConfig {
+            ca_bundle: Some("/nonexistent/ostt-ca.pem".into()),
+            ..Default::default()
+        };
+        let err = http_client_builder(&network).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Failed to read [network] ca_bundle"));
+
+        let network = NetworkConfig {
+            proxy: Some("http://proxy.example:3128".to_string()),
+            no_proxy: vec!["localhost".to_string()],
+            connect_timeout_secs: Some(5),
+            request_timeout_secs: Some(30),
+            user_agent: Some("ostt-test".to_string()),
+            ..Default::default()
+        };
+        assert!(http_client_builder(&network).unwrap().build().is_ok());
+    }
 }
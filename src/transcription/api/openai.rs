@@ -2,54 +2,178 @@
 //!
 //! Handles transcription requests to OpenAI's Whisper API using multipart form data.
 
-use serde::Deserialize;
-use std::path::Path;
+use async_trait::async_trait;
 
-use super::TranscriptionConfig;
+use super::verbose_json::{self, VerboseJsonResponse};
+use super::{
+    base_url, AudioUpload, KeywordSupport, ModelCapabilities, Provider, ProviderCapabilities,
+    TranscriptionConfig, TranscriptionResponse, OPENAI_CONTAINERS,
+};
+use crate::transcription::{TranscriptionModel, TranscriptionProvider};
 
-/// OpenAI API response wrapper
-#[derive(Debug, Deserialize)]
-struct OpenAiResponse {
-    text: String,
-}
+const PROVIDER: TranscriptionProvider = TranscriptionProvider::new("openai", "OpenAI");
+
+const BASE_URL: &str = "https://api.openai.com/v1";
+
+const TRANSCRIPTION_PATH: &str = "/audio/transcriptions";
+
+/// Transcription endpoint asking for plain `json`, the only format of the GPT-4o models
+const JSON_TRANSCRIPTION_PATH: &str = "/audio/transcriptions?response_format=json";
+
+/// Path of the endpoint that lists the models available to the account
+const MODELS_PATH: &str = "/models";
+
+/// Path of the endpoint that translates speech to English (Whisper only)
+const TRANSLATION_PATH: &str = "/audio/translations";
+
+/// Maximum upload size accepted by the transcriptions endpoint
+const MAX_UPLOAD_BYTES: u64 = 25 * 1024 * 1024;
+
+/// Maximum audio duration accepted by the GPT-4o transcription models
+const MAX_DURATION_SECS: u64 = 1400;
+
+/// Only `whisper-1` supports `verbose_json`; the GPT-4o models return plain `json`
+const VERBOSE_JSON_MODEL: &str = "whisper-1";
+
+/// The only model that ignores the `prompt` parameter
+const NO_PROMPT_MODEL: &str = "gpt-4o-transcribe";
 
-/// Transcribes an audio file using OpenAI's Whisper API.
+/// OpenAI transcription provider.
 ///
 /// Uses multipart form data with bearer token authentication.
 ///
 /// Keywords are passed as the `prompt` parameter to guide transcription context.
 /// OpenAI's Whisper API uses the prompt to improve accuracy for domain-specific terms.
-pub async fn transcribe(config: &TranscriptionConfig, audio_path: &Path) -> anyhow::Result<String> {
-    let audio_data =
-        std::fs::read(audio_path).map_err(|e| anyhow::anyhow!("Failed to read audio file: {e}"))?;
+pub struct OpenAi;
 
-    let client = reqwest::Client::new();
+#[async_trait]
+impl Provider for OpenAi {
+    fn info(&self) -> TranscriptionProvider {
+        PROVIDER
+    }
+
+    fn models(&self) -> Vec<TranscriptionModel> {
+        vec![
+            TranscriptionModel::new(
+                PROVIDER,
+                "gpt-4o-transcribe",
+                "gpt-4o-transcribe",
+                "GPT-4o Transcribe (latest, best accuracy)",
+            ),
+            TranscriptionModel::new(
+                PROVIDER,
+                "gpt-4o-mini-transcribe",
+                "gpt-4o-mini-transcribe",
+                "GPT-4o Mini Transcribe (faster, lighter)",
+            ),
+            TranscriptionModel::new(PROVIDER, "whisper", "whisper-1", "Whisper (legacy)"),
+        ]
+    }
 
-    let file_name = audio_path
-        .file_name()
-        .unwrap_or_default()
-        .to_string_lossy()
-        .to_string();
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            max_upload_bytes: Some(MAX_UPLOAD_BYTES),
+            max_duration_secs: Some(MAX_DURATION_SECS),
+            containers: OPENAI_CONTAINERS,
+            ..Default::default()
+        }
+    }
 
-    let file_part = reqwest::multipart::Part::bytes(audio_data)
-        .file_name(file_name.clone())
-        .mime_str("audio/mpeg")
-        .map_err(|e| anyhow::anyhow!("Failed to create file part for upload: {e}"))?;
+    fn supports_translation(&self, model: &TranscriptionModel) -> bool {
+        model.api_model_name() == VERBOSE_JSON_MODEL
+    }
 
+    /// Whisper returns timings and has no duration limit; GPT-4o Transcribe takes no prompt.
+    fn model_capabilities(&self, model: &TranscriptionModel) -> ModelCapabilities {
+        let whisper = model.api_model_name() == VERBOSE_JSON_MODEL;
+        let keywords = if model.api_model_name() == NO_PROMPT_MODEL {
+            KeywordSupport::None
+        } else {
+            KeywordSupport::Prompt
+        };
+        ModelCapabilities {
+            max_duration_secs: (!whisper).then_some(MAX_DURATION_SECS),
+            timestamps: whisper,
+            keywords,
+            ..ModelCapabilities::from_provider(self.capabilities(), whisper)
+        }
+    }
+
+    fn model_list_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+    ) -> Option<reqwest::RequestBuilder> {
+        let endpoint = format!("{}{MODELS_PATH}", base_url(config, BASE_URL));
+        Some(client.get(endpoint).bearer_auth(&config.api_key))
+    }
+
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let (form, path, debug_params) = transcription_form(config, audio)?;
+        let url = format!("{}{path}", base_url(config, BASE_URL));
+
+        tracing::debug!(
+            "OpenAI API Call:\n  URL: {}\n  Method: POST\n  Headers:\n    Authorization: Bearer <redacted>\n    Content-Type: multipart/form-data\n  Body parameters: {}",
+            url,
+            debug_params.join("\n    ")
+        );
+
+        Ok(client
+            .post(url)
+            .bearer_auth(&config.api_key)
+            .multipart(form))
+    }
+
+    fn parse_response(
+        &self,
+        _config: &TranscriptionConfig,
+        body: &str,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let transcription: VerboseJsonResponse = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse OpenAI response: {e}"))?;
+
+        // Debug log: Log the full response for debugging
+        tracing::debug!(
+            "OpenAI API Response:\n  Status: Success\n  Transcription length: {} characters\n  Full response: {:#?}",
+            transcription.text_len(),
+            transcription
+        );
+
+        Ok(transcription.into())
+    }
+}
+
+/// Builds the multipart form of a transcription (or, with `--translate`, translation)
+/// request for an OpenAI model.
+///
+/// Returns the form, the endpoint path to append to the base URL and the body parameters
+/// for the debug log. Shared with Azure OpenAI, which only differs in URL and auth.
+pub(super) fn transcription_form(
+    config: &TranscriptionConfig,
+    audio: AudioUpload,
+) -> anyhow::Result<(reqwest::multipart::Form, &'static str, Vec<String>)> {
     let mut form = reqwest::multipart::Form::new()
-        .part("file", file_part)
+        .part("file", audio.into_file_part()?)
         .text("model", config.model.api_model_name().to_string());
 
     // Debug log: Log the API call details (without the audio data)
     let mut debug_params = vec![format!("model={}", config.model.api_model_name())];
 
+    // Translations are always English, so the endpoint takes no language
+    if let Some(language) = config.language.as_ref().filter(|_| !config.translate) {
+        form = form.text("language", language.clone());
+        debug_params.push(format!("language={language}"));
+    }
+
     // Add keywords as prompt for better transcription context
     // Note: gpt-4o-transcribe doesn't support prompt parameter, only whisper-1 and gpt-4o-mini-transcribe do
     if !config.keywords.is_empty() {
-        let should_use_prompt = match config.model.api_model_name() {
-            "gpt-4o-transcribe" => false, // gpt-4o-transcribe doesn't support prompt
-            _ => true,                    // whisper-1 and gpt-4o-mini-transcribe support it
-        };
+        let should_use_prompt = config.model.api_model_name() != NO_PROMPT_MODEL;
 
         if should_use_prompt {
             let prompt = config.keywords.join(", ");
@@ -68,70 +192,19 @@ pub async fn transcribe(config: &TranscriptionConfig, audio_path: &Path) -> anyh
         }
     }
 
-    let endpoint = config.model.endpoint();
-    let url = format!("{endpoint}?response_format=json");
-    debug_params.push("response_format=json".to_string());
-
-    tracing::debug!(
-        "OpenAI API Call:\n  URL: {}\n  Method: POST\n  Headers:\n    Authorization: Bearer <redacted>\n    Content-Type: multipart/form-data\n  Body parameters: {}",
-        url,
-        debug_params.join("\n    ")
-    );
-
-    let response = match client
-        .post(&url)
-        .bearer_auth(&config.api_key)
-        .multipart(form)
-        .send()
-        .await
-    {
-        Ok(resp) => resp,
-        Err(e) => {
-            let error_msg = if e.is_connect() {
-                "Failed to connect to OpenAI API server. Check your internet connection."
-                    .to_string()
-            } else if e.is_timeout() {
-                "Request to OpenAI timed out. The API server is not responding.".to_string()
-            } else if e.to_string().contains("builder") {
-                format!(
-                    "Failed to build OpenAI API request: {e}. This may be a configuration error."
-                )
-            } else {
-                format!("OpenAI network error: {e}")
-            };
-            return Err(anyhow::anyhow!(error_msg));
-        }
+    let path = if config.translate {
+        // Translations return segments but not word timings
+        form = form.text("response_format", "verbose_json");
+        debug_params.push("response_format=verbose_json".to_string());
+        TRANSLATION_PATH
+    } else if config.model.api_model_name() == VERBOSE_JSON_MODEL {
+        form = verbose_json::add_form_fields(form);
+        debug_params.push("response_format=verbose_json".to_string());
+        TRANSCRIPTION_PATH
+    } else {
+        debug_params.push("response_format=json".to_string());
+        JSON_TRANSCRIPTION_PATH
     };
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-
-        let human_readable = match status.as_u16() {
-            401 => "OpenAI API key is invalid or expired. Please run 'ostt auth' to update your API key.".to_string(),
-            403 => "You don't have permission to use OpenAI's API. Check your API key and account status.".to_string(),
-            429 => "Too many requests to OpenAI. You've hit the API rate limit. Please wait and try again.".to_string(),
-            500 | 502 | 503 | 504 => "OpenAI API server is experiencing issues. Please try again later.".to_string(),
-            _ => format!("OpenAI API error (status {status}): {error_body}"),
-        };
-
-        return Err(anyhow::anyhow!(human_readable));
-    }
-
-    let transcription: OpenAiResponse = response
-        .json()
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to parse OpenAI response: {e}"))?;
-
-    // Debug log: Log the full response for debugging
-    tracing::debug!(
-        "OpenAI API Response:\n  Status: Success\n  Transcription length: {} characters\n  Full response: {:#?}",
-        transcription.text.len(),
-        transcription
-    );
-
-    Ok(transcription.text.trim().to_string())
+    Ok((form, path, debug_params))
 }
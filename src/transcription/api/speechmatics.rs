@@ -0,0 +1,437 @@
+//! Speechmatics batch API implementation.
+//!
+//! Handles transcription requests to Speechmatics' batch jobs API using a submit→poll→fetch
+//! pattern:
+//! 1. Submit a job with the audio file and a JSON job config in one multipart request
+//! 2. Poll the job until it is done
+//! 3. Fetch the transcript in `json-v2` format
+//!
+//! The default endpoint is Speechmatics' global one; EU and US accounts can point
+//! `[providers.base_urls] speechmatics` at their regional endpoint.
+
+use std::path::Path;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    base_url, AudioUpload, KeywordSupport, Provider, ProviderCapabilities, TranscriptWord,
+    TranscriptionConfig, TranscriptionResponse,
+};
+use crate::transcription::diarization::SpeakerNumbers;
+use crate::transcription::error::TranscriptionError;
+use crate::transcription::{TranscriptionModel, TranscriptionProvider};
+
+const PROVIDER: TranscriptionProvider = TranscriptionProvider::new("speechmatics", "Speechmatics");
+
+const BASE_URL: &str = "https://asr.api.speechmatics.com/v2";
+
+/// Maximum number of poll attempts before timing out (10 minutes at 3-second intervals)
+const MAX_POLL_ATTEMPTS: u32 = 200;
+
+/// Polling interval in seconds
+const POLL_INTERVAL_SECS: u64 = 3;
+
+/// Language sent when none is requested or configured (automatic language identification)
+const AUTO_LANGUAGE: &str = "auto";
+
+/// Label Speechmatics gives words it could not attribute to a speaker
+const UNKNOWN_SPEAKER: &str = "UU";
+
+/// Job config sent with the audio
+#[derive(Debug, Serialize)]
+struct JobConfig {
+    #[serde(rename = "type")]
+    job_type: &'static str,
+    transcription_config: JobTranscriptionConfig,
+}
+
+/// The `transcription_config` section of a job config
+#[derive(Debug, Serialize)]
+struct JobTranscriptionConfig {
+    language: String,
+    operating_point: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    additional_vocab: Vec<VocabEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diarization: Option<&'static str>,
+}
+
+/// A word or phrase of the custom dictionary
+#[derive(Debug, Serialize)]
+struct VocabEntry {
+    content: String,
+}
+
+/// Response of the job submission endpoint
+#[derive(Debug, Deserialize)]
+struct SubmitResponse {
+    id: String,
+}
+
+/// Response of the job status endpoint
+#[derive(Debug, Deserialize)]
+struct JobStatusResponse {
+    job: JobStatus,
+}
+
+#[derive(Debug, Deserialize)]
+struct JobStatus {
+    id: String,
+    /// `running`, `done`, `rejected`, `deleted` or `expired`
+    status: String,
+    #[serde(default)]
+    errors: Vec<JobError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JobError {
+    message: String,
+}
+
+/// A transcript in `json-v2` format
+#[derive(Debug, Deserialize)]
+struct TranscriptResponse {
+    #[serde(default)]
+    results: Vec<TranscriptResult>,
+    metadata: Option<TranscriptMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptMetadata {
+    transcription_config: Option<TranscriptConfigMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptConfigMetadata {
+    language: Option<String>,
+}
+
+/// A word or punctuation mark of the transcript
+#[derive(Debug, Deserialize)]
+struct TranscriptResult {
+    /// `word`, `punctuation` or `entity`
+    #[serde(rename = "type")]
+    result_type: String,
+    start_time: f64,
+    end_time: f64,
+    /// Whether punctuation attaches to the `previous` or `next` word, or `both` or `none`
+    attaches_to: Option<String>,
+    #[serde(default)]
+    alternatives: Vec<Alternative>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Alternative {
+    content: String,
+    confidence: Option<f64>,
+    language: Option<String>,
+    /// Speaker label (`S1`, `S2`, ...) when diarization is enabled
+    speaker: Option<String>,
+}
+
+/// Speechmatics batch transcription provider.
+///
+/// Submits a multipart job with bearer token authentication, polls it at 3-second
+/// intervals for up to 10 minutes and fetches the finished transcript.
+///
+/// Keywords are passed as `additional_vocab` entries. The operating point is the model's
+/// API name, so it is chosen by selecting `speechmatics-enhanced` or `speechmatics-standard`,
+/// and the language defaults to automatic identification, overridable in
+/// `[providers.speechmatics]`.
+pub struct Speechmatics;
+
+#[async_trait]
+impl Provider for Speechmatics {
+    fn info(&self) -> TranscriptionProvider {
+        PROVIDER
+    }
+
+    fn models(&self) -> Vec<TranscriptionModel> {
+        vec![
+            TranscriptionModel::new(
+                PROVIDER,
+                "speechmatics-enhanced",
+                "enhanced",
+                "Enhanced operating point (best accuracy)",
+            ),
+            TranscriptionModel::new(
+                PROVIDER,
+                "speechmatics-standard",
+                "standard",
+                "Standard operating point (faster, lower cost)",
+            ),
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            keywords: KeywordSupport::Keyterms,
+            diarization: true,
+            timestamps: true,
+            ..Default::default()
+        }
+    }
+
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        config: &TranscriptionConfig,
+        audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let job_config = serde_json::to_string(&job_config(config))?;
+        let form = reqwest::multipart::Form::new()
+            .text("config", job_config.clone())
+            .part("data_file", audio.into_file_part()?);
+
+        let endpoint = format!("{}/jobs/", base_url(config, BASE_URL));
+        tracing::debug!(
+            "Speechmatics API Call:\n  URL: {}\n  Method: POST\n  Headers:\n    Authorization: Bearer <redacted>\n    Content-Type: multipart/form-data\n  Job config: {}",
+            endpoint,
+            job_config
+        );
+
+        Ok(client
+            .post(&endpoint)
+            .bearer_auth(&config.api_key)
+            .multipart(form))
+    }
+
+    /// Parses a `json-v2` transcript, joining words and punctuation into the text.
+    fn parse_response(
+        &self,
+        _config: &TranscriptionConfig,
+        body: &str,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let transcript: TranscriptResponse = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse Speechmatics transcript: {e}"))?;
+
+        let mut text = String::new();
+        let mut attach_next = true;
+        let mut words = Vec::new();
+        let mut language = None;
+        let mut speakers = SpeakerNumbers::default();
+        for result in transcript.results {
+            let Some(best) = result.alternatives.into_iter().next() else {
+                continue;
+            };
+            let attaches_to = result.attaches_to.as_deref().unwrap_or("none");
+            let is_punctuation = result.result_type == "punctuation";
+            let attach_previous = is_punctuation && matches!(attaches_to, "previous" | "both");
+            if !attach_next && !attach_previous {
+                text.push(' ');
+            }
+            text.push_str(&best.content);
+            attach_next = is_punctuation && matches!(attaches_to, "next" | "both");
+
+            if is_punctuation {
+                continue;
+            }
+            if language.is_none() {
+                language = best.language.clone();
+            }
+            words.push(TranscriptWord {
+                text: best.content,
+                start: result.start_time,
+                end: result.end_time,
+                confidence: best.confidence,
+                speaker: best
+                    .speaker
+                    .filter(|label| label != UNKNOWN_SPEAKER)
+                    .map(|label| speakers.number(&label)),
+            });
+        }
+
+        // Without per-word languages, report the requested language unless it was "auto"
+        let language = language.or_else(|| {
+            transcript
+                .metadata
+                .and_then(|metadata| metadata.transcription_config)
+                .and_then(|config| config.language)
+                .filter(|language| language != AUTO_LANGUAGE)
+        });
+
+        Ok(TranscriptionResponse {
+            text,
+            segments: Vec::new(),
+            words,
+            language,
+        })
+    }
+
+    async fn transcribe(
+        &self,
+        config: &TranscriptionConfig,
+        audio_path: &Path,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let client = self.http_client(config)?;
+        let jobs_url = format!("{}/jobs", base_url(config, BASE_URL));
+
+        // Step 1: Submit the job with the audio
+        tracing::debug!("Submitting Speechmatics job...");
+        let audio = self.upload(&client, config, audio_path).await?;
+        let request = self.build_request(&client, config, audio)?;
+        let body = self.send(config, request).await?;
+        let job: SubmitResponse = serde_json::from_str(&body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse Speechmatics submit response: {e}"))?;
+        tracing::debug!("Speechmatics job submitted, id: {}", job.id);
+
+        // Step 2: Poll until the job is done
+        let status_url = format!("{jobs_url}/{}", job.id);
+        let mut attempts: u32 = 0;
+        loop {
+            attempts += 1;
+            if attempts > MAX_POLL_ATTEMPTS {
+                return Err(TranscriptionError::poll_timeout(
+                    PROVIDER.name(),
+                    Duration::from_secs(MAX_POLL_ATTEMPTS as u64 * POLL_INTERVAL_SECS),
+                )
+                .into());
+            }
+
+            let poll_request = client.get(&status_url).bearer_auth(&config.api_key);
+            let body = self.send(config, poll_request).await?;
+            let status: JobStatusResponse = serde_json::from_str(&body).map_err(|e| {
+                anyhow::anyhow!("Failed to parse Speechmatics job status response: {e}")
+            })?;
+
+            tracing::debug!(
+                "Poll attempt {}/{}: status={}, id={}",
+                attempts,
+                MAX_POLL_ATTEMPTS,
+                status.job.status,
+                status.job.id
+            );
+
+            match status.job.status.as_str() {
+                "done" => break,
+                "running" => tokio::time::sleep(Duration::from_secs(POLL_INTERVAL_SECS)).await,
+                other => {
+                    let errors: Vec<String> =
+                        status.job.errors.into_iter().map(|e| e.message).collect();
+                    let reason = if errors.is_empty() {
+                        format!("job {other}")
+                    } else {
+                        errors.join("; ")
+                    };
+                    return Err(TranscriptionError::JobFailed {
+                        message: format!("Speechmatics transcription failed: {reason}"),
+                    }
+                    .into());
+                }
+            }
+        }
+
+        // Step 3: Fetch the transcript
+        let transcript_request = client
+            .get(format!("{status_url}/transcript"))
+            .query(&[("format", "json-v2")])
+            .bearer_auth(&config.api_key);
+        let body = self.send(config, transcript_request).await?;
+        self.parse_response(config, &body)
+    }
+}
+
+/// Returns the job config for a transcription with `config`.
+///
+/// The requested language takes precedence over `[providers.speechmatics].language`. The
+/// operating point is the model's API name, so history and usage name the operating point
+/// that produced the transcript.
+fn job_config(config: &TranscriptionConfig) -> JobConfig {
+    let language = config
+        .language
+        .as_ref()
+        .or(config.providers.speechmatics.language.as_ref())
+        .filter(|language| !language.is_empty())
+        .cloned()
+        .unwrap_or_else(|| AUTO_LANGUAGE.to_string());
+
+    JobConfig {
+        job_type: "transcription",
+        transcription_config: JobTranscriptionConfig {
+            language,
+            operating_point: config.model.api_model_name().to_string(),
+            additional_vocab: config
+                .keywords
+                .iter()
+                .map(|keyword| VocabEntry {
+                    content: keyword.clone(),
+                })
+                .collect(),
+            diarization: config.diarize.then_some("speaker"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProvidersConfig;
+
+    fn config() -> TranscriptionConfig {
+        TranscriptionConfig::new(
+            Speechmatics.models().remove(0),
+            String::new(),
+            vec!["ostt".to_string(), "Speechmatics".to_string()],
+            ProvidersConfig::default(),
+        )
+    }
+
+    #[test]
+    fn job_config_carries_vocabulary_language_and_operating_point() {
+        let mut config = config();
+        let job = serde_json::to_value(job_config(&config)).unwrap();
+        assert_eq!(
+            job,
+            serde_json::json!({
+                "type": "transcription",
+                "transcription_config": {
+                    "language": "auto",
+                    "operating_point": "enhanced",
+                    "additional_vocab": [{"content": "ostt"}, {"content": "Speechmatics"}],
+                }
+            })
+        );
+
+        config.providers.speechmatics.language = Some("de".to_string());
+        config.diarize = true;
+        let job = job_config(&config).transcription_config;
+        assert_eq!(job.language, "de");
+        assert_eq!(job.diarization, Some("speaker"));
+
+        // The selected model decides the operating point
+        config.model = Speechmatics.models().remove(1);
+        assert_eq!(
+            job_config(&config).transcription_config.operating_point,
+            "standard"
+        );
+
+        // A requested language wins over the configured one
+        config.language = Some("sv".to_string());
+        assert_eq!(job_config(&config).transcription_config.language, "sv");
+    }
+
+    #[test]
+    fn transcript_joins_words_and_punctuation() {
+        let body = r#"{"format":"2.9","metadata":{"transcription_config":{"language":"auto"}},
+            "results":[
+              {"type":"word","start_time":0.1,"end_time":0.5,
+               "alternatives":[{"content":"Hello","confidence":0.98,"language":"en","speaker":"S1"}]},
+              {"type":"punctuation","start_time":0.5,"end_time":0.5,"attaches_to":"previous",
+               "alternatives":[{"content":",","confidence":1.0,"language":"en","speaker":"S1"}]},
+              {"type":"word","start_time":0.6,"end_time":1.0,
+               "alternatives":[{"content":"world","confidence":0.91,"language":"en","speaker":"S2"}]},
+              {"type":"punctuation","start_time":1.0,"end_time":1.0,"attaches_to":"previous",
+               "alternatives":[{"content":".","confidence":1.0,"language":"en","speaker":"S2"}]}
+            ]}"#;
+
+        let response = Speechmatics.parse_response(&config(), body).unwrap();
+        assert_eq!(response.text, "Hello, world.");
+        assert_eq!(response.language.as_deref(), Some("en"));
+        let words: Vec<&str> = response.words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(words, ["Hello", "world"]);
+        assert_eq!(response.words[1].start, 0.6);
+        assert_eq!(response.words[1].speaker, Some(2));
+    }
+}
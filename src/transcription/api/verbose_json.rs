@@ -0,0 +1,124 @@
+//! OpenAI-compatible `verbose_json` responses.
+//!
+//! Whisper-style APIs (OpenAI `whisper-1`, Groq) return segment and word timestamps when
+//! asked for `response_format=verbose_json` with `timestamp_granularities[]`. This module
+//! holds the shared request fields and response parsing.
+
+use serde::Deserialize;
+
+use super::{TranscriptSegment, TranscriptWord, TranscriptionResponse};
+
+/// Timestamp granularities requested alongside `verbose_json`
+const TIMESTAMP_GRANULARITIES: [&str; 2] = ["segment", "word"];
+
+/// Adds the form fields that request a `verbose_json` response with word and segment timings.
+pub(super) fn add_form_fields(mut form: reqwest::multipart::Form) -> reqwest::multipart::Form {
+    form = form.text("response_format", "verbose_json");
+    for granularity in TIMESTAMP_GRANULARITIES {
+        form = form.text("timestamp_granularities[]", granularity);
+    }
+    form
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct VerboseJsonResponse {
+    text: String,
+    #[serde(default)]
+    segments: Vec<VerboseJsonSegment>,
+    #[serde(default)]
+    words: Vec<VerboseJsonWord>,
+    /// Detected language, e.g. `english` (only in `verbose_json` responses)
+    language: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseJsonSegment {
+    text: String,
+    start: f64,
+    end: f64,
+    /// Average log probability of the segment's tokens
+    avg_logprob: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseJsonWord {
+    word: String,
+    start: f64,
+    end: f64,
+}
+
+impl VerboseJsonResponse {
+    /// Returns the length of the transcribed text, for debug logging.
+    pub(super) fn text_len(&self) -> usize {
+        self.text.len()
+    }
+}
+
+impl From<VerboseJsonResponse> for TranscriptionResponse {
+    fn from(response: VerboseJsonResponse) -> Self {
+        let segments = response
+            .segments
+            .into_iter()
+            .map(|segment| TranscriptSegment {
+                text: segment.text.trim().to_string(),
+                start: segment.start,
+                end: segment.end,
+                confidence: segment.avg_logprob.map(f64::exp),
+                speaker: None,
+            })
+            .collect();
+        let words = response
+            .words
+            .into_iter()
+            .map(|word| TranscriptWord {
+                text: word.word.trim().to_string(),
+                start: word.start,
+                end: word.end,
+                confidence: None,
+                speaker: None,
+            })
+            .collect();
+
+        TranscriptionResponse {
+            text: response.text,
+            segments,
+            words,
+            language: response.language,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbose_json_maps_segments_and_words() {
+        let body = r#"{
+            "task": "transcribe", "language": "english", "duration": 2.1,
+            "text": "Hello world.",
+            "segments": [{"id": 0, "start": 0.0, "end": 2.1, "text": " Hello world.",
+                          "avg_logprob": 0.0, "no_speech_prob": 0.01}],
+            "words": [{"word": "Hello", "start": 0.1, "end": 0.5},
+                      {"word": "world", "start": 0.6, "end": 1.0}]
+        }"#;
+        let parsed: VerboseJsonResponse = serde_json::from_str(body).unwrap();
+        let response = TranscriptionResponse::from(parsed);
+
+        assert_eq!(response.text, "Hello world.");
+        assert_eq!(response.language.as_deref(), Some("english"));
+        assert_eq!(response.segments.len(), 1);
+        assert_eq!(response.segments[0].text, "Hello world.");
+        assert_eq!(response.segments[0].confidence, Some(1.0));
+        assert_eq!(response.words[1].text, "world");
+        assert_eq!(response.words[1].start, 0.6);
+        assert_eq!(response.words[1].confidence, None);
+    }
+
+    #[test]
+    fn plain_json_has_no_timings() {
+        let parsed: VerboseJsonResponse = serde_json::from_str(r#"{"text":"hi"}"#).unwrap();
+        let response = TranscriptionResponse::from(parsed);
+        assert_eq!(response, TranscriptionResponse::new("hi"));
+    }
+}
@@ -0,0 +1,237 @@
+//! Cache of finished transcriptions.
+//!
+//! Sending the same recording to the same model with the same settings gives the same
+//! transcript, so results are stored in the history database keyed by a SHA-256 hash of the
+//! audio content, the model, the language, the keywords, the provider's options and its
+//! endpoint when a base URL override points it at another server. A later
+//! `ostt retry` or `ostt transcribe` of that audio returns the stored transcript instead of
+//! paying for another request.
+
+use std::io::Read;
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use super::api::{base_url, TranscriptionConfig, TranscriptionResponse};
+use super::fallback;
+use super::registry::ProviderRegistry;
+use crate::history::HistoryManager;
+
+/// Bumped when the key layout changes, so entries with the old layout stop matching
+const KEY_VERSION: u32 = 2;
+
+/// A transcription returned by [`transcribe`].
+#[derive(Debug)]
+pub struct CachedTranscription<'a> {
+    /// The transcript
+    pub response: TranscriptionResponse,
+    /// Configuration that produced the transcript
+    pub config: &'a TranscriptionConfig,
+    /// Whether the transcript came from the cache instead of the provider
+    pub cached: bool,
+}
+
+/// Returns the SHA-256 hash of an audio file's content as lowercase hex.
+///
+/// # Errors
+/// - If the file cannot be read
+pub fn hash_audio(audio_path: &Path) -> anyhow::Result<String> {
+    let mut file = std::fs::File::open(audio_path)
+        .map_err(|e| anyhow::anyhow!("Failed to open {}: {e}", audio_path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {e}", audio_path.display()))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Returns the cache key for transcribing audio with hash `audio_hash` using `config`.
+///
+/// Only the options section of the model's own provider is included, so changing the
+/// settings of another provider keeps existing entries valid. The endpoint is the base URL
+/// override from `[providers.base_urls]` or `OSTT_<PROVIDER>_BASE_URL`, and empty for the
+/// provider's default server.
+pub fn cache_key(config: &TranscriptionConfig, audio_hash: &str) -> String {
+    let provider_id = config.model.provider().id();
+    let options = serde_json::to_value(&config.providers)
+        .ok()
+        .and_then(|providers| providers.get(provider_id).cloned())
+        .unwrap_or_default();
+    let settings = serde_json::json!({
+        "version": KEY_VERSION,
+        "audio": audio_hash,
+        "provider": provider_id,
+        "model": config.model.id(),
+        "api_model": config.model.api_model_name(),
+        "language": config.language,
+        "translate": config.translate,
+        "diarize": config.diarize,
+        "keywords": config.keywords,
+        "options": options,
+        "endpoint": base_url(config, ""),
+    });
+    format!("{:x}", Sha256::digest(settings.to_string().as_bytes()))
+}
+
+/// Transcribes with the fallback chain, returning a cached transcript when there is one.
+///
+/// Only the first model of the chain is looked up; a fallback model is only tried after
+/// the first one failed. Fresh transcripts are always stored, also when `lookup` is false,
+/// so `--no-cache` refreshes the entry. Cache errors are logged and never fail the
+/// transcription.
+///
+/// # Errors
+/// - If the transcription fails (see [`fallback::transcribe`])
+pub async fn transcribe<'a>(
+    registry: &ProviderRegistry,
+    chain: &'a [TranscriptionConfig],
+    audio_path: &Path,
+    history: &mut HistoryManager,
+    lookup: bool,
+    on_attempt: impl FnMut(&TranscriptionConfig),
+) -> anyhow::Result<CachedTranscription<'a>> {
+    let path = audio_path.to_path_buf();
+    let audio_hash = tokio::task::spawn_blocking(move || hash_audio(&path))
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result)
+        .inspect_err(|e| tracing::warn!("Transcription cache disabled: {e:#}"))
+        .ok();
+
+    if let (true, Some(audio_hash), Some(primary)) = (lookup, &audio_hash, chain.first()) {
+        match history.get_cached_response(&cache_key(primary, audio_hash)) {
+            Ok(Some(response)) => {
+                tracing::info!("Using cached transcription from {}", primary.model.id());
+                return Ok(CachedTranscription {
+                    response,
+                    config: primary,
+                    cached: true,
+                });
+            }
+            Ok(None) => tracing::debug!("No cached transcription for {}", primary.model.id()),
+            Err(e) => tracing::warn!("Failed to read transcription cache: {e}"),
+        }
+    }
+
+    let (response, config) = fallback::transcribe(registry, chain, audio_path, on_attempt).await?;
+    if let Some(audio_hash) = &audio_hash {
+        if let Err(e) = history.save_cached_response(
+            &cache_key(config, audio_hash),
+            config.model.id(),
+            &response,
+        ) {
+            tracing::warn!("Failed to cache transcription: {e}");
+        }
+    }
+    Ok(CachedTranscription {
+        response,
+        config,
+        cached: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProvidersConfig;
+    use crate::transcription::test_support::StubProvider;
+    use crate::transcription::TranscriptionModel;
+    use std::path::PathBuf;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ostt_test_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn config(model_id: &str) -> TranscriptionConfig {
+        TranscriptionConfig::new(
+            TranscriptionModel::from_id(model_id).unwrap(),
+            String::new(),
+            vec!["ostt".to_string()],
+            ProvidersConfig::default(),
+        )
+    }
+
+    #[test]
+    fn key_changes_with_every_setting() {
+        let base = config("nova-3");
+        let key = cache_key(&base, "abc");
+        assert_eq!(key, cache_key(&base.clone(), "abc"));
+        assert_ne!(key, cache_key(&base, "abd"));
+        assert_ne!(key, cache_key(&config("nova-2"), "abc"));
+
+        let mut changed = base.clone();
+        changed.language = Some("sv".to_string());
+        assert_ne!(key, cache_key(&changed, "abc"));
+
+        let mut changed = base.clone();
+        changed.keywords.push("whisper".to_string());
+        assert_ne!(key, cache_key(&changed, "abc"));
+
+        let mut changed = base.clone();
+        changed.providers.deepgram.smart_format = !changed.providers.deepgram.smart_format;
+        assert_ne!(key, cache_key(&changed, "abc"));
+
+        // A different server may run a different model under the same name
+        let mut changed = base.clone();
+        changed
+            .providers
+            .base_urls
+            .insert("deepgram".to_string(), "http://localhost:8080".to_string());
+        assert_ne!(key, cache_key(&changed, "abc"));
+
+        // Options of other providers do not affect the key
+        let mut unrelated = base.clone();
+        unrelated.providers.custom.model = Some("other".to_string());
+        assert_eq!(key, cache_key(&unrelated, "abc"));
+    }
+
+    #[tokio::test]
+    async fn repeated_transcriptions_are_served_from_the_cache() {
+        let dir = temp_dir("cache_transcribe");
+        let audio = dir.join("audio.mp3");
+        std::fs::write(&audio, b"audio").unwrap();
+        let counting = StubProvider::replying("counting", |count| {
+            Ok(TranscriptionResponse::new(format!("request {count}")))
+        });
+        let requests = counting.requests();
+        let chain = [counting.config()];
+        let mut registry = ProviderRegistry::new();
+        registry.register(Arc::new(counting));
+        let mut history = HistoryManager::new(&dir).unwrap();
+
+        let first = transcribe(&registry, &chain, &audio, &mut history, true, |_| {})
+            .await
+            .unwrap();
+        assert!(!first.cached);
+        let second = transcribe(&registry, &chain, &audio, &mut history, true, |_| {})
+            .await
+            .unwrap();
+        assert!(second.cached);
+        assert_eq!(second.response.text, "request 1");
+
+        // Skipping the lookup sends a new request and refreshes the entry
+        let refreshed = transcribe(&registry, &chain, &audio, &mut history, false, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(refreshed.response.text, "request 2");
+        let cached = transcribe(&registry, &chain, &audio, &mut history, true, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(cached.response.text, "request 2");
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
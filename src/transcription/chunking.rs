@@ -0,0 +1,391 @@
+//! Chunked transcription of long audio files.
+//!
+//! Providers such as OpenAI and Groq reject uploads over 25 MB, and the GPT-4o models also
+//! limit the audio duration. Files over a provider's limits are split at silences with
+//! ffmpeg, the chunks are transcribed concurrently, and the results are stitched back
+//! together with segment and word timings shifted by each chunk's offset.
+//!
+//! Speaker numbers from diarization are assigned per chunk, so the same person may get a
+//! different number in different chunks.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::Context;
+use futures_util::future;
+use tokio::sync::Semaphore;
+
+use super::api::{ModelCapabilities, TranscriptionConfig, TranscriptionResponse};
+use super::error::TranscriptionError;
+use super::registry::ProviderRegistry;
+use crate::recording::ffmpeg::{self, Silence};
+
+/// Upper bound for a chunk's length, in seconds, so long files are transcribed in parallel
+const MAX_CHUNK_SECS: f64 = 600.0;
+
+/// Number of chunks transcribed at the same time
+const MAX_PARALLEL_CHUNKS: usize = 4;
+
+/// Size of the encoded chunks (32 kbit/s MP3), in bytes per second
+const CHUNK_BYTES_PER_SEC: f64 = 4000.0;
+
+/// Share of a provider limit a chunk may use, leaving headroom for encoding overhead
+const LIMIT_HEADROOM: f64 = 0.9;
+
+/// Volume below which audio counts as silence, in dB
+const SILENCE_NOISE_DB: i32 = -30;
+
+/// Shortest pause considered a safe place to cut, in seconds
+const MIN_SILENCE_SECS: f64 = 0.4;
+
+/// Transcribes an audio file, splitting it first if it exceeds the provider's limits.
+///
+/// # Errors
+/// - If the file is over the limits but cannot be split (e.g. ffmpeg is missing)
+/// - If any chunk fails to transcribe
+pub async fn transcribe(
+    registry: &ProviderRegistry,
+    config: &TranscriptionConfig,
+    audio_path: &Path,
+) -> anyhow::Result<TranscriptionResponse> {
+    let capabilities = registry
+        .get(config.model.provider().id())
+        .map(|provider| provider.model_capabilities(&config.model));
+    let duration = match capabilities {
+        Some(capabilities) => chunking_duration(&capabilities, audio_path).await?,
+        None => None,
+    };
+
+    let (Some(capabilities), Some(duration)) = (capabilities, duration) else {
+        return registry.transcribe(config, audio_path).await;
+    };
+
+    let max_chunk = max_chunk_secs(&capabilities);
+    let path = audio_path.to_path_buf();
+    let silences = tokio::task::spawn_blocking(move || {
+        ffmpeg::detect_silences(&path, SILENCE_NOISE_DB, MIN_SILENCE_SECS)
+    })
+    .await?
+    .map_err(split_error)?;
+    let chunks = plan_chunks(duration, &silences, max_chunk);
+    tracing::info!(
+        "Splitting {:.0}s of audio into {} chunks for {}",
+        duration,
+        chunks.len(),
+        config.model.provider().name()
+    );
+
+    let chunk_dir = ChunkDir::create()?;
+    transcribe_chunks(registry, config, audio_path, chunk_dir.path(), &chunks).await
+}
+
+/// Temporary directory holding the chunks of one transcription, removed when dropped.
+///
+/// Every call gets its own directory, since `ostt compare` splits the same file for
+/// several models at the same time.
+struct ChunkDir(PathBuf);
+
+impl ChunkDir {
+    /// Creates a directory named after the process and a per-process counter.
+    fn create() -> anyhow::Result<Self> {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "ostt_chunks_{}_{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| anyhow::anyhow!("Failed to create chunk directory: {e}"))?;
+        Ok(Self(dir))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ChunkDir {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.0) {
+            tracing::debug!("Failed to remove chunk directory: {}", e);
+        }
+    }
+}
+
+/// Returns the audio duration if the file must be split, or `None` if it fits in one request.
+async fn chunking_duration(
+    capabilities: &ModelCapabilities,
+    audio_path: &Path,
+) -> anyhow::Result<Option<f64>> {
+    if capabilities.max_upload_bytes.is_none() && capabilities.max_duration_secs.is_none() {
+        return Ok(None);
+    }
+
+    let size = std::fs::metadata(audio_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read audio file: {e}"))?
+        .len();
+    let too_large = capabilities.max_upload_bytes.is_some_and(|max| size > max);
+
+    let path = audio_path.to_path_buf();
+    let duration = match tokio::task::spawn_blocking(move || ffmpeg::probe_duration(&path)).await? {
+        Ok(duration) => duration,
+        Err(e) if too_large => {
+            return Err(TranscriptionError::PayloadTooLarge {
+                message: format!(
+                    "The audio file is {:.1} MB, over the provider's upload limit, and could not be split: {e}",
+                    size as f64 / 1_048_576.0
+                ),
+            }
+            .into());
+        }
+        Err(e) => {
+            tracing::debug!("Could not determine audio duration, sending as is: {}", e);
+            return Ok(None);
+        }
+    };
+
+    let too_long = capabilities
+        .max_duration_secs
+        .is_some_and(|max| duration > max as f64);
+    Ok((too_large || too_long).then_some(duration))
+}
+
+/// Classifies a failure to split audio that is over the provider's limits.
+///
+/// The provider would reject the file as a whole, so this is reported like an upload
+/// that is too large rather than as a generic failure.
+fn split_error(error: anyhow::Error) -> anyhow::Error {
+    TranscriptionError::PayloadTooLarge {
+        message: format!(
+            "The audio is over the provider's limits and could not be split: {error:#}"
+        ),
+    }
+    .into()
+}
+
+/// Returns the longest chunk that stays within the provider's limits.
+fn max_chunk_secs(capabilities: &ModelCapabilities) -> f64 {
+    let mut max_chunk = MAX_CHUNK_SECS;
+    if let Some(max_bytes) = capabilities.max_upload_bytes {
+        max_chunk = max_chunk.min(max_bytes as f64 * LIMIT_HEADROOM / CHUNK_BYTES_PER_SEC);
+    }
+    if let Some(max_secs) = capabilities.max_duration_secs {
+        max_chunk = max_chunk.min(max_secs as f64 * LIMIT_HEADROOM);
+    }
+    max_chunk
+}
+
+/// Splits `duration` seconds into `(start, end)` ranges no longer than `max_chunk`.
+///
+/// Each cut is placed in the middle of the latest silence in the second half of the chunk,
+/// or at the chunk limit if there is no such silence.
+fn plan_chunks(duration: f64, silences: &[Silence], max_chunk: f64) -> Vec<(f64, f64)> {
+    let mut chunks = Vec::new();
+    let mut start = 0.0;
+    while duration - start > max_chunk {
+        let limit = start + max_chunk;
+        let earliest = start + max_chunk / 2.0;
+        let cut = silences
+            .iter()
+            .map(Silence::midpoint)
+            .rev()
+            .find(|midpoint| *midpoint > earliest && *midpoint <= limit)
+            .unwrap_or(limit);
+        chunks.push((start, cut));
+        start = cut;
+    }
+    chunks.push((start, duration));
+    chunks
+}
+
+/// Extracts the planned chunks and transcribes them with a bounded number of parallel requests.
+async fn transcribe_chunks(
+    registry: &ProviderRegistry,
+    config: &TranscriptionConfig,
+    audio_path: &Path,
+    chunk_dir: &Path,
+    chunks: &[(f64, f64)],
+) -> anyhow::Result<TranscriptionResponse> {
+    let input = audio_path.to_path_buf();
+    let dir = chunk_dir.to_path_buf();
+    let ranges = chunks.to_vec();
+    let paths: Vec<PathBuf> = tokio::task::spawn_blocking(move || {
+        ranges
+            .iter()
+            .enumerate()
+            .map(|(index, (start, end))| {
+                let path = dir.join(format!("chunk_{index:03}.mp3"));
+                ffmpeg::extract_segment(&input, *start, end - start, &path)?;
+                Ok(path)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+    })
+    .await?
+    .map_err(split_error)?;
+
+    let total = paths.len();
+    let permits = Semaphore::new(MAX_PARALLEL_CHUNKS);
+    let requests = paths
+        .iter()
+        .enumerate()
+        .map(|(index, path)| transcribe_chunk(registry, config, &permits, path, index, total));
+    let responses = future::join_all(requests)
+        .await
+        .into_iter()
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let offsets = chunks.iter().map(|(start, _)| *start);
+    Ok(stitch(offsets.zip(responses).collect(), config.diarize))
+}
+
+/// Transcribes a single chunk once a request slot is free.
+async fn transcribe_chunk(
+    registry: &ProviderRegistry,
+    config: &TranscriptionConfig,
+    permits: &Semaphore,
+    path: &Path,
+    index: usize,
+    total: usize,
+) -> anyhow::Result<TranscriptionResponse> {
+    let _permit = permits.acquire().await?;
+    tracing::debug!("Transcribing chunk {}/{}", index + 1, total);
+    registry
+        .transcribe(config, path)
+        .await
+        .with_context(|| format!("Transcription of part {} of {total} failed", index + 1))
+}
+
+/// Joins chunk transcriptions in order, shifting their timings by the chunk offsets.
+///
+/// Diarized transcripts are joined with line breaks to keep one speaker turn per line.
+fn stitch(parts: Vec<(f64, TranscriptionResponse)>, diarized: bool) -> TranscriptionResponse {
+    let separator = if diarized { "\n" } else { " " };
+    let mut stitched = TranscriptionResponse::default();
+    let mut texts = Vec::new();
+
+    for (offset, response) in parts {
+        if stitched.language.is_none() {
+            stitched.language = response.language;
+        }
+        let text = response.text.trim();
+        if !text.is_empty() {
+            texts.push(text.to_string());
+        }
+        stitched
+            .segments
+            .extend(response.segments.into_iter().map(|mut segment| {
+                segment.start += offset;
+                segment.end += offset;
+                segment
+            }));
+        stitched
+            .words
+            .extend(response.words.into_iter().map(|mut word| {
+                word.start += offset;
+                word.end += offset;
+                word
+            }));
+    }
+
+    stitched.text = texts.join(separator);
+    stitched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcription::TranscriptWord;
+
+    #[test]
+    fn short_audio_is_a_single_chunk() {
+        assert_eq!(plan_chunks(120.0, &[], 600.0), vec![(0.0, 120.0)]);
+    }
+
+    #[test]
+    fn chunks_are_cut_in_late_silences() {
+        let silences = [
+            Silence {
+                start: 100.0,
+                end: 101.0,
+            },
+            Silence {
+                start: 500.0,
+                end: 502.0,
+            },
+            Silence {
+                start: 900.0,
+                end: 900.5,
+            },
+        ];
+        let chunks = plan_chunks(1300.0, &silences, 600.0);
+        // The silence at 100s is too early, so the first cut falls in the one at 500s
+        assert_eq!(
+            chunks,
+            vec![(0.0, 501.0), (501.0, 900.25), (900.25, 1300.0)]
+        );
+    }
+
+    #[test]
+    fn chunks_without_silence_are_cut_at_the_limit() {
+        assert_eq!(
+            plan_chunks(1000.0, &[], 400.0),
+            vec![(0.0, 400.0), (400.0, 800.0), (800.0, 1000.0)]
+        );
+    }
+
+    #[test]
+    fn chunk_length_respects_provider_limits() {
+        let capabilities = ModelCapabilities {
+            max_upload_bytes: Some(1_000_000),
+            max_duration_secs: Some(1400),
+            ..Default::default()
+        };
+        assert_eq!(max_chunk_secs(&capabilities), 225.0);
+        assert_eq!(
+            max_chunk_secs(&ModelCapabilities::default()),
+            MAX_CHUNK_SECS
+        );
+    }
+
+    #[test]
+    fn each_transcription_gets_its_own_chunk_directory() {
+        let first = ChunkDir::create().unwrap();
+        let second = ChunkDir::create().unwrap();
+        assert_ne!(first.path(), second.path());
+
+        let path = first.path().to_path_buf();
+        std::fs::write(path.join("chunk_000.mp3"), b"audio").unwrap();
+        drop(first);
+        assert!(!path.exists());
+        assert!(second.path().exists());
+    }
+
+    #[test]
+    fn stitching_offsets_timings() {
+        let word = |text: &str, start: f64| TranscriptWord {
+            text: text.to_string(),
+            start,
+            end: start + 0.5,
+            confidence: None,
+            speaker: None,
+        };
+        let first = TranscriptionResponse {
+            text: "Hello.".to_string(),
+            segments: Vec::new(),
+            words: vec![word("Hello.", 1.0)],
+            language: Some("en".to_string()),
+        };
+        let second = TranscriptionResponse {
+            text: " World. ".to_string(),
+            segments: Vec::new(),
+            words: vec![word("World.", 2.0)],
+            language: Some("en".to_string()),
+        };
+
+        let stitched = stitch(vec![(0.0, first), (600.0, second)], false);
+        assert_eq!(stitched.text, "Hello. World.");
+        assert_eq!(stitched.words[1].start, 602.0);
+        assert_eq!(stitched.words[1].end, 602.5);
+        assert_eq!(stitched.language.as_deref(), Some("en"));
+    }
+}
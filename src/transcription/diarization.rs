@@ -0,0 +1,160 @@
+//! Speaker diarization helpers.
+//!
+//! Providers label speakers in different ways (`A`/`B`, `0`/`1`, `speaker_0`). They map their
+//! labels to numbers starting at 1 with [`SpeakerNumbers`], so every provider produces the same
+//! `Speaker 1`, `Speaker 2`, ... turns. [`apply_speaker_turns`] then rewrites the transcript
+//! text as one line per speaker turn.
+
+use super::api::{TranscriptSegment, TranscriptWord, TranscriptionResponse};
+
+/// Assigns speaker numbers in order of first appearance.
+#[derive(Debug, Default)]
+pub struct SpeakerNumbers {
+    labels: Vec<String>,
+}
+
+impl SpeakerNumbers {
+    /// Returns the number for a provider speaker label, starting at 1.
+    pub fn number(&mut self, label: &str) -> u32 {
+        let index = match self.labels.iter().position(|l| l == label) {
+            Some(index) => index,
+            None => {
+                self.labels.push(label.to_string());
+                self.labels.len() - 1
+            }
+        };
+        index as u32 + 1
+    }
+}
+
+/// Rewrites the transcript as speaker turns if the provider labeled speakers.
+///
+/// Providers that only label words get segments built from runs of words by the same
+/// speaker. Responses without any speaker labels are left unchanged.
+pub fn apply_speaker_turns(response: &mut TranscriptionResponse) {
+    if !response.segments.iter().any(|s| s.speaker.is_some()) {
+        let segments = segments_from_words(&response.words);
+        if segments.is_empty() {
+            tracing::debug!("No speaker labels in response, keeping plain transcript");
+            return;
+        }
+        response.segments = segments;
+    }
+    response.text = speaker_turns(&response.segments);
+}
+
+/// Groups consecutive words by the same speaker into segments.
+///
+/// Returns no segments if none of the words carry a speaker label.
+fn segments_from_words(words: &[TranscriptWord]) -> Vec<TranscriptSegment> {
+    if !words.iter().any(|w| w.speaker.is_some()) {
+        return Vec::new();
+    }
+
+    let mut segments: Vec<TranscriptSegment> = Vec::new();
+    for word in words {
+        match segments.last_mut() {
+            Some(segment) if segment.speaker == word.speaker => {
+                segment.text.push(' ');
+                segment.text.push_str(&word.text);
+                segment.end = word.end;
+            }
+            _ => segments.push(TranscriptSegment {
+                text: word.text.clone(),
+                start: word.start,
+                end: word.end,
+                confidence: None,
+                speaker: word.speaker,
+            }),
+        }
+    }
+    segments
+}
+
+/// Renders segments as `Speaker N: ...` lines, merging consecutive segments by one speaker.
+fn speaker_turns(segments: &[TranscriptSegment]) -> String {
+    let mut turns: Vec<(Option<u32>, String)> = Vec::new();
+    for segment in segments {
+        let text = segment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        match turns.last_mut() {
+            Some((speaker, turn)) if *speaker == segment.speaker => {
+                turn.push(' ');
+                turn.push_str(text);
+            }
+            _ => turns.push((segment.speaker, text.to_string())),
+        }
+    }
+
+    turns
+        .into_iter()
+        .map(|(speaker, text)| match speaker {
+            Some(number) => format!("Speaker {number}: {text}"),
+            None => text,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, speaker: Option<u32>) -> TranscriptWord {
+        TranscriptWord {
+            text: text.to_string(),
+            start: 0.0,
+            end: 0.0,
+            confidence: None,
+            speaker,
+        }
+    }
+
+    #[test]
+    fn speaker_numbers_follow_first_appearance() {
+        let mut numbers = SpeakerNumbers::default();
+        assert_eq!(numbers.number("B"), 1);
+        assert_eq!(numbers.number("A"), 2);
+        assert_eq!(numbers.number("B"), 1);
+    }
+
+    #[test]
+    fn words_are_grouped_into_speaker_turns() {
+        let mut response = TranscriptionResponse {
+            text: "Hi there. Hello. How are you?".to_string(),
+            segments: Vec::new(),
+            words: vec![
+                word("Hi", Some(1)),
+                word("there.", Some(1)),
+                word("Hello.", Some(2)),
+                word("How", Some(1)),
+                word("are", Some(1)),
+                word("you?", Some(1)),
+            ],
+            language: None,
+        };
+
+        apply_speaker_turns(&mut response);
+        assert_eq!(
+            response.text,
+            "Speaker 1: Hi there.\nSpeaker 2: Hello.\nSpeaker 1: How are you?"
+        );
+        assert_eq!(response.segments.len(), 3);
+        assert_eq!(response.segments[1].speaker, Some(2));
+    }
+
+    #[test]
+    fn responses_without_speakers_are_unchanged() {
+        let mut response = TranscriptionResponse {
+            text: "plain".to_string(),
+            segments: Vec::new(),
+            words: vec![word("plain", None)],
+            language: None,
+        };
+        apply_speaker_turns(&mut response);
+        assert_eq!(response.text, "plain");
+        assert!(response.segments.is_empty());
+    }
+}
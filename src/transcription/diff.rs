@@ -0,0 +1,178 @@
+//! Word-level comparison of transcripts.
+//!
+//! Transcripts are compared word by word after normalization, so differences in case and
+//! punctuation do not count. Matching words are found with a longest common subsequence,
+//! computed in linear space; every other word is reported as different.
+
+/// Splits a transcript into words for display and comparison.
+pub fn words(text: &str) -> Vec<&str> {
+    text.split_whitespace().collect()
+}
+
+/// Returns, for each word of `other`, whether it also appears in `reference` at the same
+/// place in the word order.
+///
+/// Uses Hirschberg's algorithm, which needs memory linear in the transcript lengths, so
+/// hour-long transcripts from chunked files can be compared.
+pub fn matching_words(reference: &[&str], other: &[&str]) -> Vec<bool> {
+    let reference: Vec<String> = reference.iter().map(|w| normalize(w)).collect();
+    let other_normalized: Vec<String> = other.iter().map(|w| normalize(w)).collect();
+    let reference: Vec<&str> = reference.iter().map(String::as_str).collect();
+    let other_normalized: Vec<&str> = other_normalized.iter().map(String::as_str).collect();
+
+    let mut matches = vec![false; other_normalized.len()];
+    mark_common(&reference, &other_normalized, &mut matches);
+    matches
+}
+
+/// Marks the words of `other` that belong to a longest common subsequence with
+/// `reference`. `matches` has one entry per word of `other`.
+fn mark_common(reference: &[&str], other: &[&str], matches: &mut [bool]) {
+    // Shared leading and trailing words always match and shrink the problem
+    let prefix = reference
+        .iter()
+        .zip(other)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = reference[prefix..]
+        .iter()
+        .rev()
+        .zip(other[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let other_end = other.len() - suffix;
+    matches[..prefix].fill(true);
+    matches[other_end..].fill(true);
+
+    let reference = &reference[prefix..reference.len() - suffix];
+    let other = &other[prefix..other_end];
+    let matches = &mut matches[prefix..other_end];
+    if reference.is_empty() || other.is_empty() {
+        return;
+    }
+    if let [word] = reference {
+        if let Some(j) = other.iter().position(|w| w == word) {
+            matches[j] = true;
+        }
+        return;
+    }
+
+    // Split the reference in half and find where the best alignment crosses the middle
+    let middle = reference.len() / 2;
+    let forward = lcs_lengths(&reference[..middle], other);
+    let reference_tail: Vec<&str> = reference[middle..].iter().rev().copied().collect();
+    let other_reversed: Vec<&str> = other.iter().rev().copied().collect();
+    let backward = lcs_lengths(&reference_tail, &other_reversed);
+    let split = (0..=other.len())
+        .max_by_key(|&j| (forward[j] + backward[other.len() - j], std::cmp::Reverse(j)))
+        .unwrap_or(0);
+
+    let (before, after) = matches.split_at_mut(split);
+    mark_common(&reference[..middle], &other[..split], before);
+    mark_common(&reference[middle..], &other[split..], after);
+}
+
+/// Returns the LCS length of `reference` and each prefix `other[..j]`, for `j` in
+/// `0..=other.len()`, keeping a single row of the table.
+fn lcs_lengths(reference: &[&str], other: &[&str]) -> Vec<u32> {
+    let mut row = vec![0u32; other.len() + 1];
+    for word in reference {
+        let mut diagonal = 0;
+        for (j, other_word) in other.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if word == other_word {
+                diagonal + 1
+            } else {
+                above.max(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row
+}
+
+/// Returns the share of words the two transcripts have in common, from 0.0 to 1.0.
+///
+/// Words are counted against the longer transcript, so both missing and extra words
+/// lower the score.
+pub fn similarity(reference: &[&str], other: &[&str]) -> f64 {
+    let longest = reference.len().max(other.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    let common = matching_words(reference, other)
+        .into_iter()
+        .filter(|matched| *matched)
+        .count();
+    common as f64 / longest as f64
+}
+
+/// Lowercases a word and strips surrounding punctuation.
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_and_punctuation_are_ignored() {
+        let reference = words("Hello, world.");
+        let other = words("hello world");
+        assert_eq!(matching_words(&reference, &other), [true, true]);
+        assert_eq!(similarity(&reference, &other), 1.0);
+    }
+
+    #[test]
+    fn substitutions_and_insertions_are_marked() {
+        let reference = words("the quick brown fox jumps");
+        let other = words("the quick brawn fox really jumps");
+        assert_eq!(
+            matching_words(&reference, &other),
+            [true, true, false, true, false, true]
+        );
+        assert_eq!(similarity(&reference, &other), 4.0 / 6.0);
+    }
+
+    #[test]
+    fn long_transcripts_match_every_shared_word() {
+        // Every seventh word differs, spread over the whole transcript
+        let reference: Vec<String> = (0..3000).map(|i| format!("w{i}")).collect();
+        let other: Vec<String> = (0..3000)
+            .map(|i| {
+                if i % 7 == 3 {
+                    format!("x{i}")
+                } else {
+                    format!("w{i}")
+                }
+            })
+            .collect();
+        let reference: Vec<&str> = reference.iter().map(String::as_str).collect();
+        let other: Vec<&str> = other.iter().map(String::as_str).collect();
+
+        let matches = matching_words(&reference, &other);
+        assert!(matches
+            .iter()
+            .enumerate()
+            .all(|(i, matched)| *matched == (i % 7 != 3)));
+    }
+
+    #[test]
+    fn reordered_words_match_a_longest_subsequence() {
+        let reference = words("a b c d e f");
+        let other = words("b a d c f e");
+        let common = matching_words(&reference, &other)
+            .into_iter()
+            .filter(|matched| *matched)
+            .count();
+        assert_eq!(common, 3);
+    }
+
+    #[test]
+    fn empty_transcripts_are_identical() {
+        assert_eq!(similarity(&[], &[]), 1.0);
+        assert_eq!(similarity(&words("hello"), &[]), 0.0);
+    }
+}
@@ -0,0 +1,398 @@
+//! Discovery of provider models that ostt does not ship with.
+//!
+//! Providers with an OpenAI-style models endpoint (OpenAI, Groq, DeepInfra and custom
+//! OpenAI-compatible servers) can list the speech models available to the account. `ostt auth`
+//! fetches those lists and keeps them in `~/.local/share/ostt/discovered_models.json` for a
+//! day, and every model that is not built in is offered next to the built-in models.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::api::{http_client_builder, Provider, TranscriptionConfig};
+use super::model::TranscriptionModel;
+use super::registry::{registry, ProviderRegistry};
+use crate::config::file::{NetworkConfig, ProvidersConfig};
+
+/// How long a fetched model list is used before `ostt auth` fetches it again
+pub const CACHE_TTL: chrono::TimeDelta = chrono::TimeDelta::hours(24);
+
+/// Timeout for a model list request when `[network]` sets no request timeout
+const LIST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Parts of a model name that mark a speech-to-text model in a full model list
+const SPEECH_MODEL_MARKERS: &[&str] = &["whisper", "transcribe"];
+
+/// The model lists fetched from each provider, keyed by provider id.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DiscoveryCache {
+    #[serde(default)]
+    pub providers: BTreeMap<String, DiscoveredModels>,
+}
+
+/// The speech models listed by one provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveredModels {
+    /// When the list was fetched
+    pub fetched_at: DateTime<Utc>,
+    /// API model names as returned by the provider
+    pub models: Vec<String>,
+}
+
+impl DiscoveryCache {
+    /// Reads the cache from `path`; a missing file is an empty cache.
+    ///
+    /// # Errors
+    /// - If the file cannot be read or parsed
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| anyhow::anyhow!("Failed to parse {}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(anyhow::anyhow!("Failed to read {}: {e}", path.display())),
+        }
+    }
+
+    /// Writes the cache to `path`, creating its directory if needed.
+    ///
+    /// # Errors
+    /// - If the file cannot be written
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .map_err(|e| anyhow::anyhow!("Failed to write {}: {e}", path.display()))
+    }
+
+    /// Returns true if the list of `provider_id` is missing or older than [`CACHE_TTL`].
+    pub fn is_stale(&self, provider_id: &str, now: DateTime<Utc>) -> bool {
+        self.providers
+            .get(provider_id)
+            .is_none_or(|entry| now - entry.fetched_at > CACHE_TTL)
+    }
+
+    /// Returns the discovered models of every provider in `registry`, skipping the ones
+    /// that are built in.
+    pub fn models(&self, registry: &ProviderRegistry) -> Vec<TranscriptionModel> {
+        let builtin = registry.models();
+        let mut models: Vec<TranscriptionModel> = Vec::new();
+        for provider in registry.providers() {
+            let Some(entry) = self.providers.get(provider.info().id()) else {
+                continue;
+            };
+            for name in &entry.models {
+                let model = discovered_model(provider.as_ref(), name);
+                let known = builtin.iter().chain(&models).any(|m| {
+                    m.id() == model.id()
+                        || (m.provider() == model.provider()
+                            && m.api_model_name() == model.api_model_name())
+                });
+                if !known {
+                    models.push(model);
+                }
+            }
+        }
+        models
+    }
+}
+
+/// Returns the path of the discovery cache (~/.local/share/ostt/discovered_models.json).
+///
+/// # Errors
+/// - If the home directory cannot be determined
+pub fn cache_path() -> anyhow::Result<PathBuf> {
+    let home =
+        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+    Ok(home
+        .join(".local")
+        .join("share")
+        .join("ostt")
+        .join("discovered_models.json"))
+}
+
+/// Returns the discovered models from the cache.
+///
+/// Never fails: a cache that cannot be read is logged and treated as empty.
+pub fn cached_models() -> Vec<TranscriptionModel> {
+    match cache_path().and_then(|path| DiscoveryCache::load(&path)) {
+        Ok(cache) => cache.models(registry()),
+        Err(e) => {
+            tracing::warn!("Ignoring discovered models: {e}");
+            Vec::new()
+        }
+    }
+}
+
+/// Creates the model for an API model name listed by `provider`.
+///
+/// The id is the provider id followed by the last path segment of the name, e.g.
+/// `deepinfra-whisper-large-v3-turbo` for `openai/whisper-large-v3-turbo`.
+pub fn discovered_model(provider: &dyn Provider, api_model_name: &str) -> TranscriptionModel {
+    let info = provider.info();
+    let short_name = api_model_name
+        .rsplit('/')
+        .next()
+        .unwrap_or(api_model_name)
+        .to_lowercase();
+    TranscriptionModel::new(
+        info,
+        format!("{}-{short_name}", info.id()),
+        api_model_name.to_string(),
+        format!("{api_model_name} (discovered)"),
+    )
+}
+
+/// Parses an OpenAI-style model list (`{"data": [{"id": ...}]}`) into model names.
+///
+/// # Errors
+/// - If the body is not a model list
+pub fn parse_openai_model_list(body: &str) -> anyhow::Result<Vec<String>> {
+    #[derive(Deserialize)]
+    struct ModelList {
+        data: Vec<ModelEntry>,
+    }
+    #[derive(Deserialize)]
+    struct ModelEntry {
+        id: String,
+    }
+
+    let list: ModelList = serde_json::from_str(body)
+        .map_err(|e| anyhow::anyhow!("Failed to parse model list: {e}"))?;
+    Ok(list.data.into_iter().map(|entry| entry.id).collect())
+}
+
+/// Returns true if a model name from a full model list looks like a speech-to-text model.
+pub fn is_speech_model(name: &str) -> bool {
+    let name = name.to_lowercase();
+    SPEECH_MODEL_MARKERS
+        .iter()
+        .any(|marker| name.contains(marker))
+}
+
+/// Fetches the speech models that `provider` lists for an account.
+///
+/// Returns `Ok(None)` if the provider cannot list its models.
+///
+/// # Errors
+/// - If the request fails or the provider returns an error status
+/// - If the response is not a model list
+pub async fn fetch(
+    provider: &dyn Provider,
+    api_key: String,
+    providers: &ProvidersConfig,
+    network: &NetworkConfig,
+) -> anyhow::Result<Option<Vec<String>>> {
+    let Some(config) = list_config(provider, api_key, providers, network) else {
+        return Ok(None);
+    };
+    let mut builder = http_client_builder(network)?;
+    if network.request_timeout_secs.is_none() {
+        builder = builder.timeout(LIST_TIMEOUT);
+    }
+    let client = builder
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {e}"))?;
+
+    let Some(request) = provider.model_list_request(&client, &config) else {
+        return Ok(None);
+    };
+    let body = provider.send(&config, request).await?;
+    provider.parse_model_list(&body).map(Some)
+}
+
+/// Returns the providers whose cached model list is stale and that can be asked for one,
+/// i.e. that have an API key saved or do not need one.
+pub fn stale_providers(
+    providers: &ProvidersConfig,
+    network: &NetworkConfig,
+) -> Vec<Arc<dyn Provider>> {
+    let cache = cached_or_empty();
+    let now = Utc::now();
+    let client = reqwest::Client::new();
+    registry()
+        .providers()
+        .filter(|provider| cache.is_stale(provider.info().id(), now))
+        .filter(|provider| {
+            let api_key = saved_api_key(provider.as_ref());
+            if api_key.is_none() && provider.capabilities().requires_api_key {
+                return false;
+            }
+            list_config(
+                provider.as_ref(),
+                api_key.unwrap_or_default(),
+                providers,
+                network,
+            )
+            .is_some_and(|config| provider.model_list_request(&client, &config).is_some())
+        })
+        .cloned()
+        .collect()
+}
+
+/// Fetches the model lists of `stale` providers and stores them in the cache.
+///
+/// A provider whose list cannot be fetched keeps its previous list; the failure is logged.
+/// Returns the number of discovered models afterwards.
+///
+/// # Errors
+/// - If the cache cannot be written
+pub async fn refresh(
+    stale: &[Arc<dyn Provider>],
+    providers: &ProvidersConfig,
+    network: &NetworkConfig,
+) -> anyhow::Result<usize> {
+    let path = cache_path()?;
+    let mut cache = cached_or_empty();
+    for provider in stale {
+        let api_key = saved_api_key(provider.as_ref()).unwrap_or_default();
+        match fetch(provider.as_ref(), api_key, providers, network).await {
+            Ok(Some(models)) => {
+                tracing::info!(
+                    "{} lists {} speech models",
+                    provider.info().name(),
+                    models.len()
+                );
+                cache.providers.insert(
+                    provider.info().id().to_string(),
+                    DiscoveredModels {
+                        fetched_at: Utc::now(),
+                        models,
+                    },
+                );
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                "Failed to fetch the model list of {}: {e:#}",
+                provider.info().name()
+            ),
+        }
+    }
+    cache.save(&path)?;
+    Ok(cache.models(registry()).len())
+}
+
+/// Returns the configuration a model list request of `provider` is built from.
+///
+/// Uses the provider's first built-in model, so base URL overrides apply as they do for
+/// transcriptions.
+fn list_config(
+    provider: &dyn Provider,
+    api_key: String,
+    providers: &ProvidersConfig,
+    network: &NetworkConfig,
+) -> Option<TranscriptionConfig> {
+    let model = provider.models().into_iter().next()?;
+    let mut config = TranscriptionConfig::new(model, api_key, Vec::new(), providers.clone());
+    config.network = network.clone();
+    Some(config)
+}
+
+/// Loads the cache, logging and discarding a cache that cannot be read.
+fn cached_or_empty() -> DiscoveryCache {
+    cache_path()
+        .and_then(|path| DiscoveryCache::load(&path))
+        .inspect_err(|e| tracing::warn!("Ignoring discovered models: {e}"))
+        .unwrap_or_default()
+}
+
+/// Returns the saved API key of a provider, if any.
+fn saved_api_key(provider: &dyn Provider) -> Option<String> {
+    crate::config::get_api_key(provider.info().id())
+        .ok()
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_with(provider_id: &str, models: &[&str], fetched_at: DateTime<Utc>) -> DiscoveryCache {
+        let mut cache = DiscoveryCache::default();
+        cache.providers.insert(
+            provider_id.to_string(),
+            DiscoveredModels {
+                fetched_at,
+                models: models.iter().map(|m| m.to_string()).collect(),
+            },
+        );
+        cache
+    }
+
+    #[test]
+    fn speech_models_are_picked_from_a_model_list() {
+        let body = r#"{"object":"list","data":[
+            {"id":"gpt-4o-transcribe","object":"model"},
+            {"id":"gpt-4o-mini-tts","object":"model"},
+            {"id":"whisper-1","object":"model"},
+            {"id":"gpt-4o-transcribe-diarize","object":"model"}
+        ]}"#;
+        let names = parse_openai_model_list(body).unwrap();
+        assert_eq!(names.len(), 4);
+        let speech: Vec<&String> = names.iter().filter(|n| is_speech_model(n)).collect();
+        assert_eq!(
+            speech,
+            [
+                "gpt-4o-transcribe",
+                "whisper-1",
+                "gpt-4o-transcribe-diarize"
+            ]
+        );
+        assert!(parse_openai_model_list(r#"{"error":"nope"}"#).is_err());
+    }
+
+    #[test]
+    fn only_models_that_are_not_built_in_are_discovered() {
+        let cache = cache_with(
+            "deepinfra",
+            &[
+                "openai/whisper-large-v3",
+                "openai/whisper-large-v3-turbo",
+                "openai/whisper-large-v3-turbo",
+            ],
+            Utc::now(),
+        );
+        let models = cache.models(registry());
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id(), "deepinfra-whisper-large-v3-turbo");
+        assert_eq!(models[0].api_model_name(), "openai/whisper-large-v3-turbo");
+        assert_eq!(
+            models[0].description(),
+            "openai/whisper-large-v3-turbo (discovered)"
+        );
+
+        // Lists of unknown providers are ignored
+        assert!(cache_with("nope", &["whisper"], Utc::now())
+            .models(registry())
+            .is_empty());
+    }
+
+    #[test]
+    fn cache_round_trips_and_expires() {
+        let dir = std::env::temp_dir().join(format!("ostt_test_discovery_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("discovered_models.json");
+        assert!(DiscoveryCache::load(&path).unwrap().providers.is_empty());
+
+        let fetched_at = Utc::now() - chrono::TimeDelta::hours(2);
+        cache_with("groq", &["distil-whisper-large-v3-en"], fetched_at)
+            .save(&path)
+            .unwrap();
+        let cache = DiscoveryCache::load(&path).unwrap();
+        assert_eq!(
+            cache.providers["groq"].models,
+            ["distil-whisper-large-v3-en"]
+        );
+        assert!(!cache.is_stale("groq", Utc::now()));
+        assert!(cache.is_stale(
+            "groq",
+            fetched_at + CACHE_TTL + chrono::TimeDelta::seconds(1)
+        ));
+        assert!(cache.is_stale("openai", Utc::now()));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
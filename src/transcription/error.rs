@@ -0,0 +1,326 @@
+//! Typed transcription errors.
+//!
+//! Failed provider requests are reported as a [`TranscriptionError`] so callers can tell
+//! an invalid key from a network outage without parsing the message: the fallback chain
+//! retries transient failures with another model, the error screen shows a tailored
+//! message, and the process exits with a code that identifies the failure.
+//!
+//! The errors travel inside `anyhow::Error` and are recognized with `downcast_ref`, which
+//! also sees through `context` wrappers.
+
+use std::fmt;
+use std::time::Duration;
+
+/// A transcription failure that callers can handle programmatically.
+#[derive(Debug)]
+pub enum TranscriptionError {
+    /// The API key is missing, invalid or lacks permission (HTTP 401/403)
+    Unauthorized {
+        /// Human-readable description of the failure
+        message: String,
+    },
+    /// The provider rejected the request because of its rate limit (HTTP 429)
+    RateLimited {
+        /// Human-readable description of the failure
+        message: String,
+        /// How long the provider asked to wait, from the `Retry-After` header
+        retry_after: Option<Duration>,
+    },
+    /// The account has run out of credits or quota (HTTP 402, or a quota error body)
+    QuotaExceeded {
+        /// Human-readable description of the failure
+        message: String,
+    },
+    /// The audio file is over the provider's upload limit (HTTP 413)
+    PayloadTooLarge {
+        /// Human-readable description of the failure
+        message: String,
+    },
+    /// The provider cannot decode the audio file (HTTP 415, or a format error body)
+    UnsupportedFormat {
+        /// Human-readable description of the failure
+        message: String,
+    },
+    /// The provider had an internal error (HTTP 5xx)
+    Server {
+        /// Human-readable description of the failure
+        message: String,
+    },
+    /// The request could not be sent or timed out
+    Network {
+        /// Human-readable description of the failure
+        message: String,
+    },
+    /// The provider accepted the audio but rejected or failed the transcription job
+    JobFailed {
+        /// Human-readable description of the failure
+        message: String,
+    },
+    /// Any other HTTP error status
+    Api {
+        /// HTTP status code returned by the provider
+        status: u16,
+        /// Human-readable description of the failure
+        message: String,
+    },
+}
+
+/// Process exit codes for each kind of failure.
+///
+/// Code 1 is used for all other errors and 2 is taken by argument parsing errors.
+pub mod exit_code {
+    pub const UNAUTHORIZED: u8 = 3;
+    pub const RATE_LIMITED: u8 = 4;
+    pub const QUOTA_EXCEEDED: u8 = 5;
+    pub const PAYLOAD_TOO_LARGE: u8 = 6;
+    pub const UNSUPPORTED_FORMAT: u8 = 7;
+    pub const NETWORK: u8 = 8;
+    pub const SERVER: u8 = 9;
+    pub const API: u8 = 10;
+    pub const JOB_FAILED: u8 = 11;
+}
+
+impl TranscriptionError {
+    /// Classifies an unsuccessful HTTP response.
+    ///
+    /// `message` is the provider's human-readable description of the failure.
+    pub fn from_status(
+        status: reqwest::StatusCode,
+        body: &str,
+        retry_after: Option<Duration>,
+        message: String,
+    ) -> Self {
+        if is_quota_exhausted(status, body) {
+            return TranscriptionError::QuotaExceeded { message };
+        }
+        if is_unsupported_format(status, body) {
+            return TranscriptionError::UnsupportedFormat { message };
+        }
+        match status.as_u16() {
+            401 | 403 => TranscriptionError::Unauthorized { message },
+            413 => TranscriptionError::PayloadTooLarge { message },
+            429 => TranscriptionError::RateLimited {
+                message,
+                retry_after,
+            },
+            500..=599 => TranscriptionError::Server { message },
+            status => TranscriptionError::Api { status, message },
+        }
+    }
+
+    /// Returns the error for a transcription job that did not finish within `waited`.
+    ///
+    /// Counts as a provider failure, so the fallback chain and the queue retry it.
+    pub fn poll_timeout(provider_name: &str, waited: Duration) -> Self {
+        TranscriptionError::Server {
+            message: format!(
+                "{provider_name} transcription timed out after {} seconds. The audio may be too long or the API is experiencing delays.",
+                waited.as_secs()
+            ),
+        }
+    }
+
+    /// Returns true if the same request may succeed later or with another provider.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            TranscriptionError::RateLimited { .. }
+                | TranscriptionError::Server { .. }
+                | TranscriptionError::Network { .. }
+        )
+    }
+
+    /// Returns true if `error` wraps a transient [`TranscriptionError`].
+    pub fn is_transient_error(error: &anyhow::Error) -> bool {
+        error
+            .downcast_ref::<TranscriptionError>()
+            .is_some_and(TranscriptionError::is_transient)
+    }
+
+    /// Returns the process exit code for this failure.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            TranscriptionError::Unauthorized { .. } => exit_code::UNAUTHORIZED,
+            TranscriptionError::RateLimited { .. } => exit_code::RATE_LIMITED,
+            TranscriptionError::QuotaExceeded { .. } => exit_code::QUOTA_EXCEEDED,
+            TranscriptionError::PayloadTooLarge { .. } => exit_code::PAYLOAD_TOO_LARGE,
+            TranscriptionError::UnsupportedFormat { .. } => exit_code::UNSUPPORTED_FORMAT,
+            TranscriptionError::Network { .. } => exit_code::NETWORK,
+            TranscriptionError::Server { .. } => exit_code::SERVER,
+            TranscriptionError::JobFailed { .. } => exit_code::JOB_FAILED,
+            TranscriptionError::Api { .. } => exit_code::API,
+        }
+    }
+
+    /// Returns the exit code for `error`, or 1 if it is not a [`TranscriptionError`].
+    pub fn exit_code_for(error: &anyhow::Error) -> u8 {
+        error
+            .downcast_ref::<TranscriptionError>()
+            .map_or(1, TranscriptionError::exit_code)
+    }
+
+    /// Returns a short title describing the kind of failure.
+    pub fn title(&self) -> &'static str {
+        match self {
+            TranscriptionError::Unauthorized { .. } => "Authentication failed",
+            TranscriptionError::RateLimited { .. } => "Rate limited",
+            TranscriptionError::QuotaExceeded { .. } => "Quota exceeded",
+            TranscriptionError::PayloadTooLarge { .. } => "Audio file too large",
+            TranscriptionError::UnsupportedFormat { .. } => "Unsupported audio format",
+            TranscriptionError::Network { .. } => "Network error",
+            TranscriptionError::Server { .. } => "Provider unavailable",
+            TranscriptionError::JobFailed { .. } => "Transcription job failed",
+            TranscriptionError::Api { .. } => "Transcription failed",
+        }
+    }
+
+    /// Returns the message shown on the error screen for `error`.
+    ///
+    /// Typed failures get a title and, for rate limits, how long to wait. Other errors
+    /// show their full context chain.
+    pub fn screen_message(error: &anyhow::Error) -> String {
+        let Some(typed) = error.downcast_ref::<TranscriptionError>() else {
+            return format!("Error: Transcription failed - {error:#}");
+        };
+        let mut message = format!("{}: {typed}", typed.title());
+        if let TranscriptionError::RateLimited {
+            retry_after: Some(wait),
+            ..
+        } = typed
+        {
+            message.push_str(&format!(" Retry in {} seconds.", wait.as_secs().max(1)));
+        }
+        message
+    }
+}
+
+impl fmt::Display for TranscriptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranscriptionError::Unauthorized { message }
+            | TranscriptionError::RateLimited { message, .. }
+            | TranscriptionError::QuotaExceeded { message }
+            | TranscriptionError::PayloadTooLarge { message }
+            | TranscriptionError::UnsupportedFormat { message }
+            | TranscriptionError::Server { message }
+            | TranscriptionError::Network { message }
+            | TranscriptionError::JobFailed { message }
+            | TranscriptionError::Api { message, .. } => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for TranscriptionError {}
+
+/// Returns true if the response reports exhausted credits or quota.
+///
+/// Deepgram answers 402, while OpenAI (429 `insufficient_quota`) and ElevenLabs
+/// (401 `quota_exceeded`) reuse other statuses with a quota error in the body.
+pub fn is_quota_exhausted(status: reqwest::StatusCode, body: &str) -> bool {
+    status == reqwest::StatusCode::PAYMENT_REQUIRED
+        || (matches!(status.as_u16(), 401 | 403 | 429) && body.to_lowercase().contains("quota"))
+}
+
+/// Returns true if the response reports an audio format the provider cannot decode.
+pub fn is_unsupported_format(status: reqwest::StatusCode, body: &str) -> bool {
+    if status == reqwest::StatusCode::UNSUPPORTED_MEDIA_TYPE {
+        return true;
+    }
+    let body = body.to_lowercase();
+    status == reqwest::StatusCode::BAD_REQUEST
+        && (body.contains("file format") || body.contains("unsupported format"))
+}
+
+/// Parses a `Retry-After` header given in seconds.
+pub fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+
+    fn classify(status: u16, body: &str) -> TranscriptionError {
+        let status = StatusCode::from_u16(status).unwrap();
+        TranscriptionError::from_status(status, body, None, "message".to_string())
+    }
+
+    #[test]
+    fn statuses_map_to_error_kinds() {
+        assert_eq!(classify(401, "").exit_code(), exit_code::UNAUTHORIZED);
+        assert_eq!(classify(429, "").exit_code(), exit_code::RATE_LIMITED);
+        assert_eq!(classify(413, "").exit_code(), exit_code::PAYLOAD_TOO_LARGE);
+        assert_eq!(classify(503, "").exit_code(), exit_code::SERVER);
+        assert_eq!(classify(404, "").exit_code(), exit_code::API);
+    }
+
+    #[test]
+    fn error_bodies_refine_the_status() {
+        let openai = r#"{"error":{"code":"insufficient_quota"}}"#;
+        assert_eq!(classify(429, openai).exit_code(), exit_code::QUOTA_EXCEEDED);
+        assert!(!classify(429, openai).is_transient());
+
+        let elevenlabs = r#"{"detail":{"status":"quota_exceeded"}}"#;
+        assert_eq!(
+            classify(401, elevenlabs).exit_code(),
+            exit_code::QUOTA_EXCEEDED
+        );
+
+        let format = r#"{"error":{"message":"Invalid file format. Supported formats: ..."}}"#;
+        assert_eq!(
+            classify(400, format).exit_code(),
+            exit_code::UNSUPPORTED_FORMAT
+        );
+    }
+
+    #[test]
+    fn polling_failures_are_classified() {
+        let timeout = TranscriptionError::poll_timeout("Gladia", Duration::from_secs(300));
+        assert_eq!(timeout.exit_code(), exit_code::SERVER);
+        assert!(timeout.is_transient());
+        assert!(timeout.to_string().contains("after 300 seconds"));
+
+        let failed = TranscriptionError::JobFailed {
+            message: "Audio file is empty".to_string(),
+        };
+        assert_eq!(failed.exit_code(), exit_code::JOB_FAILED);
+        assert!(!failed.is_transient());
+    }
+
+    #[test]
+    fn typed_errors_survive_context() {
+        use anyhow::Context;
+
+        let error: anyhow::Error = TranscriptionError::RateLimited {
+            message: "Too many requests.".to_string(),
+            retry_after: Some(Duration::from_secs(20)),
+        }
+        .into();
+        let error = Err::<(), _>(error)
+            .context("Transcription failed")
+            .unwrap_err();
+
+        assert_eq!(
+            TranscriptionError::exit_code_for(&error),
+            exit_code::RATE_LIMITED
+        );
+        assert_eq!(
+            TranscriptionError::screen_message(&error),
+            "Rate limited: Too many requests. Retry in 20 seconds."
+        );
+        assert_eq!(
+            TranscriptionError::exit_code_for(&anyhow::anyhow!("other")),
+            1
+        );
+    }
+
+    #[test]
+    fn retry_after_is_parsed_in_seconds() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+        headers.insert(reqwest::header::RETRY_AFTER, "12".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(12)));
+    }
+}
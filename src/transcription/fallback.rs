@@ -0,0 +1,186 @@
+//! Fallback to other models when a provider is temporarily unavailable.
+//!
+//! `[transcription] fallback_models` lists models to try, in order, after the selected
+//! model. A fallback is only used when the previous attempt failed with a rate limit,
+//! a server error or a network problem; other errors such as an invalid API key are
+//! returned right away.
+
+use std::path::Path;
+
+use super::api::{TranscriptionConfig, TranscriptionResponse};
+use super::chunking;
+use super::error::TranscriptionError;
+use super::model::TranscriptionModel;
+use super::registry::ProviderRegistry;
+use crate::config;
+
+/// Returns the primary configuration followed by one per usable fallback model.
+///
+/// Fallback models that are unknown or whose provider has no saved API key are skipped
+/// with a warning. Keywords and options are shared with the primary configuration.
+pub fn build_chain(
+    primary: TranscriptionConfig,
+    fallback_model_ids: &[String],
+) -> Vec<TranscriptionConfig> {
+    build_chain_with_keys(primary, fallback_model_ids, |provider_id| {
+        config::get_api_key(provider_id).ok().flatten()
+    })
+}
+
+fn build_chain_with_keys(
+    primary: TranscriptionConfig,
+    fallback_model_ids: &[String],
+    api_key: impl Fn(&str) -> Option<String>,
+) -> Vec<TranscriptionConfig> {
+    let mut chain = vec![primary];
+    for model_id in fallback_model_ids {
+        if chain.iter().any(|config| config.model.id() == model_id) {
+            continue;
+        }
+
+        let Some(model) = TranscriptionModel::from_id_with_config(model_id, &chain[0].providers)
+        else {
+            tracing::warn!("Ignoring unknown fallback model '{}'", model_id);
+            continue;
+        };
+        let provider = model.provider();
+        let key = match api_key(provider.id()) {
+            Some(key) => key,
+            None if !provider.requires_api_key() => String::new(),
+            None => {
+                tracing::warn!(
+                    "Ignoring fallback model '{}': no API key for {}",
+                    model_id,
+                    provider.name()
+                );
+                continue;
+            }
+        };
+
+        let mut config = chain[0].clone();
+        config.model = model;
+        config.api_key = key;
+        chain.push(config);
+    }
+    chain
+}
+
+/// Transcribes with each configuration in turn until one succeeds.
+///
+/// `on_attempt` is called before each attempt, e.g. to show which provider is in use.
+/// Returns the response together with the configuration that produced it.
+///
+/// # Errors
+/// - If an attempt fails with an error that another model would not fix
+/// - If the last model in the chain fails
+pub async fn transcribe<'a>(
+    registry: &ProviderRegistry,
+    chain: &'a [TranscriptionConfig],
+    audio_path: &Path,
+    mut on_attempt: impl FnMut(&TranscriptionConfig),
+) -> anyhow::Result<(TranscriptionResponse, &'a TranscriptionConfig)> {
+    let (last, fallbacks) = chain
+        .split_last()
+        .ok_or_else(|| anyhow::anyhow!("No transcription model configured"))?;
+
+    for (index, config) in fallbacks.iter().enumerate() {
+        on_attempt(config);
+        match chunking::transcribe(registry, config, audio_path).await {
+            Ok(response) => return Ok((response, config)),
+            Err(e) if TranscriptionError::is_transient_error(&e) => {
+                tracing::warn!(
+                    "{} failed ({:#}), falling back to {}",
+                    config.model.id(),
+                    e,
+                    chain[index + 1].model.id()
+                );
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    on_attempt(last);
+    let response = chunking::transcribe(registry, last, audio_path).await?;
+    Ok((response, last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProvidersConfig;
+    use crate::transcription::test_support::StubProvider;
+    use std::sync::{Arc, Mutex};
+
+    /// Returns a registry whose first model fails, transiently or not, and whose second
+    /// model succeeds, with the chain trying them in that order.
+    fn chain(transient: bool) -> (ProviderRegistry, Vec<TranscriptionConfig>) {
+        let flaky = StubProvider::replying("flaky", move |_| {
+            if transient {
+                Err(TranscriptionError::RateLimited {
+                    message: "slow down".to_string(),
+                    retry_after: None,
+                }
+                .into())
+            } else {
+                Err(anyhow::anyhow!("invalid API key"))
+            }
+        });
+        let steady = StubProvider::new("steady", "steady text");
+        let chain = vec![flaky.config(), steady.config()];
+
+        let mut registry = ProviderRegistry::new();
+        registry.register(Arc::new(flaky));
+        registry.register(Arc::new(steady));
+        (registry, chain)
+    }
+
+    #[tokio::test]
+    async fn transient_failures_fall_back_to_next_model() {
+        let (registry, chain) = chain(true);
+        let attempts = Mutex::new(Vec::new());
+
+        let (response, used) = transcribe(&registry, &chain, Path::new("unused.mp3"), |config| {
+            attempts.lock().unwrap().push(config.model.id().to_string())
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(response.text, "steady text");
+        assert_eq!(used.model.id(), "steady-1");
+        assert_eq!(*attempts.lock().unwrap(), ["flaky-1", "steady-1"]);
+    }
+
+    #[tokio::test]
+    async fn other_failures_do_not_fall_back() {
+        let (registry, chain) = chain(false);
+        let err = transcribe(&registry, &chain, Path::new("unused.mp3"), |_| {})
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid API key"));
+    }
+
+    #[test]
+    fn chain_skips_unknown_models_and_missing_keys() {
+        let primary = TranscriptionConfig::new(
+            TranscriptionModel::from_id("nova-3").unwrap(),
+            "dg-key".to_string(),
+            vec!["ostt".to_string()],
+            ProvidersConfig::default(),
+        );
+        let fallbacks = [
+            "nova-3".to_string(),
+            "does-not-exist".to_string(),
+            "whisper".to_string(),
+            "groq-whisper-large-v3-turbo".to_string(),
+        ];
+
+        let chain = build_chain_with_keys(primary, &fallbacks, |provider_id| {
+            (provider_id == "groq").then(|| "groq-key".to_string())
+        });
+
+        let ids: Vec<&str> = chain.iter().map(|c| c.model.id()).collect();
+        assert_eq!(ids, ["nova-3", "groq-whisper-large-v3-turbo"]);
+        assert_eq!(chain[1].api_key, "groq-key");
+        assert_eq!(chain[1].keywords, ["ostt"]);
+    }
+}
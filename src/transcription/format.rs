@@ -0,0 +1,321 @@
+//! Output formats for transcriptions.
+//!
+//! Renders a [`TranscriptionResponse`] as plain text, JSON, or timed captions (SubRip and
+//! WebVTT). Captions are built from word timings when the provider reports them, otherwise
+//! from segment timings, and are split so each cue stays short enough to read on screen.
+
+use clap::ValueEnum;
+
+use super::api::{TranscriptSegment, TranscriptWord, TranscriptionResponse};
+
+/// Maximum characters per caption line
+const MAX_LINE_CHARS: usize = 42;
+
+/// Maximum lines per caption cue
+const MAX_CUE_LINES: usize = 2;
+
+/// Maximum time a single cue stays on screen, in seconds
+const MAX_CUE_SECS: f64 = 7.0;
+
+/// A pause between words longer than this starts a new cue, in seconds
+const MAX_WORD_GAP_SECS: f64 = 1.5;
+
+/// Output format for a transcription.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Plain text
+    #[default]
+    Txt,
+    /// SubRip subtitles
+    Srt,
+    /// WebVTT subtitles
+    Vtt,
+    /// Text with segment and word timings as JSON
+    Json,
+}
+
+/// A single caption shown on screen for a span of time.
+#[derive(Debug, Clone, PartialEq)]
+struct Cue {
+    start: f64,
+    end: f64,
+    lines: Vec<String>,
+}
+
+/// Renders a transcription in the requested format.
+///
+/// # Errors
+/// - If captions are requested but the provider returned no timing information
+/// - If JSON serialization fails
+pub fn render(response: &TranscriptionResponse, format: OutputFormat) -> anyhow::Result<String> {
+    match format {
+        OutputFormat::Txt => Ok(response.text.trim().to_string()),
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(response)?),
+        OutputFormat::Srt | OutputFormat::Vtt => {
+            let cues = build_cues(response);
+            if cues.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "The transcription has no timing information, so captions cannot be created. \
+                     Use a model that returns timestamps (e.g. OpenAI whisper-1, Groq, Deepgram, \
+                     AssemblyAI or ElevenLabs)."
+                ));
+            }
+            Ok(if format == OutputFormat::Srt {
+                render_srt(&cues)
+            } else {
+                render_vtt(&cues)
+            })
+        }
+    }
+}
+
+/// Builds caption cues, preferring word timings over segment timings.
+fn build_cues(response: &TranscriptionResponse) -> Vec<Cue> {
+    if !response.words.is_empty() {
+        return cues_from_words(&response.words);
+    }
+    response
+        .segments
+        .iter()
+        .flat_map(|segment| cues_from_words(&interpolate_words(segment)))
+        .collect()
+}
+
+/// Splits a segment into words with times spread proportionally to their length.
+fn interpolate_words(segment: &TranscriptSegment) -> Vec<TranscriptWord> {
+    let tokens: Vec<&str> = segment.text.split_whitespace().collect();
+    let total_chars: usize = tokens.iter().map(|t| t.chars().count()).sum();
+    if total_chars == 0 {
+        return Vec::new();
+    }
+
+    let duration = (segment.end - segment.start).max(0.0);
+    let mut elapsed_chars = 0;
+    tokens
+        .into_iter()
+        .map(|token| {
+            let start = segment.start + duration * elapsed_chars as f64 / total_chars as f64;
+            elapsed_chars += token.chars().count();
+            let end = segment.start + duration * elapsed_chars as f64 / total_chars as f64;
+            TranscriptWord {
+                text: token.to_string(),
+                start,
+                end,
+                confidence: segment.confidence,
+                speaker: segment.speaker,
+            }
+        })
+        .collect()
+}
+
+/// Groups timed words into cues that respect line length, duration, pauses and speaker changes.
+fn cues_from_words(words: &[TranscriptWord]) -> Vec<Cue> {
+    let max_cue_chars = MAX_LINE_CHARS * MAX_CUE_LINES;
+    let mut cues = Vec::new();
+    let mut current: Vec<&TranscriptWord> = Vec::new();
+
+    for word in words.iter().filter(|w| !w.text.trim().is_empty()) {
+        if let (Some(first), Some(last)) = (current.first(), current.last()) {
+            let text_len = cue_text_len(&current) + 1 + word.text.chars().count();
+            let ends_sentence = last.text.ends_with(['.', '?', '!']);
+            if text_len > max_cue_chars
+                || word.end - first.start > MAX_CUE_SECS
+                || word.start - last.end > MAX_WORD_GAP_SECS
+                || word.speaker != last.speaker
+                || ends_sentence
+            {
+                cues.push(make_cue(&current));
+                current.clear();
+            }
+        }
+        current.push(word);
+    }
+    if !current.is_empty() {
+        cues.push(make_cue(&current));
+    }
+    cues
+}
+
+/// Returns the length of the words joined by single spaces.
+fn cue_text_len(words: &[&TranscriptWord]) -> usize {
+    let chars: usize = words.iter().map(|w| w.text.chars().count()).sum();
+    chars + words.len().saturating_sub(1)
+}
+
+fn make_cue(words: &[&TranscriptWord]) -> Cue {
+    let text = words
+        .iter()
+        .map(|w| w.text.trim())
+        .collect::<Vec<_>>()
+        .join(" ");
+    Cue {
+        start: words[0].start,
+        end: words[words.len() - 1].end,
+        lines: wrap_lines(&text),
+    }
+}
+
+/// Wraps cue text into lines of at most [`MAX_LINE_CHARS`], balancing two-line cues.
+fn wrap_lines(text: &str) -> Vec<String> {
+    let char_count = text.chars().count();
+    if char_count <= MAX_LINE_CHARS {
+        return vec![text.to_string()];
+    }
+
+    // Break at the space closest to the middle so both lines have similar length,
+    // preferring breaks where both lines fit
+    let middle = char_count / 2;
+    let split = text
+        .char_indices()
+        .filter(|(_, c)| *c == ' ')
+        .map(|(i, _)| i)
+        .min_by_key(|i| {
+            let first = text[..*i].chars().count();
+            let fits = first <= MAX_LINE_CHARS && char_count - first - 1 <= MAX_LINE_CHARS;
+            (!fits, first.abs_diff(middle))
+        });
+
+    match split {
+        Some(i) => vec![text[..i].to_string(), text[i + 1..].to_string()],
+        None => vec![text.to_string()],
+    }
+}
+
+fn render_srt(cues: &[Cue]) -> String {
+    cues.iter()
+        .enumerate()
+        .map(|(i, cue)| {
+            format!(
+                "{}\n{} --> {}\n{}\n",
+                i + 1,
+                format_timestamp(cue.start, ','),
+                format_timestamp(cue.end, ','),
+                cue.lines.join("\n")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_vtt(cues: &[Cue]) -> String {
+    let body = cues
+        .iter()
+        .map(|cue| {
+            format!(
+                "{} --> {}\n{}\n",
+                format_timestamp(cue.start, '.'),
+                format_timestamp(cue.end, '.'),
+                cue.lines.join("\n")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("WEBVTT\n\n{body}")
+}
+
+/// Formats seconds as `HH:MM:SS<sep>mmm` (`,` for SubRip, `.` for WebVTT).
+fn format_timestamp(seconds: f64, separator: char) -> String {
+    let total_millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    let hours = total_millis / 3_600_000;
+    let minutes = total_millis / 60_000 % 60;
+    let secs = total_millis / 1000 % 60;
+    let millis = total_millis % 1000;
+    format!("{hours:02}:{minutes:02}:{secs:02}{separator}{millis:03}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start: f64, end: f64) -> TranscriptWord {
+        TranscriptWord {
+            text: text.to_string(),
+            start,
+            end,
+            confidence: None,
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn timestamps_use_format_specific_separator() {
+        assert_eq!(format_timestamp(3723.456, ','), "01:02:03,456");
+        assert_eq!(format_timestamp(0.5, '.'), "00:00:00.500");
+    }
+
+    #[test]
+    fn srt_and_vtt_from_words() {
+        let response = TranscriptionResponse {
+            text: "Hello there. General Kenobi.".to_string(),
+            segments: Vec::new(),
+            words: vec![
+                word("Hello", 0.0, 0.4),
+                word("there.", 0.5, 0.9),
+                word("General", 1.2, 1.6),
+                word("Kenobi.", 1.7, 2.2),
+            ],
+            language: None,
+        };
+
+        assert_eq!(
+            render(&response, OutputFormat::Srt).unwrap(),
+            "1\n00:00:00,000 --> 00:00:00,900\nHello there.\n\n\
+             2\n00:00:01,200 --> 00:00:02,200\nGeneral Kenobi.\n"
+        );
+        assert_eq!(
+            render(&response, OutputFormat::Vtt).unwrap(),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:00.900\nHello there.\n\n\
+             00:00:01.200 --> 00:00:02.200\nGeneral Kenobi.\n"
+        );
+    }
+
+    #[test]
+    fn long_segments_are_split_into_readable_cues() {
+        let text = "this is a rather long segment that keeps going without any punctuation \
+                    so that it has to be split into several captions for the viewer";
+        let response = TranscriptionResponse {
+            text: text.to_string(),
+            segments: vec![TranscriptSegment {
+                text: text.to_string(),
+                start: 10.0,
+                end: 16.0,
+                confidence: None,
+                speaker: None,
+            }],
+            words: Vec::new(),
+            language: None,
+        };
+
+        let cues = build_cues(&response);
+        assert!(cues.len() > 1);
+        assert_eq!(cues[0].start, 10.0);
+        assert_eq!(cues.last().unwrap().end, 16.0);
+        for cue in &cues {
+            assert!(cue.lines.len() <= MAX_CUE_LINES);
+            assert!(cue
+                .lines
+                .iter()
+                .all(|l| l.chars().count() <= MAX_LINE_CHARS));
+        }
+    }
+
+    #[test]
+    fn pauses_and_duration_start_new_cues() {
+        let words = vec![
+            word("one", 0.0, 0.5),
+            word("two", 3.0, 3.5),
+            word("three", 3.6, 9.0),
+            word("four", 9.0, 11.0),
+        ];
+        let cues = cues_from_words(&words);
+        let texts: Vec<String> = cues.iter().map(|c| c.lines.join(" ")).collect();
+        assert_eq!(texts, ["one", "two three", "four"]);
+    }
+
+    #[test]
+    fn captions_require_timings() {
+        let response = TranscriptionResponse::new("no timings");
+        assert!(render(&response, OutputFormat::Srt).is_err());
+        assert_eq!(render(&response, OutputFormat::Txt).unwrap(), "no timings");
+    }
+}
@@ -0,0 +1,204 @@
+//! GGML model files for the offline whisper.cpp backend.
+//!
+//! Models are kept in `~/.local/share/ostt/models` as `ggml-<name>.bin`, the file names used
+//! by the whisper.cpp project. `ostt auth` downloads them from the whisper.cpp model
+//! repository or links a file the user already has, so transcription itself never needs
+//! the network.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use super::api::http_client_builder;
+use super::model::TranscriptionModel;
+use crate::config::file::{NetworkConfig, ProvidersConfig};
+
+/// Where `ostt auth` downloads GGML models from
+const DOWNLOAD_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+/// First bytes of every GGML whisper model (the `ggml` magic number, little-endian)
+const GGML_MAGIC: [u8; 4] = *b"lmgg";
+
+/// Returns the directory that holds downloaded models.
+///
+/// # Errors
+/// - If the home directory cannot be determined
+pub fn models_dir() -> anyhow::Result<PathBuf> {
+    let home =
+        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+    Ok(home
+        .join(".local")
+        .join("share")
+        .join("ostt")
+        .join("models"))
+}
+
+/// Returns the model file used for `model`.
+///
+/// `[providers.local].model_path` takes precedence; otherwise the file is
+/// `ggml-<api model name>.bin` in [`models_dir`].
+///
+/// # Errors
+/// - If the home directory cannot be determined
+pub fn model_path(
+    providers: &ProvidersConfig,
+    model: &TranscriptionModel,
+) -> anyhow::Result<PathBuf> {
+    match &providers.local.model_path {
+        Some(path) => Ok(path.clone()),
+        None => Ok(models_dir()?.join(file_name(model))),
+    }
+}
+
+/// Returns the whisper.cpp file name of a model, e.g. `ggml-base.bin`.
+pub fn file_name(model: &TranscriptionModel) -> String {
+    format!("ggml-{}.bin", model.api_model_name())
+}
+
+/// Returns true if `path` starts with the GGML magic number.
+///
+/// # Errors
+/// - If the file cannot be read
+pub fn is_ggml_file(path: &Path) -> anyhow::Result<bool> {
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| anyhow::anyhow!("Failed to open {}: {e}", path.display()))?;
+    let mut magic = [0u8; 4];
+    match file.read_exact(&mut magic) {
+        Ok(()) => Ok(magic == GGML_MAGIC),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(anyhow::anyhow!("Failed to read {}: {e}", path.display())),
+    }
+}
+
+/// Makes an existing GGML file available as the model file of `model`.
+///
+/// The file is symlinked into [`models_dir`] on Unix and copied elsewhere, so it can stay
+/// where the user keeps it.
+///
+/// # Errors
+/// - If the file does not exist or is not a GGML model
+/// - If the link or copy cannot be created
+pub fn import(model: &TranscriptionModel, source: &Path) -> anyhow::Result<PathBuf> {
+    if !is_ggml_file(source)? {
+        return Err(anyhow::anyhow!(
+            "{} is not a GGML whisper model",
+            source.display()
+        ));
+    }
+    let source = source
+        .canonicalize()
+        .map_err(|e| anyhow::anyhow!("Failed to resolve {}: {e}", source.display()))?;
+    let dir = models_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    let target = dir.join(file_name(model));
+    if target == source {
+        return Ok(target);
+    }
+    if target.symlink_metadata().is_ok() {
+        std::fs::remove_file(&target)?;
+    }
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&source, &target)
+        .map_err(|e| anyhow::anyhow!("Failed to link {}: {e}", target.display()))?;
+    #[cfg(not(unix))]
+    std::fs::copy(&source, &target)
+        .map_err(|e| anyhow::anyhow!("Failed to copy model to {}: {e}", target.display()))?;
+
+    tracing::info!("Using {} for {}", source.display(), model.id());
+    Ok(target)
+}
+
+/// Downloads the GGML file of `model` into [`models_dir`].
+///
+/// The file is written next to its final name and renamed once complete, so an
+/// interrupted download never leaves a truncated model behind. `on_progress` is called with
+/// the bytes received so far and the total size, if the server reports one.
+///
+/// # Errors
+/// - If the request fails or the server returns an error status
+/// - If the file cannot be written
+pub async fn download(
+    model: &TranscriptionModel,
+    network: &NetworkConfig,
+    mut on_progress: impl FnMut(u64, Option<u64>),
+) -> anyhow::Result<PathBuf> {
+    let dir = models_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    let target = dir.join(file_name(model));
+    let partial = target.with_extension("bin.part");
+    let url = format!("{DOWNLOAD_BASE_URL}/{}", file_name(model));
+
+    let client = http_client_builder(network)?
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {e}"))?;
+    let mut response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to download {url}: {e}"))?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "Failed to download {url}: server returned {}",
+            response.status()
+        ));
+    }
+
+    let total = response.content_length();
+    let mut file = std::fs::File::create(&partial)
+        .map_err(|e| anyhow::anyhow!("Failed to create {}: {e}", partial.display()))?;
+    let mut received = 0u64;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| anyhow::anyhow!("Download of {url} was interrupted: {e}"))?
+    {
+        file.write_all(&chunk)?;
+        received += chunk.len() as u64;
+        on_progress(received, total);
+    }
+    file.flush()?;
+    drop(file);
+
+    if !is_ggml_file(&partial)? {
+        std::fs::remove_file(&partial).ok();
+        return Err(anyhow::anyhow!("{url} did not return a GGML model"));
+    }
+    std::fs::rename(&partial, &target)?;
+    tracing::info!("Downloaded {} to {}", model.id(), target.display());
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_model_path_replaces_the_models_dir() {
+        let model = TranscriptionModel::from_id("local-whisper-base").unwrap();
+        assert_eq!(file_name(&model), "ggml-base.bin");
+
+        let default = model_path(&ProvidersConfig::default(), &model).unwrap();
+        assert!(default.ends_with(".local/share/ostt/models/ggml-base.bin"));
+
+        let mut providers = ProvidersConfig::default();
+        providers.local.model_path = Some(PathBuf::from("/models/ggml-medium.en.bin"));
+        assert_eq!(
+            model_path(&providers, &model).unwrap(),
+            PathBuf::from("/models/ggml-medium.en.bin")
+        );
+    }
+
+    #[test]
+    fn only_ggml_files_are_accepted() {
+        let dir = std::env::temp_dir().join(format!("ostt-ggml-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let model = dir.join("model.bin");
+        std::fs::write(&model, b"lmgg\x00\x00").unwrap();
+        let other = dir.join("other.bin");
+        std::fs::write(&other, b"RIFF").unwrap();
+
+        assert!(is_ggml_file(&model).unwrap());
+        assert!(!is_ggml_file(&other).unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
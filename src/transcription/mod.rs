@@ -1,14 +1,37 @@
 //! Transcription service for audio-to-text conversion.
 //!
 //! This module provides support for multiple transcription providers and models through a
-//! unified interface. Each provider has its own API endpoint and authentication method.
+//! unified interface. Each provider implements the [`Provider`] trait with its own API endpoint
+//! and authentication method, and is looked up through the [`ProviderRegistry`].
 
 pub mod animation;
 pub mod api;
+pub mod cache;
+pub mod chunking;
+pub mod diarization;
+pub mod diff;
+pub mod discovery;
+pub mod error;
+pub mod fallback;
+pub mod format;
+#[cfg(feature = "local-whisper")]
+pub mod local_model;
 pub mod model;
+pub mod pricing;
 pub mod provider;
+pub mod registry;
+pub mod streaming;
+#[cfg(test)]
+pub(crate) mod test_support;
 
 pub use animation::TranscriptionAnimation;
-pub use api::{transcribe, TranscriptionConfig, TranscriptionResponse};
+pub use api::{
+    transcribe, AudioUpload, KeywordSupport, ModelCapabilities, Provider, ProviderCapabilities,
+    TranscriptSegment, TranscriptWord, TranscriptionConfig, TranscriptionResponse,
+};
+pub use error::TranscriptionError;
+pub use format::OutputFormat;
 pub use model::TranscriptionModel;
 pub use provider::TranscriptionProvider;
+pub use registry::{registry, ProviderRegistry};
+pub use streaming::{LiveRequest, LiveSession, LiveTranscript, LiveUpdate};
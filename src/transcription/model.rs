@@ -1,215 +1,298 @@
 //! Transcription model definitions and metadata.
 //!
-//! Defines supported transcription models (e.g., Whisper) with their associated metadata,
-//! providers, API endpoints, and model names.
+//! Describes a transcription model (e.g., Whisper) with its provider, id, description and the
+//! model name sent to the API. Built-in models are declared by each provider module in
+//! [`super::api`] and discovered through the [`ProviderRegistry`](super::registry::ProviderRegistry).
+//! Users can declare additional models for the built-in providers as `[[providers.models]]`
+//! entries in `ostt.toml`, and `ostt auth` adds the models providers list that are not built
+//! in (see [`super::discovery`]).
 
-use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
+use super::api::ModelCapabilities;
+use super::discovery;
 use super::provider::TranscriptionProvider;
+use super::registry::registry;
+use crate::config::{ModelConfig, ProvidersConfig};
 
-/// Represents a supported transcription model
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum TranscriptionModel {
-    /// OpenAI GPT-4o Transcribe model (latest, best accuracy)
-    Gpt4oTranscribe,
-    /// OpenAI GPT-4o Mini Transcribe model (faster, lighter)
-    Gpt4oMiniTranscribe,
-    /// OpenAI Whisper model (legacy)
-    Whisper,
-    /// Deepgram Nova 3 model (latest, fastest)
-    DeepgramNova3,
-    /// Deepgram Nova 2 model (previous generation)
-    DeepgramNova2,
-    /// DeepInfra Whisper Large V3 model
-    DeepInfraWhisperLargeV3,
-    /// DeepInfra Whisper Base model
-    DeepInfraWhisperBase,
-    /// Groq Whisper Large V3 model
-    GroqWhisperLargeV3,
-    /// Groq Whisper Large V3 Turbo model (faster)
-    GroqWhisperLargeV3Turbo,
-    /// AssemblyAI Universal 3 Pro model (best accuracy)
-    AssemblyAIUniversal3Pro,
-    /// Berget KB Whisper Large model (Swedish optimized)
-    BergetWhisperKBLarge,
-    /// Berget NB Whisper Large model (Norwegian optimized)
-    BergetWhisperNBLarge,
-    /// Berget Whisper Large V3 model (general-purpose, OpenAI)
-    BergetWhisperLargeV3,
-    /// ElevenLabs Scribe v2 model (highest accuracy, 99 languages)
-    ElevenLabsScribeV2,
-    /// ElevenLabs Scribe v1 model (previous generation)
-    ElevenLabsScribeV1,
+/// Describes a supported transcription model
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TranscriptionModel {
+    provider: TranscriptionProvider,
+    id: Cow<'static, str>,
+    api_model_name: Cow<'static, str>,
+    description: Cow<'static, str>,
 }
 
 impl TranscriptionModel {
+    /// Creates a model description.
+    ///
+    /// # Arguments
+    /// * `provider` - The provider serving this model
+    /// * `id` - Unique model identifier used by ostt (e.g. "groq-whisper-large-v3")
+    /// * `api_model_name` - Model name sent to the provider API (e.g. "whisper-large-v3")
+    /// * `description` - Human-readable description shown in `ostt auth`
+    pub fn new(
+        provider: TranscriptionProvider,
+        id: impl Into<Cow<'static, str>>,
+        api_model_name: impl Into<Cow<'static, str>>,
+        description: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            provider,
+            id: id.into(),
+            api_model_name: api_model_name.into(),
+            description: description.into(),
+        }
+    }
+
     /// Returns the provider for this model
     pub fn provider(&self) -> TranscriptionProvider {
-        match self {
-            TranscriptionModel::Gpt4oTranscribe
-            | TranscriptionModel::Gpt4oMiniTranscribe
-            | TranscriptionModel::Whisper => TranscriptionProvider::OpenAI,
-            TranscriptionModel::DeepgramNova3 | TranscriptionModel::DeepgramNova2 => {
-                TranscriptionProvider::Deepgram
-            }
-            TranscriptionModel::DeepInfraWhisperLargeV3
-            | TranscriptionModel::DeepInfraWhisperBase => TranscriptionProvider::DeepInfra,
-            TranscriptionModel::GroqWhisperLargeV3
-            | TranscriptionModel::GroqWhisperLargeV3Turbo => TranscriptionProvider::Groq,
-            TranscriptionModel::AssemblyAIUniversal3Pro => TranscriptionProvider::AssemblyAI,
-            TranscriptionModel::BergetWhisperKBLarge
-            | TranscriptionModel::BergetWhisperNBLarge
-            | TranscriptionModel::BergetWhisperLargeV3 => TranscriptionProvider::Berget,
-            TranscriptionModel::ElevenLabsScribeV2 | TranscriptionModel::ElevenLabsScribeV1 => {
-                TranscriptionProvider::ElevenLabs
-            }
-        }
+        self.provider
     }
 
     /// Returns the model identifier as a string
-    pub fn id(&self) -> &'static str {
-        match self {
-            TranscriptionModel::Gpt4oTranscribe => "gpt-4o-transcribe",
-            TranscriptionModel::Gpt4oMiniTranscribe => "gpt-4o-mini-transcribe",
-            TranscriptionModel::Whisper => "whisper",
-            TranscriptionModel::DeepgramNova3 => "nova-3",
-            TranscriptionModel::DeepgramNova2 => "nova-2",
-            TranscriptionModel::DeepInfraWhisperLargeV3 => "deepinfra-whisper-large-v3",
-            TranscriptionModel::DeepInfraWhisperBase => "deepinfra-whisper-base",
-            TranscriptionModel::GroqWhisperLargeV3 => "groq-whisper-large-v3",
-            TranscriptionModel::GroqWhisperLargeV3Turbo => "groq-whisper-large-v3-turbo",
-            TranscriptionModel::AssemblyAIUniversal3Pro => "assemblyai-universal-3-pro",
-            TranscriptionModel::BergetWhisperKBLarge => "berget-whisper-kb-large",
-            TranscriptionModel::BergetWhisperNBLarge => "berget-whisper-nb-large",
-            TranscriptionModel::BergetWhisperLargeV3 => "berget-whisper-large-v3",
-            TranscriptionModel::ElevenLabsScribeV2 => "elevenlabs-scribe-v2",
-            TranscriptionModel::ElevenLabsScribeV1 => "elevenlabs-scribe-v1",
-        }
+    pub fn id(&self) -> &str {
+        &self.id
     }
 
     /// Returns a human-readable description of the model
-    pub fn description(&self) -> &'static str {
-        match self {
-            TranscriptionModel::Gpt4oTranscribe => "GPT-4o Transcribe (latest, best accuracy)",
-            TranscriptionModel::Gpt4oMiniTranscribe => "GPT-4o Mini Transcribe (faster, lighter)",
-            TranscriptionModel::Whisper => "Whisper (legacy)",
-            TranscriptionModel::DeepgramNova3 => "Nova 3 (latest, fastest)",
-            TranscriptionModel::DeepgramNova2 => "Nova 2 (previous generation)",
-            TranscriptionModel::DeepInfraWhisperLargeV3 => "Whisper Large V3 (best accuracy)",
-            TranscriptionModel::DeepInfraWhisperBase => "Whisper Base (fast, lightweight)",
-            TranscriptionModel::GroqWhisperLargeV3 => "Whisper Large V3 (high accuracy)",
-            TranscriptionModel::GroqWhisperLargeV3Turbo => "Whisper Large V3 Turbo (fastest)",
-            TranscriptionModel::AssemblyAIUniversal3Pro => "Universal 3 Pro (best accuracy)",
-            TranscriptionModel::BergetWhisperKBLarge => "KB Whisper Large (Swedish optimized)",
-            TranscriptionModel::BergetWhisperNBLarge => "NB Whisper Large (Norwegian optimized)",
-            TranscriptionModel::BergetWhisperLargeV3 => "Whisper Large V3 (general-purpose)",
-            TranscriptionModel::ElevenLabsScribeV2 => "Scribe v2 (highest accuracy, 99 languages)",
-            TranscriptionModel::ElevenLabsScribeV1 => "Scribe v1 (previous generation)",
-        }
+    pub fn description(&self) -> &str {
+        &self.description
     }
 
-    /// Returns the API endpoint for this model
-    pub fn endpoint(&self) -> &'static str {
-        match self {
-            TranscriptionModel::Gpt4oTranscribe
-            | TranscriptionModel::Gpt4oMiniTranscribe
-            | TranscriptionModel::Whisper => "https://api.openai.com/v1/audio/transcriptions",
-            TranscriptionModel::DeepgramNova3 | TranscriptionModel::DeepgramNova2 => {
-                "https://api.deepgram.com/v1/listen"
-            }
-            TranscriptionModel::DeepInfraWhisperLargeV3
-            | TranscriptionModel::DeepInfraWhisperBase => "https://api.deepinfra.com/v1/inference",
-            TranscriptionModel::GroqWhisperLargeV3
-            | TranscriptionModel::GroqWhisperLargeV3Turbo => {
-                "https://api.groq.com/openai/v1/audio/transcriptions"
-            }
-            TranscriptionModel::AssemblyAIUniversal3Pro => "https://api.assemblyai.com/v2",
-            TranscriptionModel::BergetWhisperKBLarge
-            | TranscriptionModel::BergetWhisperNBLarge
-            | TranscriptionModel::BergetWhisperLargeV3 => {
-                "https://api.berget.ai/v1/audio/transcriptions"
-            }
-            TranscriptionModel::ElevenLabsScribeV2 | TranscriptionModel::ElevenLabsScribeV1 => {
-                "https://api.elevenlabs.io/v1/speech-to-text"
-            }
-        }
+    /// Returns the model name to send to the API
+    pub fn api_model_name(&self) -> &str {
+        &self.api_model_name
     }
 
-    /// Returns the model name to send to the API
-    pub fn api_model_name(&self) -> &'static str {
-        match self {
-            TranscriptionModel::Gpt4oTranscribe => "gpt-4o-transcribe",
-            TranscriptionModel::Gpt4oMiniTranscribe => "gpt-4o-mini-transcribe",
-            TranscriptionModel::Whisper => "whisper-1",
-            TranscriptionModel::DeepgramNova3 => "nova-3",
-            TranscriptionModel::DeepgramNova2 => "nova-2",
-            TranscriptionModel::DeepInfraWhisperLargeV3 => "openai/whisper-large-v3",
-            TranscriptionModel::DeepInfraWhisperBase => "openai/whisper-base",
-            TranscriptionModel::GroqWhisperLargeV3 => "whisper-large-v3",
-            TranscriptionModel::GroqWhisperLargeV3Turbo => "whisper-large-v3-turbo",
-            TranscriptionModel::AssemblyAIUniversal3Pro => "universal-3-pro",
-            TranscriptionModel::BergetWhisperKBLarge => "KBLab/kb-whisper-large",
-            TranscriptionModel::BergetWhisperNBLarge => "NbAiLab/nb-whisper-large",
-            TranscriptionModel::BergetWhisperLargeV3 => "openai/whisper-large-v3",
-            TranscriptionModel::ElevenLabsScribeV2 => "scribe_v2",
-            TranscriptionModel::ElevenLabsScribeV1 => "scribe_v1",
-        }
+    /// Returns what this model supports, or `None` if its provider is not registered
+    pub fn capabilities(&self) -> Option<ModelCapabilities> {
+        registry()
+            .get(self.provider.id())
+            .map(|provider| provider.model_capabilities(self))
     }
 
     /// Parses a model ID string into a TranscriptionModel
     pub fn from_id(id: &str) -> Option<Self> {
-        match id {
-            "gpt-4o-transcribe" => Some(TranscriptionModel::Gpt4oTranscribe),
-            "gpt-4o-mini-transcribe" => Some(TranscriptionModel::Gpt4oMiniTranscribe),
-            "whisper" => Some(TranscriptionModel::Whisper),
-            "nova-3" => Some(TranscriptionModel::DeepgramNova3),
-            "nova-2" => Some(TranscriptionModel::DeepgramNova2),
-            "deepinfra-whisper-large-v3" => Some(TranscriptionModel::DeepInfraWhisperLargeV3),
-            "deepinfra-whisper-base" => Some(TranscriptionModel::DeepInfraWhisperBase),
-            "groq-whisper-large-v3" => Some(TranscriptionModel::GroqWhisperLargeV3),
-            "groq-whisper-large-v3-turbo" => Some(TranscriptionModel::GroqWhisperLargeV3Turbo),
-            "assemblyai-universal-3-pro" => Some(TranscriptionModel::AssemblyAIUniversal3Pro),
-            "berget-whisper-kb-large" => Some(TranscriptionModel::BergetWhisperKBLarge),
-            "berget-whisper-nb-large" => Some(TranscriptionModel::BergetWhisperNBLarge),
-            "berget-whisper-large-v3" => Some(TranscriptionModel::BergetWhisperLargeV3),
-            "elevenlabs-scribe-v2" => Some(TranscriptionModel::ElevenLabsScribeV2),
-            "elevenlabs-scribe-v1" => Some(TranscriptionModel::ElevenLabsScribeV1),
-            _ => None,
-        }
+        registry().find_model(id)
     }
 
     /// Returns all available models
-    pub fn all() -> &'static [Self] {
-        &[
-            TranscriptionModel::Gpt4oTranscribe,
-            TranscriptionModel::Gpt4oMiniTranscribe,
-            TranscriptionModel::Whisper,
-            TranscriptionModel::DeepgramNova3,
-            TranscriptionModel::DeepgramNova2,
-            TranscriptionModel::DeepInfraWhisperLargeV3,
-            TranscriptionModel::DeepInfraWhisperBase,
-            TranscriptionModel::GroqWhisperLargeV3,
-            TranscriptionModel::GroqWhisperLargeV3Turbo,
-            TranscriptionModel::AssemblyAIUniversal3Pro,
-            TranscriptionModel::BergetWhisperKBLarge,
-            TranscriptionModel::BergetWhisperNBLarge,
-            TranscriptionModel::BergetWhisperLargeV3,
-            TranscriptionModel::ElevenLabsScribeV2,
-            TranscriptionModel::ElevenLabsScribeV1,
-        ]
+    pub fn all() -> Vec<Self> {
+        registry().models()
     }
 
     /// Returns all available model IDs
-    pub fn available_ids() -> Vec<&'static str> {
-        Self::all().iter().map(|m| m.id()).collect()
+    pub fn available_ids() -> Vec<String> {
+        Self::all().iter().map(|m| m.id().to_string()).collect()
     }
 
     /// Returns all models for a given provider
     pub fn models_for_provider(provider: &TranscriptionProvider) -> Vec<TranscriptionModel> {
         Self::all()
-            .iter()
+            .into_iter()
             .filter(|m| m.provider() == *provider)
-            .cloned()
             .collect()
     }
+
+    /// Creates a model from a user-defined `[[providers.models]]` entry.
+    ///
+    /// # Errors
+    /// - If the id or API model name is empty
+    /// - If the provider is not a registered provider
+    /// - If the id is already used by a built-in model
+    pub fn from_config(model: &ModelConfig) -> anyhow::Result<Self> {
+        if model.id.trim().is_empty() {
+            return Err(anyhow::anyhow!("Model id must not be empty"));
+        }
+        if model.api_model_name.trim().is_empty() {
+            return Err(anyhow::anyhow!(
+                "Model '{}' must set api_model_name",
+                model.id
+            ));
+        }
+        let provider = TranscriptionProvider::from_id(&model.provider).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Model '{}' uses unknown provider '{}'",
+                model.id,
+                model.provider
+            )
+        })?;
+        if Self::from_id(&model.id).is_some() {
+            return Err(anyhow::anyhow!(
+                "Model '{}' conflicts with a built-in model of the same id",
+                model.id
+            ));
+        }
+
+        let description = model
+            .description
+            .clone()
+            .unwrap_or_else(|| model.api_model_name.clone());
+        Ok(Self::new(
+            provider,
+            model.id.clone(),
+            model.api_model_name.clone(),
+            description,
+        ))
+    }
+
+    /// Returns the valid user-defined models from config.
+    ///
+    /// Invalid entries and duplicate ids are skipped with a warning; use
+    /// [`TranscriptionModel::from_config`] to get the reason for a single entry.
+    pub fn user_defined(providers: &ProvidersConfig) -> Vec<Self> {
+        let mut models: Vec<Self> = Vec::new();
+        for entry in &providers.models {
+            match Self::from_config(entry) {
+                Ok(model) if models.iter().any(|m| m.id() == model.id()) => {
+                    tracing::warn!("Ignoring duplicate model '{}' in config", model.id());
+                }
+                Ok(model) => models.push(model),
+                Err(e) => tracing::warn!("Ignoring model in config: {e}"),
+            }
+        }
+        models
+    }
+
+    /// Returns all built-in models, then the models discovered by `ostt auth`, then the
+    /// user-defined models from config
+    pub fn all_with_config(providers: &ProvidersConfig) -> Vec<Self> {
+        Self::merge(
+            Self::all(),
+            discovery::cached_models(),
+            Self::user_defined(providers),
+        )
+    }
+
+    /// Parses a model ID string, including discovered and user-defined models
+    pub fn from_id_with_config(id: &str, providers: &ProvidersConfig) -> Option<Self> {
+        Self::from_id(id).or_else(|| {
+            Self::all_with_config(providers)
+                .into_iter()
+                .find(|m| m.id() == id)
+        })
+    }
+
+    /// Joins model lists; discovered models that a user-defined model already covers
+    /// (same id, or same provider and API model name) are dropped.
+    fn merge(builtin: Vec<Self>, discovered: Vec<Self>, user_defined: Vec<Self>) -> Vec<Self> {
+        let mut models = builtin;
+        models.extend(discovered.into_iter().filter(|model| {
+            !user_defined.iter().any(|m| {
+                m.id() == model.id()
+                    || (m.provider() == model.provider()
+                        && m.api_model_name() == model.api_model_name())
+            })
+        }));
+        models.extend(user_defined);
+        models
+    }
+
+    /// Returns all models for a given provider, including user-defined models from config
+    pub fn models_for_provider_with_config(
+        provider: &TranscriptionProvider,
+        providers: &ProvidersConfig,
+    ) -> Vec<Self> {
+        Self::all_with_config(providers)
+            .into_iter()
+            .filter(|m| m.provider() == *provider)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_model(id: &str, provider: &str) -> ModelConfig {
+        ModelConfig {
+            id: id.to_string(),
+            provider: provider.to_string(),
+            api_model_name: "distil-whisper-large-v3-en".to_string(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn user_defined_model_is_resolved_by_id() {
+        let providers = ProvidersConfig {
+            models: vec![user_model("groq-distil-whisper", "groq")],
+            ..Default::default()
+        };
+        let model =
+            TranscriptionModel::from_id_with_config("groq-distil-whisper", &providers).unwrap();
+        assert_eq!(model.provider().id(), "groq");
+        assert_eq!(model.api_model_name(), "distil-whisper-large-v3-en");
+        // Description falls back to the API model name
+        assert_eq!(model.description(), "distil-whisper-large-v3-en");
+        assert!(TranscriptionModel::from_id("groq-distil-whisper").is_none());
+    }
+
+    #[test]
+    fn user_defined_models_are_listed_after_builtin_models_of_their_provider() {
+        let providers = ProvidersConfig {
+            models: vec![user_model("groq-distil-whisper", "groq")],
+            ..Default::default()
+        };
+        let groq = TranscriptionProvider::from_id("groq").unwrap();
+        let ids: Vec<String> =
+            TranscriptionModel::models_for_provider_with_config(&groq, &providers)
+                .iter()
+                .map(|m| m.id().to_string())
+                .collect();
+        assert_eq!(
+            ids,
+            [
+                "groq-whisper-large-v3",
+                "groq-whisper-large-v3-turbo",
+                "groq-distil-whisper"
+            ]
+        );
+    }
+
+    #[test]
+    fn user_defined_models_replace_discovered_models() {
+        let groq = TranscriptionProvider::from_id("groq").unwrap();
+        let discovered = vec![
+            TranscriptionModel::new(
+                groq,
+                "groq-distil-whisper-large-v3-en",
+                "distil-whisper-large-v3-en",
+                "",
+            ),
+            TranscriptionModel::new(groq, "groq-whisper-large-v4", "whisper-large-v4", ""),
+        ];
+        let providers = ProvidersConfig {
+            models: vec![user_model("groq-distil-whisper", "groq")],
+            ..Default::default()
+        };
+        let ids: Vec<String> = TranscriptionModel::merge(
+            Vec::new(),
+            discovered,
+            TranscriptionModel::user_defined(&providers),
+        )
+        .iter()
+        .map(|m| m.id().to_string())
+        .collect();
+        assert_eq!(ids, ["groq-whisper-large-v4", "groq-distil-whisper"]);
+    }
+
+    #[test]
+    fn invalid_user_models_are_rejected() {
+        assert!(TranscriptionModel::from_config(&user_model("x", "nope")).is_err());
+        assert!(TranscriptionModel::from_config(&user_model("nova-3", "deepgram")).is_err());
+        assert!(TranscriptionModel::from_config(&user_model(" ", "groq")).is_err());
+
+        let providers = ProvidersConfig {
+            models: vec![
+                user_model("x", "nope"),
+                user_model("groq-distil-whisper", "groq"),
+                user_model("groq-distil-whisper", "groq"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(TranscriptionModel::user_defined(&providers).len(), 1);
+    }
 }
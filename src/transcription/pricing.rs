@@ -0,0 +1,149 @@
+//! Approximate transcription prices and per-transcription usage.
+//!
+//! Providers bill per minute of audio. The table holds published pay-as-you-go list prices
+//! in US dollars, so the costs shown by ostt are estimates: discounts, free tiers, minimum
+//! billing increments and price changes are not taken into account.
+
+use std::path::Path;
+
+use super::api::TranscriptionResponse;
+use super::model::TranscriptionModel;
+use crate::recording::ffmpeg;
+
+/// List prices in USD per minute of audio, by provider id and API model name
+const PRICES_PER_MINUTE: &[(&str, &str, f64)] = &[
+    ("openai", "gpt-4o-transcribe", 0.006),
+    ("openai", "gpt-4o-mini-transcribe", 0.003),
+    ("openai", "whisper-1", 0.006),
+    ("azure", "gpt-4o-transcribe", 0.006),
+    ("azure", "gpt-4o-mini-transcribe", 0.003),
+    ("azure", "whisper-1", 0.006),
+    ("groq", "whisper-large-v3", 0.111 / 60.0),
+    ("groq", "whisper-large-v3-turbo", 0.04 / 60.0),
+    ("deepgram", "nova-3", 0.0043),
+    ("deepgram", "nova-2", 0.0043),
+    ("assemblyai", "universal-3-pro", 0.0035),
+    ("elevenlabs", "scribe_v2", 0.40 / 60.0),
+    ("elevenlabs", "scribe_v1", 0.40 / 60.0),
+    ("deepinfra", "openai/whisper-large-v3", 0.00045),
+    ("deepinfra", "openai/whisper-base", 0.0001),
+    ("speechmatics", "enhanced", 1.04 / 60.0),
+    ("speechmatics", "standard", 0.80 / 60.0),
+    // Only the pinned release is priced; `voxtral-mini-latest` moves to newer releases
+    ("mistral", "voxtral-mini-2507", 0.001),
+];
+
+/// Returns the list price of a model in USD per minute of audio, if known.
+///
+/// User-defined models are priced by their API model name, so a custom id for a known
+/// model gets the same price.
+pub fn price_per_minute(model: &TranscriptionModel) -> Option<f64> {
+    PRICES_PER_MINUTE
+        .iter()
+        .find(|(provider, api_model, _)| {
+            *provider == model.provider().id() && *api_model == model.api_model_name()
+        })
+        .map(|(_, _, price)| *price)
+}
+
+/// Returns the estimated cost in USD of transcribing `duration_secs` of audio with `model`.
+pub fn estimate_cost(model: &TranscriptionModel, duration_secs: f64) -> Option<f64> {
+    price_per_minute(model).map(|price| price * duration_secs / 60.0)
+}
+
+/// Formats a cost in USD with enough precision for fractions of a cent.
+pub fn format_cost(cost: f64) -> String {
+    if cost >= 1.0 {
+        format!("${cost:.2}")
+    } else {
+        format!("${cost:.4}")
+    }
+}
+
+/// Billing details of one transcription, as recorded in the history database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptionUsage {
+    /// ID of the model that produced the transcript
+    pub model_id: String,
+    /// ID of the model's provider
+    pub provider_id: String,
+    /// Length of the transcribed audio in seconds, if known
+    pub duration_secs: Option<f64>,
+    /// Estimated cost in USD, if the duration and the model's price are known
+    pub cost: Option<f64>,
+}
+
+impl TranscriptionUsage {
+    /// Creates the usage of transcribing `duration_secs` of audio with `model`.
+    pub fn new(model: &TranscriptionModel, duration_secs: Option<f64>) -> Self {
+        Self {
+            model_id: model.id().to_string(),
+            provider_id: model.provider().id().to_string(),
+            duration_secs,
+            cost: duration_secs.and_then(|seconds| estimate_cost(model, seconds)),
+        }
+    }
+
+    /// Measures the usage of a finished transcription.
+    ///
+    /// The duration is read from the audio file with ffmpeg, falling back to the end of
+    /// the transcript timings when ffmpeg is unavailable.
+    pub async fn measure(
+        model: &TranscriptionModel,
+        audio_path: &Path,
+        response: &TranscriptionResponse,
+    ) -> Self {
+        let path = audio_path.to_path_buf();
+        let duration = tokio::task::spawn_blocking(move || ffmpeg::probe_duration(&path))
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result)
+            .inspect_err(|e| tracing::debug!("Could not determine audio duration: {e}"))
+            .ok()
+            .or_else(|| response.timed_duration());
+        Self::new(model, duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cost_scales_with_duration() {
+        let whisper = TranscriptionModel::from_id("whisper").unwrap();
+        assert_eq!(price_per_minute(&whisper), Some(0.006));
+        let cost = estimate_cost(&whisper, 90.0).unwrap();
+        assert_eq!(format_cost(cost), "$0.0090");
+        assert_eq!(format_cost(12.5), "$12.50");
+    }
+
+    #[test]
+    fn unknown_models_have_no_price() {
+        let custom = TranscriptionModel::from_id("custom").unwrap();
+        assert_eq!(estimate_cost(&custom, 60.0), None);
+    }
+
+    #[test]
+    fn usage_is_priced_from_duration() {
+        let nova = TranscriptionModel::from_id("nova-3").unwrap();
+        let usage = TranscriptionUsage::new(&nova, Some(120.0));
+        assert_eq!(usage.model_id, "nova-3");
+        assert_eq!(usage.provider_id, "deepgram");
+        assert!((usage.cost.unwrap() - 0.0086).abs() < 1e-9);
+        assert_eq!(TranscriptionUsage::new(&nova, None).cost, None);
+    }
+
+    #[test]
+    fn every_priced_model_exists() {
+        let models = TranscriptionModel::all();
+        for (provider, api_model, _) in PRICES_PER_MINUTE {
+            assert!(
+                models
+                    .iter()
+                    .any(|m| m.provider().id() == *provider && m.api_model_name() == *api_model),
+                "{provider}/{api_model} is not a built-in model"
+            );
+        }
+    }
+}
@@ -1,69 +1,51 @@
-//! Transcription provider definitions and methods.
+//! Transcription provider identity.
 //!
-//! Defines supported transcription service providers (e.g., OpenAI).
-//! Each provider has its own API endpoint and authentication method.
+//! Identifies a transcription service provider (e.g., OpenAI) by a stable id and display name.
+//! The provider implementations themselves live in [`super::api`] and are looked up through
+//! the [`ProviderRegistry`](super::registry::ProviderRegistry).
 
-use serde::{Deserialize, Serialize};
+use super::registry::registry;
 
-/// Represents a supported transcription provider
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum TranscriptionProvider {
-    OpenAI,
-    Deepgram,
-    DeepInfra,
-    Groq,
-    AssemblyAI,
-    Berget,
-    ElevenLabs,
+/// Identifies a supported transcription provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TranscriptionProvider {
+    id: &'static str,
+    name: &'static str,
 }
 
 impl TranscriptionProvider {
+    /// Creates a provider identity from its id and display name
+    pub const fn new(id: &'static str, name: &'static str) -> Self {
+        Self { id, name }
+    }
+
+    /// Returns the provider identifier used for credentials (e.g. "openai")
     pub fn id(&self) -> &'static str {
-        match self {
-            TranscriptionProvider::OpenAI => "openai",
-            TranscriptionProvider::Deepgram => "deepgram",
-            TranscriptionProvider::DeepInfra => "deepinfra",
-            TranscriptionProvider::Groq => "groq",
-            TranscriptionProvider::AssemblyAI => "assemblyai",
-            TranscriptionProvider::Berget => "berget",
-            TranscriptionProvider::ElevenLabs => "elevenlabs",
-        }
+        self.id
     }
 
+    /// Returns the human-readable provider name (e.g. "OpenAI")
     pub fn name(&self) -> &'static str {
-        match self {
-            TranscriptionProvider::OpenAI => "OpenAI",
-            TranscriptionProvider::Deepgram => "Deepgram",
-            TranscriptionProvider::DeepInfra => "DeepInfra",
-            TranscriptionProvider::Groq => "Groq",
-            TranscriptionProvider::AssemblyAI => "AssemblyAI",
-            TranscriptionProvider::Berget => "Berget",
-            TranscriptionProvider::ElevenLabs => "ElevenLabs",
-        }
+        self.name
     }
 
+    /// Looks up a registered provider by its id
     pub fn from_id(id: &str) -> Option<Self> {
-        match id {
-            "openai" => Some(TranscriptionProvider::OpenAI),
-            "deepgram" => Some(TranscriptionProvider::Deepgram),
-            "deepinfra" => Some(TranscriptionProvider::DeepInfra),
-            "groq" => Some(TranscriptionProvider::Groq),
-            "assemblyai" => Some(TranscriptionProvider::AssemblyAI),
-            "berget" => Some(TranscriptionProvider::Berget),
-            "elevenlabs" => Some(TranscriptionProvider::ElevenLabs),
-            _ => None,
-        }
+        registry().get(id).map(|p| p.info())
+    }
+
+    /// Returns all registered providers
+    pub fn all() -> Vec<Self> {
+        registry().providers().map(|p| p.info()).collect()
     }
 
-    pub fn all() -> &'static [Self] {
-        &[
-            TranscriptionProvider::OpenAI,
-            TranscriptionProvider::Deepgram,
-            TranscriptionProvider::DeepInfra,
-            TranscriptionProvider::Groq,
-            TranscriptionProvider::AssemblyAI,
-            TranscriptionProvider::Berget,
-            TranscriptionProvider::ElevenLabs,
-        ]
+    /// Returns whether this provider needs an API key to be saved before use.
+    ///
+    /// Self-hosted servers often run without authentication, so the key is optional there.
+    pub fn requires_api_key(&self) -> bool {
+        registry()
+            .get(self.id)
+            .map(|p| p.capabilities().requires_api_key)
+            .unwrap_or(true)
     }
 }
@@ -0,0 +1,240 @@
+//! Registry of available transcription providers.
+//!
+//! The registry is the single source of truth for which providers and models exist.
+//! `ostt auth`, [`TranscriptionModel::all`] and [`transcribe`](super::transcribe) all read
+//! from the global registry, which holds the built-in providers. Separate registries can be
+//! built with [`ProviderRegistry::new`] to run transcriptions against test doubles.
+
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+use super::api::{self, KeywordSupport, Provider, TranscriptionConfig, TranscriptionResponse};
+use super::diarization;
+use super::model::TranscriptionModel;
+
+/// A collection of transcription providers, keyed by provider id.
+#[derive(Default)]
+pub struct ProviderRegistry {
+    providers: Vec<Arc<dyn Provider>>,
+}
+
+impl ProviderRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry containing every provider that ships with ostt.
+    pub fn with_builtin_providers() -> Self {
+        let mut registry = Self::new();
+        for provider in api::builtin_providers() {
+            registry.register(provider);
+        }
+        registry
+    }
+
+    /// Registers a provider, replacing any existing provider with the same id.
+    pub fn register(&mut self, provider: Arc<dyn Provider>) {
+        let id = provider.info().id();
+        match self.providers.iter().position(|p| p.info().id() == id) {
+            Some(index) => self.providers[index] = provider,
+            None => self.providers.push(provider),
+        }
+    }
+
+    /// Looks up a provider by its id.
+    pub fn get(&self, provider_id: &str) -> Option<&Arc<dyn Provider>> {
+        self.providers.iter().find(|p| p.info().id() == provider_id)
+    }
+
+    /// Returns all registered providers in registration order.
+    pub fn providers(&self) -> impl Iterator<Item = &Arc<dyn Provider>> {
+        self.providers.iter()
+    }
+
+    /// Returns the models of all registered providers.
+    pub fn models(&self) -> Vec<TranscriptionModel> {
+        self.providers.iter().flat_map(|p| p.models()).collect()
+    }
+
+    /// Looks up a model by its id across all registered providers.
+    pub fn find_model(&self, model_id: &str) -> Option<TranscriptionModel> {
+        self.models().into_iter().find(|m| m.id() == model_id)
+    }
+
+    /// Transcribes an audio file with the provider registered for the configured model.
+    ///
+    /// # Errors
+    /// - If no provider is registered for the model
+    /// - If the provider fails to transcribe the audio
+    pub async fn transcribe(
+        &self,
+        config: &TranscriptionConfig,
+        audio_path: &Path,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let provider_info = config.model.provider();
+        let provider = self.get(provider_info.id()).ok_or_else(|| {
+            anyhow::anyhow!(
+                "No transcription provider registered for '{}'",
+                provider_info.id()
+            )
+        })?;
+
+        tracing::info!(
+            "Transcribing with {} ({})",
+            provider_info.name(),
+            config.model.id()
+        );
+
+        let capabilities = provider.model_capabilities(&config.model);
+        capabilities.check(config, audio_path)?;
+
+        if !config.keywords.is_empty() && capabilities.keywords == KeywordSupport::None {
+            tracing::debug!(
+                "{} does not support keywords, ignoring: {:?}",
+                config.model.id(),
+                config.keywords
+            );
+        }
+
+        if config.diarize && !capabilities.diarization {
+            tracing::warn!(
+                "{} does not support speaker labels, transcribing without them",
+                config.model.id()
+            );
+        }
+
+        let response = provider.transcribe(config, audio_path).await?;
+        Ok(finish_response(config, response))
+    }
+}
+
+/// Applies the post-processing every transcription gets, wherever its text came from.
+///
+/// Groups speaker turns when diarization was requested, falls back to the requested
+/// language when the provider did not report one, and trims the text.
+pub fn finish_response(
+    config: &TranscriptionConfig,
+    mut response: TranscriptionResponse,
+) -> TranscriptionResponse {
+    if config.diarize {
+        diarization::apply_speaker_turns(&mut response);
+    }
+    if response.language.is_none() {
+        response.language = config.language.clone();
+    }
+    response.text = response.text.trim().to_string();
+    response
+}
+
+/// Returns the global registry of built-in providers.
+pub fn registry() -> &'static ProviderRegistry {
+    static REGISTRY: OnceLock<ProviderRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(ProviderRegistry::with_builtin_providers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcription::test_support::StubProvider;
+
+    fn fake() -> StubProvider {
+        StubProvider::new("fake", "  hello from the fake provider \n")
+    }
+
+    #[test]
+    fn builtin_registry_exposes_all_models() {
+        let registry = ProviderRegistry::with_builtin_providers();
+        assert!(registry.get("openai").is_some());
+        assert!(registry.get("custom").is_some());
+        assert!(registry.find_model("nova-3").is_some());
+        assert!(registry.find_model("groq-whisper-large-v3-turbo").is_some());
+        assert!(registry.find_model("does-not-exist").is_none());
+    }
+
+    #[test]
+    fn model_ids_are_unique() {
+        let models = ProviderRegistry::with_builtin_providers().models();
+        let mut ids: Vec<&str> = models.iter().map(|m| m.id()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), models.len());
+    }
+
+    #[test]
+    fn register_replaces_provider_with_same_id() {
+        let mut registry = ProviderRegistry::new();
+        registry.register(Arc::new(fake()));
+        registry.register(Arc::new(fake()));
+        assert_eq!(registry.providers().count(), 1);
+        assert_eq!(registry.models().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn transcribe_routes_to_registered_test_double() {
+        let mut registry = ProviderRegistry::new();
+        registry.register(Arc::new(fake()));
+
+        let response = registry
+            .transcribe(&fake().config(), Path::new("unused.mp3"))
+            .await
+            .unwrap();
+        assert_eq!(response.text, "hello from the fake provider");
+    }
+
+    #[test]
+    fn finished_responses_carry_the_requested_language() {
+        let mut config = fake().config();
+        config.language = Some("sv".to_string());
+
+        let response = finish_response(&config, TranscriptionResponse::new(" hej \n"));
+        assert_eq!(response.text, "hej");
+        assert_eq!(response.language.as_deref(), Some("sv"));
+
+        let mut detected = TranscriptionResponse::new("hello");
+        detected.language = Some("en".to_string());
+        let response = finish_response(&config, detected);
+        assert_eq!(response.language.as_deref(), Some("en"));
+    }
+
+    #[tokio::test]
+    async fn transcribe_fails_for_unregistered_provider() {
+        let registry = ProviderRegistry::new();
+        let err = registry
+            .transcribe(&fake().config(), Path::new("unused.mp3"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("fake"));
+    }
+
+    #[tokio::test]
+    async fn translation_fails_for_models_without_it() {
+        let mut registry = ProviderRegistry::new();
+        registry.register(Arc::new(fake()));
+        let mut config = fake().config();
+        config.translate = true;
+
+        let err = registry
+            .transcribe(&config, Path::new("unused.mp3"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("cannot translate"));
+    }
+
+    #[test]
+    fn only_whisper_models_translate() {
+        let registry = ProviderRegistry::with_builtin_providers();
+        let translates = |model_id: &str| {
+            let model = registry.find_model(model_id).unwrap();
+            registry
+                .get(model.provider().id())
+                .unwrap()
+                .supports_translation(&model)
+        };
+        assert!(translates("whisper"));
+        assert!(translates("groq-whisper-large-v3"));
+        assert!(!translates("gpt-4o-transcribe"));
+        assert!(!translates("groq-whisper-large-v3-turbo"));
+        assert!(!translates("nova-3"));
+    }
+}
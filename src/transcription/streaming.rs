@@ -0,0 +1,309 @@
+//! Live (streaming) transcription over WebSocket.
+//!
+//! While recording, audio from [`AudioRecorder`](crate::recording::AudioRecorder) is forwarded
+//! to providers that support live recognition. The provider sends back interim results, which
+//! may still change, and final results for each finished utterance. [`LiveSession`] runs the
+//! connection in the background and keeps a [`LiveTranscript`] that the recording UI renders
+//! under the visualization. When recording stops, the session asks the provider to flush its
+//! remaining results, so the transcript is ready right away instead of after a full upload.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+
+use super::api::{Provider, TranscriptionConfig};
+
+/// Minimum amount of audio sent per WebSocket message, in milliseconds.
+///
+/// The audio callback delivers ~10ms buffers; AssemblyAI rejects chunks shorter than 50ms.
+const MIN_CHUNK_MS: u32 = 100;
+
+/// Interval for keep-alive messages while no audio is sent (e.g. while paused)
+const KEEPALIVE_INTERVAL_SECS: u64 = 5;
+
+/// Maximum time to wait for the provider to flush its final results after recording stops
+const FINALIZE_TIMEOUT_SECS: u64 = 10;
+
+/// WebSocket connection details for a live transcription session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveRequest {
+    /// The `wss://` URL including query parameters
+    pub url: String,
+    /// Extra HTTP headers sent with the handshake (e.g. authorization)
+    pub headers: Vec<(&'static str, String)>,
+}
+
+/// A transcription result received from a live session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiveUpdate {
+    /// Text of the utterance in progress; replaced by the next update
+    Interim(String),
+    /// Finished utterance that will not change anymore
+    Final(String),
+}
+
+/// Transcript accumulated from the updates of a live session.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LiveTranscript {
+    finals: Vec<String>,
+    interim: String,
+}
+
+impl LiveTranscript {
+    /// Applies an update received from the provider.
+    pub fn apply(&mut self, update: LiveUpdate) {
+        match update {
+            LiveUpdate::Interim(text) => self.interim = text.trim().to_string(),
+            LiveUpdate::Final(text) => {
+                let text = text.trim();
+                if !text.is_empty() {
+                    self.finals.push(text.to_string());
+                }
+                self.interim.clear();
+            }
+        }
+    }
+
+    /// Returns the finished utterances joined into a single text.
+    pub fn final_text(&self) -> String {
+        self.finals.join(" ")
+    }
+
+    /// Returns the text of the utterance in progress.
+    pub fn interim_text(&self) -> &str {
+        &self.interim
+    }
+}
+
+/// State shared between the connection task and the UI.
+#[derive(Debug, Default)]
+struct LiveState {
+    transcript: LiveTranscript,
+    error: Option<String>,
+}
+
+/// A running live transcription session.
+///
+/// Dropping the session closes the connection without waiting for final results.
+pub struct LiveSession {
+    state: Arc<Mutex<LiveState>>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl LiveSession {
+    /// Starts a live session in the background.
+    ///
+    /// Audio chunks (mono i16 PCM at `sample_rate`) are read from `audio` until the sender is
+    /// dropped, which signals the provider to flush its final results.
+    ///
+    /// # Errors
+    /// - If the provider does not support live transcription
+    pub fn start(
+        provider: Arc<dyn Provider>,
+        config: &TranscriptionConfig,
+        sample_rate: u32,
+        audio: UnboundedReceiver<Vec<i16>>,
+    ) -> anyhow::Result<Self> {
+        let request = provider.live_request(config, sample_rate).ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} does not support live transcription",
+                provider.info().name()
+            )
+        })?;
+
+        let state = Arc::new(Mutex::new(LiveState::default()));
+        let task_state = Arc::clone(&state);
+        let task = tokio::spawn(async move {
+            let name = provider.info().name();
+            if let Err(e) = run(provider, request, sample_rate, audio, &task_state).await {
+                tracing::warn!("{} live transcription failed: {}", name, e);
+                task_state.lock().unwrap().error = Some(e.to_string());
+            }
+        });
+
+        Ok(Self {
+            state,
+            task: Some(task),
+        })
+    }
+
+    /// Returns a snapshot of the transcript received so far.
+    pub fn transcript(&self) -> LiveTranscript {
+        self.state.lock().unwrap().transcript.clone()
+    }
+
+    /// Waits for the provider to flush its final results and returns the transcript.
+    ///
+    /// Must be called after the audio sender has been dropped (i.e. recording stopped).
+    ///
+    /// # Errors
+    /// - If the connection failed at any point
+    /// - If the provider does not finish within the timeout
+    pub async fn finish(mut self) -> anyhow::Result<String> {
+        if let Some(task) = self.task.take() {
+            let timeout = Duration::from_secs(FINALIZE_TIMEOUT_SECS);
+            if tokio::time::timeout(timeout, task).await.is_err() {
+                return Err(anyhow::anyhow!(
+                    "Live transcription did not finish within {FINALIZE_TIMEOUT_SECS} seconds"
+                ));
+            }
+        }
+
+        let state = self.state.lock().unwrap();
+        if let Some(error) = &state.error {
+            return Err(anyhow::anyhow!("Live transcription failed: {error}"));
+        }
+        let mut transcript = state.transcript.clone();
+        // Keep any trailing interim text the provider never finalized
+        let interim = transcript.interim_text().to_string();
+        transcript.apply(LiveUpdate::Final(interim));
+        Ok(transcript.final_text())
+    }
+}
+
+impl Drop for LiveSession {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+    }
+}
+
+/// Connects to the provider, streams audio and collects results until the server closes.
+async fn run(
+    provider: Arc<dyn Provider>,
+    request: LiveRequest,
+    sample_rate: u32,
+    mut audio: UnboundedReceiver<Vec<i16>>,
+    state: &Mutex<LiveState>,
+) -> anyhow::Result<()> {
+    let name = provider.info().name();
+
+    let mut ws_request = request
+        .url
+        .as_str()
+        .into_client_request()
+        .map_err(|e| anyhow::anyhow!("Invalid {name} live URL: {e}"))?;
+    for (header, value) in &request.headers {
+        let value = HeaderValue::from_str(value)
+            .map_err(|e| anyhow::anyhow!("Invalid {name} live header {header}: {e}"))?;
+        ws_request.headers_mut().insert(*header, value);
+    }
+
+    tracing::debug!("Connecting to {} live transcription", name);
+    let (socket, _) = tokio_tungstenite::connect_async(ws_request)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to connect to {name} live transcription: {e}"))?;
+    tracing::info!("{} live transcription connected", name);
+
+    let (mut sink, mut stream) = socket.split();
+
+    let writer = async {
+        let min_chunk = (sample_rate * MIN_CHUNK_MS / 1000).max(1) as usize;
+        let mut buffer: Vec<i16> = Vec::with_capacity(min_chunk * 2);
+        let mut keepalive = tokio::time::interval(Duration::from_secs(KEEPALIVE_INTERVAL_SECS));
+        keepalive.tick().await;
+
+        loop {
+            tokio::select! {
+                chunk = audio.recv() => match chunk {
+                    Some(samples) => {
+                        buffer.extend_from_slice(&samples);
+                        if buffer.len() >= min_chunk {
+                            sink.send(Message::Binary(pcm_bytes(&buffer).into())).await?;
+                            buffer.clear();
+                            keepalive.reset();
+                        }
+                    }
+                    None => {
+                        if !buffer.is_empty() {
+                            sink.send(Message::Binary(pcm_bytes(&buffer).into())).await?;
+                        }
+                        if let Some(message) = provider.live_close_message() {
+                            sink.send(Message::Text(message.into())).await?;
+                        }
+                        tracing::debug!("{} live audio finished, waiting for final results", name);
+                        break;
+                    }
+                },
+                _ = keepalive.tick() => {
+                    if let Some(message) = provider.live_keepalive_message() {
+                        sink.send(Message::Text(message.into())).await?;
+                    }
+                }
+            }
+        }
+        Ok::<(), tokio_tungstenite::tungstenite::Error>(())
+    };
+
+    let reader = async {
+        while let Some(message) = stream.next().await {
+            match message? {
+                Message::Text(text) => {
+                    if let Some(update) = provider.parse_live_message(text.as_str())? {
+                        tracing::trace!("{} live update: {:?}", name, update);
+                        state.lock().unwrap().transcript.apply(update);
+                    }
+                }
+                Message::Close(frame) => {
+                    tracing::debug!("{} live connection closed: {:?}", name, frame);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        Ok::<(), anyhow::Error>(())
+    };
+
+    let (write_result, read_result) = tokio::join!(writer, reader);
+    read_result?;
+    write_result.map_err(|e| anyhow::anyhow!("{name} live connection error: {e}"))?;
+    Ok(())
+}
+
+/// Encodes samples as little-endian 16-bit PCM.
+fn pcm_bytes(samples: &[i16]) -> Vec<u8> {
+    samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interim_text_is_replaced_and_cleared_by_final() {
+        let mut transcript = LiveTranscript::default();
+        transcript.apply(LiveUpdate::Interim("hello".to_string()));
+        transcript.apply(LiveUpdate::Interim("hello wor".to_string()));
+        assert_eq!(transcript.interim_text(), "hello wor");
+        assert_eq!(transcript.final_text(), "");
+
+        transcript.apply(LiveUpdate::Final(" hello world ".to_string()));
+        transcript.apply(LiveUpdate::Interim("how are".to_string()));
+        assert_eq!(transcript.final_text(), "hello world");
+        assert_eq!(transcript.interim_text(), "how are");
+
+        transcript.apply(LiveUpdate::Final("how are you".to_string()));
+        assert_eq!(transcript.final_text(), "hello world how are you");
+        assert_eq!(transcript.interim_text(), "");
+    }
+
+    #[test]
+    fn empty_final_results_are_ignored() {
+        let mut transcript = LiveTranscript::default();
+        transcript.apply(LiveUpdate::Final("one".to_string()));
+        transcript.apply(LiveUpdate::Final("  ".to_string()));
+        assert_eq!(transcript.final_text(), "one");
+    }
+
+    #[test]
+    fn pcm_bytes_are_little_endian() {
+        assert_eq!(pcm_bytes(&[1, -2]), vec![0x01, 0x00, 0xFE, 0xFF]);
+    }
+}
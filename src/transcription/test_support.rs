@@ -0,0 +1,100 @@
+//! Test doubles shared by the transcription unit tests.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::api::{AudioUpload, Provider, TranscriptionConfig, TranscriptionResponse};
+use super::{TranscriptionModel, TranscriptionProvider};
+use crate::config::ProvidersConfig;
+
+/// Reply to a transcription, given the request number starting at 1
+type Reply = dyn Fn(usize) -> anyhow::Result<TranscriptionResponse> + Send + Sync;
+
+/// Provider that answers transcriptions without touching the network.
+///
+/// Serves a single model, `{id}-1`, and counts the transcriptions it is asked for.
+pub(crate) struct StubProvider {
+    info: TranscriptionProvider,
+    reply: Box<Reply>,
+    requests: Arc<AtomicUsize>,
+}
+
+impl StubProvider {
+    /// Creates a provider that transcribes every file as `text`.
+    pub fn new(id: &'static str, text: &'static str) -> Self {
+        Self::replying(id, move |_| Ok(TranscriptionResponse::new(text)))
+    }
+
+    /// Creates a provider that answers each transcription with `reply`.
+    pub fn replying(
+        id: &'static str,
+        reply: impl Fn(usize) -> anyhow::Result<TranscriptionResponse> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            info: TranscriptionProvider::new(id, id),
+            reply: Box::new(reply),
+            requests: Arc::default(),
+        }
+    }
+
+    /// Returns the counter of transcriptions, which stays readable after registration.
+    pub fn requests(&self) -> Arc<AtomicUsize> {
+        self.requests.clone()
+    }
+
+    /// Returns a request configuration for the provider's model.
+    pub fn config(&self) -> TranscriptionConfig {
+        TranscriptionConfig::new(
+            self.models().remove(0),
+            String::new(),
+            Vec::new(),
+            ProvidersConfig::default(),
+        )
+    }
+}
+
+#[async_trait]
+impl Provider for StubProvider {
+    fn info(&self) -> TranscriptionProvider {
+        self.info
+    }
+
+    fn models(&self) -> Vec<TranscriptionModel> {
+        let id = format!("{}-1", self.info.id());
+        vec![TranscriptionModel::new(
+            self.info,
+            id.clone(),
+            id,
+            self.info.name(),
+        )]
+    }
+
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        _config: &TranscriptionConfig,
+        _audio: AudioUpload,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        Ok(client.post("http://localhost.invalid"))
+    }
+
+    fn parse_response(
+        &self,
+        _config: &TranscriptionConfig,
+        body: &str,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        Ok(TranscriptionResponse::new(body))
+    }
+
+    async fn transcribe(
+        &self,
+        _config: &TranscriptionConfig,
+        _audio_path: &Path,
+    ) -> anyhow::Result<TranscriptionResponse> {
+        let count = self.requests.fetch_add(1, Ordering::SeqCst) + 1;
+        (self.reply)(count)
+    }
+}
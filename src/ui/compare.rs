@@ -0,0 +1,328 @@
+//! Side-by-side comparison of transcripts from several models.
+//!
+//! Shows one column per model with its latency, estimated cost and transcript. Words that
+//! differ from the reference column are highlighted; Tab picks another reference.
+
+use crossterm::{
+    event::{self, Event, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+};
+use std::io::{self, Stdout};
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::transcription::{diff, pricing};
+
+const BG: Color = Color::Rgb(0, 0, 0);
+const FG: Color = Color::Rgb(255, 255, 255);
+const DIM_FG: Color = Color::Rgb(100, 100, 100);
+const DIFF_FG: Color = Color::Rgb(255, 200, 0);
+const ERROR_FG: Color = Color::Rgb(255, 80, 80);
+const REFERENCE_FG: Color = Color::Rgb(0, 200, 255);
+
+/// Result of one model in a comparison.
+#[derive(Debug, Clone)]
+pub enum ComparisonResult {
+    /// The model is still transcribing
+    Pending,
+    /// The model finished transcribing
+    Done {
+        /// The transcript
+        text: String,
+        /// Time from sending the audio to receiving the transcript
+        latency: Duration,
+        /// Estimated cost in USD, if the model's price is known
+        cost: Option<f64>,
+    },
+    /// The transcription failed
+    Failed(String),
+}
+
+/// Interactive viewer that fills in model results as they arrive.
+pub struct CompareViewer {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    title: String,
+    model_ids: Vec<String>,
+    results: Vec<ComparisonResult>,
+    reference: Option<usize>,
+    scroll: u16,
+    cleaned_up: bool,
+}
+
+impl CompareViewer {
+    /// Creates a viewer with a pending column for each model and enters the alternate screen.
+    ///
+    /// # Errors
+    /// - If the terminal cannot be initialized
+    pub fn new(title: String, model_ids: Vec<String>) -> anyhow::Result<Self> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+        let results = vec![ComparisonResult::Pending; model_ids.len()];
+        Ok(Self {
+            terminal,
+            title,
+            model_ids,
+            results,
+            reference: None,
+            scroll: 0,
+            cleaned_up: false,
+        })
+    }
+
+    /// Runs the viewer until the user exits, updating columns as `(index, result)` pairs
+    /// arrive on `updates`.
+    ///
+    /// # Errors
+    /// - If terminal rendering or input handling fails
+    pub fn run(
+        &mut self,
+        updates: &mut UnboundedReceiver<(usize, ComparisonResult)>,
+    ) -> anyhow::Result<()> {
+        loop {
+            while let Ok((index, result)) = updates.try_recv() {
+                if self.reference.is_none() && matches!(result, ComparisonResult::Done { .. }) {
+                    self.reference = Some(index);
+                }
+                self.results[index] = result;
+            }
+
+            self.draw()?;
+
+            if event::poll(Duration::from_millis(50))? {
+                if let Event::Key(key) = event::read()? {
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => break,
+                        KeyCode::Tab => self.next_reference(),
+                        KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+                        KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+                        KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                        KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        self.cleanup()
+    }
+
+    /// Makes the next finished column the reference for highlighting differences.
+    fn next_reference(&mut self) {
+        let count = self.results.len();
+        let start = self.reference.map_or(0, |index| index + 1);
+        self.reference = (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|index| matches!(self.results[*index], ComparisonResult::Done { .. }));
+    }
+
+    /// Renders the header, one column per model and the help footer.
+    fn draw(&mut self) -> anyhow::Result<()> {
+        let reference_words: Option<Vec<String>> =
+            self.reference.and_then(|index| match &self.results[index] {
+                ComparisonResult::Done { text, .. } => {
+                    Some(diff::words(text).into_iter().map(str::to_string).collect())
+                }
+                _ => None,
+            });
+        let title = self.title.clone();
+        let model_ids = &self.model_ids;
+        let results = &self.results;
+        let reference = self.reference;
+        let scroll = self.scroll;
+
+        self.terminal.draw(|frame| {
+            let area = frame.area();
+            let padding_block = Block::default()
+                .padding(Padding::uniform(1))
+                .style(Style::default().bg(BG));
+            let inner_area = padding_block.inner(area);
+            frame.render_widget(padding_block, area);
+
+            let [header_area, columns_area, help_area] = Layout::vertical([
+                Constraint::Length(2),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .areas(inner_area);
+
+            frame.render_widget(
+                Paragraph::new(title).style(Style::default().fg(FG)),
+                header_area,
+            );
+
+            let column_areas = Layout::horizontal(
+                results
+                    .iter()
+                    .map(|_| Constraint::Ratio(1, results.len() as u32)),
+            )
+            .split(columns_area);
+
+            let reference_words: Option<Vec<&str>> = reference_words
+                .as_ref()
+                .map(|words| words.iter().map(String::as_str).collect());
+            for (index, result) in results.iter().enumerate() {
+                let is_reference = reference == Some(index);
+                let mut block_title = format!(" {} ", model_ids[index]);
+                if is_reference {
+                    block_title.push_str("(reference) ");
+                }
+                let block = Block::default()
+                    .title(block_title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(if is_reference {
+                        REFERENCE_FG
+                    } else {
+                        DIM_FG
+                    }))
+                    .padding(Padding::horizontal(1));
+
+                let lines = column_lines(
+                    result,
+                    reference_words.as_deref().filter(|_| !is_reference),
+                    reference.map(|index| model_ids[index].as_str()),
+                );
+                let paragraph = Paragraph::new(lines)
+                    .block(block)
+                    .wrap(Wrap { trim: true })
+                    .scroll((scroll, 0));
+                frame.render_widget(paragraph, column_areas[index]);
+            }
+
+            frame.render_widget(
+                Paragraph::new("tab change reference, ↑↓ scroll, esc/q exit")
+                    .style(Style::default().fg(DIM_FG))
+                    .alignment(Alignment::Center),
+                help_area,
+            );
+        })?;
+        Ok(())
+    }
+
+    /// Restores the terminal.
+    ///
+    /// # Errors
+    /// - If the terminal mode cannot be restored
+    pub fn cleanup(&mut self) -> anyhow::Result<()> {
+        if self.cleaned_up {
+            return Ok(());
+        }
+        self.cleaned_up = true;
+        disable_raw_mode()?;
+        execute!(self.terminal.backend_mut(), LeaveAlternateScreen)?;
+        self.terminal.show_cursor()?;
+        Ok(())
+    }
+}
+
+impl Drop for CompareViewer {
+    fn drop(&mut self) {
+        let _ = self.cleanup();
+    }
+}
+
+/// Builds the text of one column: a stats line, a blank line and the transcript.
+///
+/// When `reference` is given, words that are not in the reference transcript are
+/// highlighted and the stats include the share of matching words.
+fn column_lines<'a>(
+    result: &'a ComparisonResult,
+    reference: Option<&[&str]>,
+    reference_id: Option<&str>,
+) -> Vec<Line<'a>> {
+    match result {
+        ComparisonResult::Pending => {
+            vec![Line::styled("Transcribing...", Style::default().fg(DIM_FG))]
+        }
+        ComparisonResult::Failed(message) => {
+            vec![Line::styled(
+                message.as_str(),
+                Style::default().fg(ERROR_FG),
+            )]
+        }
+        ComparisonResult::Done {
+            text,
+            latency,
+            cost,
+        } => {
+            let words = diff::words(text);
+            let cost = cost.map_or_else(|| "cost n/a".to_string(), pricing::format_cost);
+            let mut stats = vec![Line::styled(
+                format!(
+                    "{:.1}s · {cost} · {} words",
+                    latency.as_secs_f64(),
+                    words.len()
+                ),
+                Style::default().fg(DIM_FG),
+            )];
+
+            let matches = match reference {
+                Some(reference) => {
+                    let similarity = diff::similarity(reference, &words);
+                    stats.push(Line::styled(
+                        format!(
+                            "{:.0}% match with {}",
+                            similarity * 100.0,
+                            reference_id.unwrap_or("reference")
+                        ),
+                        Style::default().fg(DIM_FG),
+                    ));
+                    diff::matching_words(reference, &words)
+                }
+                None => vec![true; words.len()],
+            };
+
+            let mut spans = Vec::with_capacity(words.len() * 2);
+            for (index, (word, matched)) in words.iter().zip(matches).enumerate() {
+                if index > 0 {
+                    spans.push(Span::raw(" "));
+                }
+                let style = if matched {
+                    Style::default().fg(FG)
+                } else {
+                    Style::default().fg(DIFF_FG).add_modifier(Modifier::BOLD)
+                };
+                spans.push(Span::styled(*word, style));
+            }
+
+            stats.push(Line::default());
+            stats.push(Line::from(spans));
+            stats
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn differing_words_are_highlighted() {
+        let result = ComparisonResult::Done {
+            text: "the brawn fox".to_string(),
+            latency: Duration::from_millis(1500),
+            cost: Some(0.006),
+        };
+        let reference = ["the", "brown", "fox"];
+        let lines = column_lines(&result, Some(&reference), Some("whisper"));
+
+        assert_eq!(lines[0].to_string(), "1.5s · $0.0060 · 3 words");
+        assert_eq!(lines[1].to_string(), "67% match with whisper");
+        let transcript = &lines[3];
+        assert_eq!(transcript.to_string(), "the brawn fox");
+        let highlighted: Vec<&str> = transcript
+            .spans
+            .iter()
+            .filter(|span| span.style.fg == Some(DIFF_FG))
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(highlighted, ["brawn"]);
+    }
+}
@@ -3,6 +3,7 @@
 //! Contains reusable UI widgets and components that are used
 //! by multiple features throughout the application.
 
+pub mod compare;
 pub mod error;
 
 pub use error::ErrorScreen;
@@ -0,0 +1,712 @@
+//! Runs every built-in provider against a local stub HTTP server.
+//!
+//! Each provider's base URL is pointed at the stub through `[providers.base_urls]`, so the
+//! real request building, error mapping and response parsing are exercised without
+//! network access or API keys.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use ostt::config::{NetworkConfig, ProvidersConfig};
+use ostt::transcription::error::exit_code;
+use ostt::transcription::{
+    discovery, registry, TranscriptionConfig, TranscriptionError, TranscriptionModel,
+    TranscriptionResponse,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// A canned response for one method and path (query string ignored).
+#[derive(Clone)]
+struct Route {
+    method: &'static str,
+    path: String,
+    status: u16,
+    body: String,
+    headers: Vec<(&'static str, &'static str)>,
+}
+
+impl Route {
+    fn new(method: &'static str, path: &str, status: u16, body: &str) -> Self {
+        Self {
+            method,
+            path: path.to_string(),
+            status,
+            body: body.to_string(),
+            headers: Vec::new(),
+        }
+    }
+
+    fn with_header(mut self, name: &'static str, value: &'static str) -> Self {
+        self.headers.push((name, value));
+        self
+    }
+}
+
+/// A request received by the stub server.
+#[derive(Debug, Clone)]
+struct Recorded {
+    method: String,
+    /// Path including the query string, or the absolute URL for proxied requests
+    target: String,
+    /// Header lines, lowercased
+    headers: Vec<String>,
+}
+
+impl Recorded {
+    /// Returns the request path without scheme, host or query string.
+    fn path(&self) -> &str {
+        let target = match self.target.strip_prefix("http://") {
+            Some(rest) => &rest[rest.find('/').unwrap_or(rest.len())..],
+            None => &self.target,
+        };
+        target.split('?').next().unwrap_or_default()
+    }
+}
+
+/// Minimal HTTP/1.1 server that answers each connection with one canned response.
+struct StubServer {
+    url: String,
+    requests: Arc<Mutex<Vec<Recorded>>>,
+}
+
+impl StubServer {
+    async fn start(routes: Vec<Route>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let routes = routes.clone();
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let Some(request) = read_request(&mut stream).await else {
+                        return;
+                    };
+                    let route = routes
+                        .iter()
+                        .find(|route| {
+                            route.method == request.method && route.path == request.path()
+                        })
+                        .cloned()
+                        .unwrap_or_else(|| Route::new("", "", 404, r#"{"error":"no route"}"#));
+                    recorded.lock().unwrap().push(request);
+
+                    let mut response = format!(
+                        "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+                        route.status,
+                        route.body.len()
+                    );
+                    for (name, value) in &route.headers {
+                        response.push_str(&format!("{name}: {value}\r\n"));
+                    }
+                    response.push_str("\r\n");
+                    response.push_str(&route.body);
+                    stream.write_all(response.as_bytes()).await.ok();
+                    stream.shutdown().await.ok();
+                });
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    fn requests(&self) -> Vec<Recorded> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Reads the request line, headers and body (by length or chunked) of one request.
+async fn read_request(stream: &mut tokio::net::TcpStream) -> Option<Recorded> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let header_end = loop {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(position) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break position + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.split("\r\n").filter(|line| !line.is_empty());
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?.to_string();
+    let headers: Vec<String> = lines.map(str::to_lowercase).collect();
+
+    let content_length = headers.iter().find_map(|line| {
+        line.strip_prefix("content-length:")
+            .and_then(|value| value.trim().parse::<usize>().ok())
+    });
+    let chunked = headers
+        .iter()
+        .any(|line| line.starts_with("transfer-encoding:") && line.contains("chunked"));
+
+    // Drain the body so the client sees the whole request accepted
+    loop {
+        let body = &buffer[header_end..];
+        let complete = match content_length {
+            Some(length) => body.len() >= length,
+            None if chunked => body.ends_with(b"0\r\n\r\n"),
+            None => true,
+        };
+        if complete {
+            break;
+        }
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    Some(Recorded {
+        method,
+        target,
+        headers,
+    })
+}
+
+/// How one provider is exercised against the stub.
+struct Case {
+    /// Model used for the request
+    model_id: &'static str,
+    /// Routes the flow needs besides the transcription endpoint (uploads, polling)
+    extra_routes: Vec<Route>,
+    /// Method and path of the endpoint that returns the transcript or the error
+    method: &'static str,
+    path: &'static str,
+    /// Successful response body of that endpoint
+    body: &'static str,
+    /// Header line expected on the transcription request, lowercased
+    auth_header: &'static str,
+}
+
+fn cases() -> Vec<Case> {
+    vec![
+        Case {
+            model_id: "whisper",
+            extra_routes: Vec::new(),
+            method: "POST",
+            path: "/audio/transcriptions",
+            body: r#"{"text":"hello world","language":"english","segments":[{"text":"hello world","start":0.0,"end":1.5}]}"#,
+            auth_header: "authorization: bearer test-key",
+        },
+        Case {
+            model_id: "groq-whisper-large-v3",
+            extra_routes: Vec::new(),
+            method: "POST",
+            path: "/audio/transcriptions",
+            body: r#"{"text":"hello world"}"#,
+            auth_header: "authorization: bearer test-key",
+        },
+        Case {
+            model_id: "nova-3",
+            extra_routes: Vec::new(),
+            method: "POST",
+            path: "/listen",
+            body: r#"{"results":{"channels":[{"alternatives":[{"transcript":"hello world","words":[{"word":"hello","start":0.0,"end":0.5},{"word":"world","start":0.6,"end":1.0}]}]}]}}"#,
+            auth_header: "authorization: token test-key",
+        },
+        Case {
+            model_id: "deepinfra-whisper-large-v3",
+            extra_routes: Vec::new(),
+            method: "POST",
+            path: "/inference/openai/whisper-large-v3",
+            body: r#"{"text":"hello world","language":"en"}"#,
+            auth_header: "authorization: bearer test-key",
+        },
+        Case {
+            model_id: "assemblyai-universal-3-pro",
+            extra_routes: vec![
+                Route::new(
+                    "POST",
+                    "/upload",
+                    200,
+                    r#"{"upload_url":"https://cdn.example/audio"}"#,
+                ),
+                Route::new(
+                    "GET",
+                    "/transcript/t-1",
+                    200,
+                    r#"{"id":"t-1","status":"completed","text":"hello world","language_code":"en"}"#,
+                ),
+            ],
+            method: "POST",
+            path: "/transcript",
+            body: r#"{"id":"t-1","status":"queued"}"#,
+            auth_header: "authorization: test-key",
+        },
+        Case {
+            model_id: "berget-whisper-kb-large",
+            extra_routes: Vec::new(),
+            method: "POST",
+            path: "/audio/transcriptions",
+            body: r#"{"text":"hello world"}"#,
+            auth_header: "authorization: bearer test-key",
+        },
+        Case {
+            model_id: "elevenlabs-scribe-v2",
+            extra_routes: Vec::new(),
+            method: "POST",
+            path: "/speech-to-text",
+            body: r#"{"text":"hello world","language_code":"eng","words":[{"text":"hello","type":"word","start":0.0,"end":0.5}]}"#,
+            auth_header: "xi-api-key: test-key",
+        },
+        Case {
+            model_id: "mistral-voxtral-mini-latest",
+            extra_routes: Vec::new(),
+            method: "POST",
+            path: "/audio/transcriptions",
+            body: r#"{"model":"voxtral-mini-2507","text":"hello world","language":"en","segments":[{"text":"hello world","start":0.0,"end":1.5}]}"#,
+            auth_header: "authorization: bearer test-key",
+        },
+        Case {
+            model_id: "speechmatics-enhanced",
+            extra_routes: vec![
+                Route::new(
+                    "GET",
+                    "/jobs/j-1",
+                    200,
+                    r#"{"job":{"id":"j-1","status":"done"}}"#,
+                ),
+                Route::new(
+                    "GET",
+                    "/jobs/j-1/transcript",
+                    200,
+                    r#"{"results":[{"type":"word","start_time":0.0,"end_time":0.5,"alternatives":[{"content":"hello","language":"en"}]},{"type":"word","start_time":0.6,"end_time":1.0,"alternatives":[{"content":"world","language":"en"}]}]}"#,
+                ),
+            ],
+            method: "POST",
+            path: "/jobs/",
+            body: r#"{"id":"j-1"}"#,
+            auth_header: "authorization: bearer test-key",
+        },
+        Case {
+            model_id: "gladia-solaria",
+            extra_routes: vec![
+                Route::new(
+                    "POST",
+                    "/upload",
+                    200,
+                    r#"{"audio_url":"https://api.gladia.io/file/abc"}"#,
+                ),
+                Route::new(
+                    "GET",
+                    "/pre-recorded/g-1",
+                    200,
+                    r#"{"id":"g-1","status":"done","result":{"transcription":{"full_transcript":"hello world","languages":["en"],"utterances":[]}}}"#,
+                ),
+            ],
+            method: "POST",
+            path: "/pre-recorded",
+            body: r#"{"id":"g-1","result_url":"https://api.gladia.io/v2/pre-recorded/g-1"}"#,
+            auth_header: "x-gladia-key: test-key",
+        },
+        Case {
+            model_id: "azure-gpt-4o-transcribe",
+            extra_routes: Vec::new(),
+            method: "POST",
+            path: "/openai/deployments/gpt-4o-transcribe/audio/transcriptions",
+            body: r#"{"text":"hello world"}"#,
+            auth_header: "api-key: test-key",
+        },
+        Case {
+            model_id: "custom",
+            extra_routes: Vec::new(),
+            method: "POST",
+            path: "/audio/transcriptions",
+            body: r#"{"text":"hello world"}"#,
+            auth_header: "authorization: bearer test-key",
+        },
+    ]
+}
+
+/// Writes a small placeholder audio file; providers upload it without decoding.
+fn audio_file() -> PathBuf {
+    let path = std::env::temp_dir().join(format!("ostt_stub_audio_{}.mp3", std::process::id()));
+    std::fs::write(&path, b"ID3 not really audio").unwrap();
+    path
+}
+
+/// Transcribes with the case's model against a stub serving `routes`.
+async fn run(
+    case: &Case,
+    routes: Vec<Route>,
+) -> (anyhow::Result<TranscriptionResponse>, Vec<Recorded>) {
+    let server = StubServer::start(routes).await;
+
+    let model = TranscriptionModel::from_id(case.model_id).unwrap();
+    let provider_id = model.provider().id().to_string();
+    let mut providers = ProvidersConfig::default();
+    providers
+        .base_urls
+        .insert(provider_id.clone(), server.url.clone());
+    let config = TranscriptionConfig::new(model, "test-key".to_string(), Vec::new(), providers);
+
+    let provider = registry().get(&provider_id).unwrap().clone();
+    let result = provider.transcribe(&config, &audio_file()).await;
+    (result, server.requests())
+}
+
+/// Runs every case with the transcription endpoint answering `status` and `body`.
+async fn run_all_with_response(
+    status: u16,
+    body: &str,
+    headers: &[(&'static str, &'static str)],
+) -> Vec<(&'static str, anyhow::Error)> {
+    let mut errors = Vec::new();
+    for case in cases() {
+        let mut route = Route::new(case.method, case.path, status, body);
+        for (name, value) in headers {
+            route = route.with_header(name, value);
+        }
+        let mut routes = case.extra_routes.clone();
+        routes.push(route);
+
+        let (result, _) = run(&case, routes).await;
+        match result {
+            Ok(response) => panic!("{}: expected an error, got {response:?}", case.model_id),
+            Err(e) => errors.push((case.model_id, e)),
+        }
+    }
+    errors
+}
+
+#[tokio::test]
+async fn successful_responses_are_parsed() {
+    for case in cases() {
+        let mut routes = case.extra_routes.clone();
+        routes.push(Route::new(case.method, case.path, 200, case.body));
+
+        let (result, requests) = run(&case, routes).await;
+        let response = result.unwrap_or_else(|e| panic!("{}: {e:#}", case.model_id));
+        assert_eq!(response.text.trim(), "hello world", "{}", case.model_id);
+
+        let request = requests
+            .iter()
+            .find(|r| r.method == case.method && r.path() == case.path)
+            .unwrap_or_else(|| panic!("{}: endpoint not called", case.model_id));
+        assert!(
+            request.headers.iter().any(|h| h == case.auth_header),
+            "{}: missing '{}' in {:?}",
+            case.model_id,
+            case.auth_header,
+            request.headers
+        );
+        assert!(
+            request
+                .headers
+                .iter()
+                .any(|h| h.starts_with("user-agent: ostt/")),
+            "{}",
+            case.model_id
+        );
+    }
+}
+
+#[tokio::test]
+async fn timings_and_language_are_read() {
+    let case = cases()
+        .into_iter()
+        .find(|c| c.model_id == "nova-3")
+        .unwrap();
+    let (result, requests) = run(&case, vec![Route::new("POST", "/listen", 200, case.body)]).await;
+    let response = result.unwrap();
+    assert_eq!(response.words.len(), 2);
+    assert_eq!(response.words[1].end, 1.0);
+    assert!(requests[0].target.starts_with("/listen?model=nova-3"));
+
+    let case = cases()
+        .into_iter()
+        .find(|c| c.model_id == "assemblyai-universal-3-pro")
+        .unwrap();
+    let mut routes = case.extra_routes.clone();
+    routes.push(Route::new("POST", "/transcript", 200, case.body));
+    let (result, requests) = run(&case, routes).await;
+    assert_eq!(result.unwrap().language.as_deref(), Some("en"));
+    let paths: Vec<&str> = requests.iter().map(|r| r.target.as_str()).collect();
+    assert_eq!(paths, ["/upload", "/transcript", "/transcript/t-1"]);
+}
+
+#[tokio::test]
+async fn unauthorized_is_classified() {
+    let errors = run_all_with_response(401, r#"{"error":"invalid api key"}"#, &[]).await;
+    for (model_id, e) in errors {
+        assert!(
+            matches!(
+                e.downcast_ref::<TranscriptionError>(),
+                Some(TranscriptionError::Unauthorized { .. })
+            ),
+            "{model_id}: {e:#}"
+        );
+        assert_eq!(TranscriptionError::exit_code_for(&e), 3, "{model_id}");
+    }
+}
+
+#[tokio::test]
+async fn rate_limits_are_classified_with_retry_after() {
+    let errors = run_all_with_response(
+        429,
+        r#"{"error":"rate limit exceeded"}"#,
+        &[("Retry-After", "7")],
+    )
+    .await;
+    for (model_id, e) in errors {
+        match e.downcast_ref::<TranscriptionError>() {
+            Some(TranscriptionError::RateLimited { retry_after, .. }) => {
+                assert_eq!(retry_after.map(|d| d.as_secs()), Some(7), "{model_id}");
+            }
+            other => panic!("{model_id}: expected a rate limit, got {other:?}"),
+        }
+        assert!(TranscriptionError::is_transient_error(&e), "{model_id}");
+    }
+}
+
+#[tokio::test]
+async fn server_errors_are_classified() {
+    let errors = run_all_with_response(500, "internal error", &[]).await;
+    for (model_id, e) in errors {
+        assert!(
+            matches!(
+                e.downcast_ref::<TranscriptionError>(),
+                Some(TranscriptionError::Server { .. })
+            ),
+            "{model_id}: {e:#}"
+        );
+        assert!(TranscriptionError::is_transient_error(&e), "{model_id}");
+    }
+}
+
+#[tokio::test]
+async fn failed_jobs_exit_with_their_own_code() {
+    let failures = [
+        (
+            "assemblyai-universal-3-pro",
+            Route::new(
+                "GET",
+                "/transcript/t-1",
+                200,
+                r#"{"id":"t-1","status":"error","error":"Audio file is empty"}"#,
+            ),
+        ),
+        (
+            "speechmatics-enhanced",
+            Route::new(
+                "GET",
+                "/jobs/j-1",
+                200,
+                r#"{"job":{"id":"j-1","status":"rejected","errors":[{"message":"bad audio"}]}}"#,
+            ),
+        ),
+        (
+            "gladia-solaria",
+            Route::new(
+                "GET",
+                "/pre-recorded/g-1",
+                200,
+                r#"{"id":"g-1","status":"error","error_code":422,"result":null}"#,
+            ),
+        ),
+    ];
+    for (model_id, failed_poll) in failures {
+        let case = cases()
+            .into_iter()
+            .find(|c| c.model_id == model_id)
+            .unwrap();
+        // The failed poll answer comes first, so it wins over the case's successful one
+        let mut routes = vec![failed_poll];
+        routes.extend(case.extra_routes.clone());
+        routes.push(Route::new(case.method, case.path, 200, case.body));
+
+        let (result, _) = run(&case, routes).await;
+        let e = result.unwrap_err();
+        assert!(
+            matches!(
+                e.downcast_ref::<TranscriptionError>(),
+                Some(TranscriptionError::JobFailed { .. })
+            ),
+            "{model_id}: {e:#}"
+        );
+        assert_eq!(
+            TranscriptionError::exit_code_for(&e),
+            exit_code::JOB_FAILED,
+            "{model_id}"
+        );
+        assert!(!TranscriptionError::is_transient_error(&e), "{model_id}");
+    }
+}
+
+#[tokio::test]
+async fn malformed_json_is_reported() {
+    let errors = run_all_with_response(200, "{not json", &[]).await;
+    for (model_id, e) in errors {
+        assert!(
+            format!("{e:#}").contains("Failed to parse"),
+            "{model_id}: {e:#}"
+        );
+        assert!(
+            e.downcast_ref::<TranscriptionError>().is_none(),
+            "{model_id}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn unreachable_servers_are_network_errors() {
+    // Bind and drop a listener to get a local port that refuses connections
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let mut providers = ProvidersConfig::default();
+    providers.base_urls.insert("openai".to_string(), url);
+    let config = TranscriptionConfig::new(
+        TranscriptionModel::from_id("whisper").unwrap(),
+        "test-key".to_string(),
+        Vec::new(),
+        providers,
+    );
+    let e = registry()
+        .get("openai")
+        .unwrap()
+        .transcribe(&config, &audio_file())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            e.downcast_ref::<TranscriptionError>(),
+            Some(TranscriptionError::Network { .. })
+        ),
+        "{e:#}"
+    );
+}
+
+#[tokio::test]
+async fn network_settings_apply_to_provider_requests() {
+    let proxy = StubServer::start(vec![Route::new(
+        "POST",
+        "/audio/transcriptions",
+        200,
+        r#"{"text":"through the proxy"}"#,
+    )])
+    .await;
+
+    // The host does not resolve, so the request only succeeds through the proxy
+    let mut providers = ProvidersConfig::default();
+    providers.base_urls.insert(
+        "openai".to_string(),
+        "http://transcription.invalid".to_string(),
+    );
+    let mut config = TranscriptionConfig::new(
+        TranscriptionModel::from_id("whisper").unwrap(),
+        "test-key".to_string(),
+        Vec::new(),
+        providers,
+    );
+    config.network = NetworkConfig {
+        proxy: Some(proxy.url.clone()),
+        user_agent: Some("ostt-test/1.0".to_string()),
+        request_timeout_secs: Some(10),
+        ..Default::default()
+    };
+
+    let response = registry()
+        .get("openai")
+        .unwrap()
+        .transcribe(&config, &audio_file())
+        .await
+        .unwrap();
+    assert_eq!(response.text, "through the proxy");
+
+    let requests = proxy.requests();
+    assert!(requests[0]
+        .target
+        .starts_with("http://transcription.invalid/audio/transcriptions"));
+    assert!(requests[0]
+        .headers
+        .iter()
+        .any(|h| h == "user-agent: ostt-test/1.0"));
+}
+
+#[tokio::test]
+async fn model_lists_are_fetched_from_openai_compatible_providers() {
+    let list = r#"{"object":"list","data":[{"id":"whisper-large-v3"},{"id":"distil-whisper-large-v3-en"},{"id":"llama-3.3-70b-versatile"}]}"#;
+    let cases = [
+        (
+            "openai",
+            "/models",
+            vec!["whisper-large-v3", "distil-whisper-large-v3-en"],
+        ),
+        (
+            "groq",
+            "/models",
+            vec!["whisper-large-v3", "distil-whisper-large-v3-en"],
+        ),
+        (
+            "deepinfra",
+            "/openai/models",
+            vec!["whisper-large-v3", "distil-whisper-large-v3-en"],
+        ),
+        // Custom servers only list what they serve, so nothing is filtered out
+        (
+            "custom",
+            "/models",
+            vec![
+                "whisper-large-v3",
+                "distil-whisper-large-v3-en",
+                "llama-3.3-70b-versatile",
+            ],
+        ),
+    ];
+
+    for (provider_id, path, expected) in cases {
+        let server = StubServer::start(vec![Route::new("GET", path, 200, list)]).await;
+        let mut providers = ProvidersConfig::default();
+        providers
+            .base_urls
+            .insert(provider_id.to_string(), server.url.clone());
+        let provider = registry().get(provider_id).unwrap();
+
+        let models = discovery::fetch(
+            provider.as_ref(),
+            "test-key".to_string(),
+            &providers,
+            &NetworkConfig::default(),
+        )
+        .await
+        .unwrap_or_else(|e| panic!("{provider_id}: {e:#}"));
+        assert_eq!(
+            models,
+            Some(expected.iter().map(|m| m.to_string()).collect()),
+            "{provider_id}"
+        );
+
+        let requests = server.requests();
+        assert!(
+            requests[0]
+                .headers
+                .iter()
+                .any(|h| h == "authorization: bearer test-key"),
+            "{provider_id}"
+        );
+    }
+
+    // Providers without a models endpoint are not asked
+    let deepgram = registry().get("deepgram").unwrap();
+    let models = discovery::fetch(
+        deepgram.as_ref(),
+        "test-key".to_string(),
+        &ProvidersConfig::default(),
+        &NetworkConfig::default(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(models, None);
+}